@@ -17,7 +17,7 @@ pub struct ByteArrayCursor<'data>
 impl ByteArrayCursor<'_>
 {
     /// Creates a `ByteArrayCursor`.
-    pub fn new(bytes: &[u8]) -> ByteArrayCursor { ByteArrayCursor { bytes, offset: 0 } }
+    pub fn new(bytes: &[u8]) -> ByteArrayCursor<'_> { ByteArrayCursor { bytes, offset: 0 } }
 
     fn is_end_of_data(&self, index: usize) -> bool { index >= self.bytes.len() }
 }
@@ -103,7 +103,7 @@ mod tests
 {
     use super::*;
 
-    fn new_cursor(bytes: &[u8]) -> ByteArrayCursor { ByteArrayCursor::new(bytes) }
+    fn new_cursor(bytes: &[u8]) -> ByteArrayCursor<'_> { ByteArrayCursor::new(bytes) }
 
     byte_cursor_tests!(new_cursor);
 }
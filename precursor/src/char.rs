@@ -590,7 +590,7 @@ pub trait CharCursor<'data>: Clone + PartialOrd
 
     /// Returns the bytes that represent the token currently pointed at by this cursor.  
     /// If the current position does not represent a valid token (for example a bad character encoding) an error is returned.
-    fn token_bytes(&self) -> CursorResult<Cow<[u8]>>;
+    fn token_bytes(&self) -> CursorResult<Cow<'_, [u8]>>;
 
     /// Returns the index (zero-based) of the position this cursor represents in the resource.  If the resource is at the end of data
     /// this will be the length of the resource in bytes.
@@ -133,10 +133,8 @@ impl Regex
         let cursor = cursor_in.clone();
         let mut stack = vec![(self.states[StateId(0)], cursor)];
 
-        while !stack.is_empty()
+        while let Some((state, mut cursor)) = stack.pop()
         {
-            let (state, mut cursor) = stack.pop().unwrap();
-
             match state
             {
                 State::Alternation(id) =>
@@ -234,7 +234,7 @@ impl<'data, BC: ByteCursor<'data>> CharCursor<'data> for Utf8CharCursor<'data, B
         }
     }
 
-    fn token_bytes(&self) -> CursorResult<Cow<[u8]>>
+    fn token_bytes(&self) -> CursorResult<Cow<'_, [u8]>>
     {
         let (_, end) = self.char_token()?;
         self.bytes.between(&end)
@@ -312,7 +312,7 @@ mod tests
 
     use super::*;
 
-    fn new_cursor(s: &str, eols: LineEndings) -> Utf8CharCursor<ByteArrayCursor>
+    fn new_cursor(s: &str, eols: LineEndings) -> Utf8CharCursor<'_, ByteArrayCursor<'_>>
     {
         let bytes = ByteArrayCursor::new(s.as_bytes());
         Utf8CharCursor::new(bytes, eols)
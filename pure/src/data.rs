@@ -1,10 +1,32 @@
 // // Copyright 2022 Dave Wathen. All rights reserved.
 
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use precursor::byte::ByteCursor;
 
 use crate::{pure_type::Typed, *};
 
+pub mod date;
+pub mod lambda;
+pub mod list;
+pub mod map;
+pub mod measure;
 pub mod model;
+pub mod pair;
+
+use self::date::{PureDate, PureTime};
+use self::lambda::Lambda;
+use self::list::PureList;
+use self::map::PureMap;
+use self::measure::Quantity;
+use self::pair::PurePair;
 
 pub const ZERO_NIL: Collection = Collection { pure_type: Type::Nil, multiplicity: PURE_ZERO, contents: CollectionContents::Zero };
 
@@ -14,6 +36,52 @@ pub enum Value
     Boolean(bool),
     Integer(i64),
     Float(f64),
+    Decimal(BigDecimal),
+    String(String),
+    Binary(Arc<[u8]>),
+    Date(PureDate),
+    StrictTime(PureTime),
+    LatestDate,
+    Enum(EnumValue),
+    Instance(Arc<Instance>),
+    Quantity(Quantity),
+    Lambda(Lambda),
+    Pair(PurePair),
+    List(PureList),
+    Map(PureMap),
+}
+
+/// A value of a user defined enumeration.  Values of an enumeration are created by the `Model` that defines it.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EnumValue
+{
+    pub(crate) enumeration: model::ElementId,
+    pub(crate) ordinal: usize,
+    pub(crate) name: Arc<str>,
+}
+
+impl EnumValue
+{
+    pub fn enumeration(&self) -> model::ElementId { self.enumeration }
+    pub fn ordinal(&self) -> usize { self.ordinal }
+    pub fn name(&self) -> &str { &self.name }
+}
+
+/// An instance of a user defined class holding a value for each of the class's properties.  Instances are created by the `Model`
+/// that defines the class so that their values are checked against the properties.
+#[derive(Debug, PartialEq)]
+pub struct Instance
+{
+    pub(crate) class: model::ElementId,
+    pub(crate) values: Vec<(Arc<str>, Collection)>,
+}
+
+impl Instance
+{
+    pub fn class(&self) -> model::ElementId { self.class }
+
+    /// Returns the value of the named property, or `None` if the class has no such property.
+    pub fn get(&self, property: &str) -> Option<&Collection> { self.values.iter().find(|(name, _)| &**name == property).map(|(_, value)| value) }
 }
 
 impl Value
@@ -22,6 +90,260 @@ impl Value
     {
         Collection { pure_type: self.pure_type(), multiplicity: PURE_ONE, contents: CollectionContents::One(self) }
     }
+
+    /// Creates a Decimal value from its text, which may be written as a Pure literal (e.g. `12.50D`).  The scale of the text is
+    /// preserved so `12.50D` has a scale of 2.
+    pub fn decimal(text: &str) -> PureExecutionResult<Value>
+    {
+        let digits = text.strip_suffix(['D', 'd']).unwrap_or(text);
+        let valid = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+        match BigDecimal::from_str(digits)
+        {
+            Ok(d) if valid => Ok(Value::Decimal(d)),
+            _ => Err(PureExecutionError::IllegalValue { pure_type: Type::Decimal, value: text.to_string() }),
+        }
+    }
+
+    /// Creates a date value from a Pure date or time literal (e.g. `%2022-03-01T10:15:00.000+0000`, `%10:15` or `%latest`).  The type
+    /// of the value follows from the precision of the literal.
+    pub fn date(text: &str) -> PureExecutionResult<Value>
+    {
+        let literal = text.strip_prefix('%').unwrap_or(text);
+        if literal == "latest"
+        {
+            Ok(Value::LatestDate)
+        }
+        else if literal.as_bytes().get(2) == Some(&b':')
+        {
+            Ok(Value::StrictTime(text.parse()?))
+        }
+        else
+        {
+            Ok(Value::Date(text.parse()?))
+        }
+    }
+
+    /// Creates a Binary value from the bytes that lie between two cursors over the same resource.
+    pub fn binary_between<'data, C: ByteCursor<'data>>(from: &C, to: &C) -> PureExecutionResult<Value>
+    {
+        Ok(Value::Binary(Arc::from(from.between(to)?)))
+    }
+
+    /// Returns this value as a `BigDecimal` if it is a number that can be represented exactly.  Floats are converted using their
+    /// shortest decimal representation, so `0.1` becomes `0.1` rather than its binary expansion.
+    pub fn to_decimal(&self) -> Option<BigDecimal>
+    {
+        match self
+        {
+            Value::Integer(i) => Some(BigDecimal::from(*i)),
+            Value::Float(f) if f.is_finite() => BigDecimal::from_str(&f.to_string()).ok(),
+            Value::Decimal(d) => Some(d.clone()),
+            _ => None,
+        }
+    }
+
+    /// Compares two numbers of any numeric type exactly, as their values given by `to_decimal`, except that infinite Floats are
+    /// greater or less than any Integer.  Returns `None` if either value is not a number, a Float is NaN or an infinite Float is
+    /// compared with a Decimal.
+    pub fn compare_numbers(&self, other: &Value) -> Option<Ordering>
+    {
+        // Integers up to 2^53 are exactly Floats, so are ordered against Floats as Floats
+        let exact = |i: &i64| i.unsigned_abs() <= 1 << 53;
+        match (self, other)
+        {
+            (Value::Integer(l), Value::Integer(r)) => Some(l.cmp(r)),
+            (Value::Float(l), Value::Float(r)) => l.partial_cmp(r),
+            (Value::Integer(l), Value::Float(r)) if exact(l) => (*l as f64).partial_cmp(r),
+            (Value::Float(l), Value::Integer(r)) if exact(r) => l.partial_cmp(&(*r as f64)),
+            (Value::Float(l), Value::Integer(_)) if l.is_infinite() => Some(l.total_cmp(&0.0)),
+            (Value::Integer(_), Value::Float(r)) if r.is_infinite() => Some(0.0_f64.total_cmp(r)),
+            _ => Some(self.to_decimal()?.cmp(&other.to_decimal()?)),
+        }
+    }
+
+    /// Tests equality as Pure does: numbers of different types are equal when they have the same value, so `1`, `1.0` and `1.0D`
+    /// are all equal, while all other values are equal only to values of the same type.  Pairs, lists and maps are equal when their
+    /// contents are equal.
+    pub fn equals(&self, other: &Value) -> bool
+    {
+        match (self, other)
+        {
+            (Value::Integer(_) | Value::Float(_) | Value::Decimal(_), Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) =>
+            {
+                self.compare_numbers(other) == Some(Ordering::Equal)
+            }
+            (Value::Quantity(l), Value::Quantity(r)) => l.unit() == r.unit() && l.amount().equals(r.amount()),
+            (Value::Pair(l), Value::Pair(r)) => l.first().equals(r.first()) && l.second().equals(r.second()),
+            (Value::List(l), Value::List(r)) => l.values().len() == r.values().len() && l.values().iter().zip(r.values()).all(|(l, r)| l.equals(r)),
+            (Value::Map(l), Value::Map(r)) => l.len() == r.len() && l.iter().all(|(key, value)| r.get(key).is_some_and(|v| value.equals(v))),
+            _ => self == other,
+        }
+    }
+
+    /// Writes this value as Pure's `toString` does.  Floats are written as Java writes doubles, so `1.0` keeps its decimal point and
+    /// very large or small floats use scientific notation such as `1.0E20`.
+    pub fn to_pure_string(&self) -> String
+    {
+        match self
+        {
+            Value::Float(f) if f.is_nan() => "NaN".to_string(),
+            Value::Float(f) if f.is_infinite() => if *f > 0.0 { "Infinity" } else { "-Infinity" }.to_string(),
+            Value::Float(f) if *f == 0.0 || (1e-3..1e7).contains(&f.abs()) => format!("{f:?}"),
+            Value::Float(f) =>
+            {
+                let scientific = format!("{f:e}");
+                let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+                let point = if mantissa.contains('.') { "" } else { ".0" };
+                format!("{mantissa}{point}E{exponent}")
+            }
+            other => other.to_string(),
+        }
+    }
+
+    /// Compares two values of the same kind: numbers of any numeric type, dates, strings, booleans, binaries or values of the same
+    /// enumeration, which are ordered as they are declared.  The order is total for each kind, so that values can be sorted: as in
+    /// Java, NaN is greater than any other number and equal only to itself.  Returns `None` if the values cannot be compared.
+    pub fn compare(&self, other: &Value) -> Option<Ordering>
+    {
+        let number = |v: &Value| matches!(v, Value::Integer(_) | Value::Float(_) | Value::Decimal(_));
+        match (self, other)
+        {
+            (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            (Value::Binary(l), Value::Binary(r)) => Some(l.cmp(r)),
+            (Value::Enum(l), Value::Enum(r)) if l.enumeration == r.enumeration => Some(l.ordinal.cmp(&r.ordinal)),
+            (Value::Float(l), Value::Float(r)) if l.is_nan() || r.is_nan() => Some(l.is_nan().cmp(&r.is_nan())),
+            (Value::Float(l), r) if l.is_nan() && number(r) => Some(Ordering::Greater),
+            (l, Value::Float(r)) if r.is_nan() && number(l) => Some(Ordering::Less),
+            // Only an infinite Float cannot be compared with a Decimal
+            (Value::Float(l), Value::Decimal(_)) if l.is_infinite() => Some(l.total_cmp(&0.0)),
+            (Value::Decimal(_), Value::Float(r)) if r.is_infinite() => Some(0.0_f64.total_cmp(r)),
+            (l, _) if number(l) => self.compare_numbers(other),
+            _ => self.compare_dates(other),
+        }
+    }
+
+    /// Compares two dates.  `%latest` stands for a date later than any concrete date, as it does when querying milestoned data, and
+    /// is equal only to itself.  Returns `None` if either value is not a date or a `StrictTime` is compared with a date.
+    pub fn compare_dates(&self, other: &Value) -> Option<Ordering>
+    {
+        match (self, other)
+        {
+            (Value::LatestDate, Value::LatestDate) => Some(Ordering::Equal),
+            (Value::LatestDate, Value::Date(_)) => Some(Ordering::Greater),
+            (Value::Date(_), Value::LatestDate) => Some(Ordering::Less),
+            (Value::Date(l), Value::Date(r)) => Some(l.cmp(r)),
+            (Value::StrictTime(l), Value::StrictTime(r)) => Some(l.cmp(r)),
+            _ => None,
+        }
+    }
+}
+
+/// A value that is compared and hashed as Pure tests values for equality, so that values can be collected into a `HashSet` or used
+/// as the keys of a `HashMap`.  Keys are equal when their values are `equals`, except that NaN is equal to itself so that NaNs are
+/// not all distinct from each other.  Numbers are hashed by their exact values, which are the same for all numbers that are equal.
+#[derive(Debug, Clone)]
+pub struct ValueKey<V>(pub V);
+
+impl<V: Borrow<Value>> PartialEq for ValueKey<V>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        let (left, right) = (self.0.borrow(), other.0.borrow());
+        match (left, right)
+        {
+            (Value::Float(l), Value::Float(r)) if l.is_nan() && r.is_nan() => true,
+            _ => left.equals(right),
+        }
+    }
+}
+
+impl<V: Borrow<Value>> Eq for ValueKey<V> {}
+
+impl<V: Borrow<Value>> Hash for ValueKey<V>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) { hash_value(self.0.borrow(), state) }
+}
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H)
+{
+    if hash_number(value, state)
+    {
+        return;
+    }
+
+    std::mem::discriminant(value).hash(state);
+    match value
+    {
+        Value::Boolean(b) => b.hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Binary(b) => b.hash(state),
+        Value::Date(d) => d.hash(state),
+        Value::StrictTime(t) => t.hash(state),
+        Value::Enum(e) => e.hash(state),
+        Value::Instance(i) =>
+        {
+            i.class.hash(state);
+            for (name, values) in &i.values
+            {
+                name.hash(state);
+                values.into_iter().for_each(|v| hash_value(v, state));
+            }
+        }
+        Value::Quantity(q) =>
+        {
+            q.unit().id().hash(state);
+            hash_value(q.amount(), state);
+        }
+        Value::Pair(p) =>
+        {
+            hash_value(p.first(), state);
+            hash_value(p.second(), state);
+        }
+        Value::List(l) => l.values().iter().for_each(|v| hash_value(v, state)),
+        // The entries of a map are in no particular order, so their hashes are combined in a way that does not depend on it
+        Value::Map(m) =>
+        {
+            let entries = m.iter().fold(0, |hashes, (key, value)| {
+                let mut hasher = DefaultHasher::new();
+                hash_value(key, &mut hasher);
+                hash_value(value, &mut hasher);
+                hashes ^ hasher.finish()
+            });
+            m.len().hash(state);
+            entries.hash(state);
+        }
+        // Lambdas are equal only to themselves so are hashed alike
+        _ => (),
+    }
+}
+
+/// Hashes a number by its exact value, as an Integer when it is one so that Integers need not be converted.  Returns whether the
+/// value was a number.
+fn hash_number<H: Hasher>(value: &Value, state: &mut H) -> bool
+{
+    match value
+    {
+        Value::Integer(i) => i.hash(state),
+        // Zero and negative zero are equal, and whole Floats up to 2^53 are exactly Integers
+        Value::Float(f) if f.fract() == 0.0 && f.abs() <= 9007199254740992.0 => (*f as i64).hash(state),
+        // All NaNs are hashed alike
+        Value::Float(f) if f.is_nan() => f64::NAN.to_bits().hash(state),
+        Value::Float(f) if f.is_infinite() => f.to_bits().hash(state),
+        Value::Float(_) | Value::Decimal(_) =>
+        {
+            if let Some(d) = value.to_decimal()
+            {
+                match d.to_i64().filter(|_| d.is_integer())
+                {
+                    Some(i) => i.hash(state),
+                    None => d.hash(state),
+                }
+            }
+        }
+        _ => return false,
+    }
+    true
 }
 
 macro_rules! impl_from {
@@ -68,6 +390,71 @@ impl_try_from!(u128, Integer, i64);
 impl_from!(f32, Float, f64);
 impl_from!(f64, Float, f64);
 
+impl From<BigDecimal> for Value
+{
+    fn from(v: BigDecimal) -> Self { Value::Decimal(v) }
+}
+
+impl From<Arc<[u8]>> for Value
+{
+    fn from(v: Arc<[u8]>) -> Self { Value::Binary(v) }
+}
+
+impl From<Vec<u8>> for Value
+{
+    fn from(v: Vec<u8>) -> Self { Value::Binary(Arc::from(v)) }
+}
+
+impl From<&[u8]> for Value
+{
+    fn from(v: &[u8]) -> Self { Value::Binary(Arc::from(v)) }
+}
+
+impl From<PureDate> for Value
+{
+    fn from(v: PureDate) -> Self { Value::Date(v) }
+}
+
+impl From<PureTime> for Value
+{
+    fn from(v: PureTime) -> Self { Value::StrictTime(v) }
+}
+
+impl From<Quantity> for Value
+{
+    fn from(v: Quantity) -> Self { Value::Quantity(v) }
+}
+
+impl From<Lambda> for Value
+{
+    fn from(v: Lambda) -> Self { Value::Lambda(v) }
+}
+
+impl From<PurePair> for Value
+{
+    fn from(v: PurePair) -> Self { Value::Pair(v) }
+}
+
+impl From<PureList> for Value
+{
+    fn from(v: PureList) -> Self { Value::List(v) }
+}
+
+impl From<PureMap> for Value
+{
+    fn from(v: PureMap) -> Self { Value::Map(v) }
+}
+
+impl From<String> for Value
+{
+    fn from(v: String) -> Self { Value::String(v) }
+}
+
+impl From<&str> for Value
+{
+    fn from(v: &str) -> Self { Value::String(v.to_owned()) }
+}
+
 macro_rules! impl_try_from_value {
     ($for:ty, $pure_type:ident) => {
         impl TryFrom<Value> for $for
@@ -90,6 +477,38 @@ macro_rules! impl_try_from_value {
 
 impl_try_from_value!(bool, Boolean);
 impl_try_from_value!(i64, Integer);
+impl_try_from_value!(BigDecimal, Decimal);
+impl_try_from_value!(String, String);
+impl_try_from_value!(PureTime, StrictTime);
+
+impl TryFrom<Value> for Arc<[u8]>
+{
+    type Error = PureExecutionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error>
+    {
+        match value
+        {
+            Value::Binary(b) => Ok(b),
+            _ => Err(PureExecutionError::WrongType { expected: Type::Binary, found: value.pure_type() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for PureDate
+{
+    type Error = PureExecutionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error>
+    {
+        match value
+        {
+            Value::Date(d) => Ok(d),
+            Value::LatestDate => Err(PureExecutionError::IllegalCast { from: "%latest".into(), to: "a concrete date".into() }),
+            _ => Err(PureExecutionError::WrongType { expected: Type::Date, found: value.pure_type() }),
+        }
+    }
+}
 
 impl TryFrom<Value> for f64
 {
@@ -101,6 +520,7 @@ impl TryFrom<Value> for f64
         {
             Value::Float(f) => Ok(f),
             Value::Integer(i) => Ok(i as f64),
+            Value::Decimal(ref d) => d.to_f64().ok_or_else(|| PureExecutionError::IllegalCast { from: value.to_string(), to: "Float".into() }),
             _ => Err(PureExecutionError::WrongType { expected: Type::Number, found: value.pure_type() }),
         }
     }
@@ -115,6 +535,19 @@ impl Display for Value
             Value::Boolean(v) => write!(f, "{v}"),
             Value::Integer(v) => write!(f, "{v}"),
             Value::Float(v) => write!(f, "{v}"),
+            Value::Decimal(v) => write!(f, "{}", v.to_plain_string()),
+            Value::String(v) => write!(f, "{v}"),
+            Value::Binary(v) => write!(f, "{}", hex::encode(v)),
+            Value::Date(v) => write!(f, "{v}"),
+            Value::StrictTime(v) => write!(f, "{v}"),
+            Value::LatestDate => write!(f, "latest"),
+            Value::Enum(v) => write!(f, "{}", v.name),
+            Value::Instance(v) => write!(f, "<{} instance>", Type::Class(v.class)),
+            Value::Quantity(v) => write!(f, "{v}"),
+            Value::Lambda(v) => write!(f, "{v}"),
+            Value::Pair(v) => write!(f, "{v}"),
+            Value::List(v) => write!(f, "{v}"),
+            Value::Map(v) => write!(f, "{v}"),
         }
     }
 }
@@ -128,6 +561,31 @@ impl pure_type::Typed for Value
             Self::Boolean(_) => Type::Boolean,
             Self::Integer(_) => Type::Integer,
             Self::Float(_) => Type::Float,
+            Self::Decimal(_) => Type::Decimal,
+            Self::String(_) => Type::String,
+            Self::Binary(_) => Type::Binary,
+            Self::Date(d) => d.pure_type(),
+            Self::StrictTime(_) => Type::StrictTime,
+            Self::LatestDate => Type::LatestDate,
+            Self::Enum(v) => Type::Enum(v.enumeration),
+            Self::Instance(v) => Type::Class(v.class),
+            Self::Quantity(v) => v.pure_type(),
+            Self::Lambda(v) => v.pure_type(),
+            Self::Pair(_) => Type::Pair,
+            Self::List(_) => Type::List,
+            Self::Map(_) => Type::Map,
+        }
+    }
+
+    fn generic_type(&self) -> GenericType
+    {
+        match self
+        {
+            Self::Lambda(v) => v.generic_type(),
+            Self::Pair(v) => v.generic_type(),
+            Self::List(v) => v.generic_type(),
+            Self::Map(v) => v.generic_type(),
+            _ => GenericType::from(self.pure_type()),
         }
     }
 }
@@ -311,6 +769,313 @@ mod tests
         Ok(())
     }
 
+    #[test]
+    fn decimal_value() -> PureExecutionResult<()>
+    {
+        let d = Value::decimal("12.50D")?;
+        assert_eq!(pure_type::Type::Decimal, d.pure_type());
+        assert_eq!("12.50", d.to_string());
+        assert_eq!(12.5, <Value as TryInto<f64>>::try_into(d.clone())?);
+
+        let big: BigDecimal = d.try_into()?;
+        assert_eq!(2, big.fractional_digit_count());
+        assert_eq!(4, big.digits());
+
+        assert_eq!("123456789012345678901234567890.123456789", Value::decimal("123456789012345678901234567890.123456789")?.to_string());
+        assert_eq!("-0.001", Value::decimal("-0.001d")?.to_string());
+        assert_eq!("7", Value::from(BigDecimal::from(7)).to_string());
+
+        let bad = Value::decimal("1.2.3");
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Decimal: 1.2.3", bad.err().unwrap().to_string());
+
+        let bad = Value::decimal("0x12");
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Decimal: 0x12", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_numbers() -> PureExecutionResult<()>
+    {
+        assert_eq!(Some(Ordering::Less), Value::from(1).compare_numbers(&2.into()));
+        assert_eq!(Some(Ordering::Equal), Value::from(1).compare_numbers(&1.0.into()));
+        assert_eq!(Some(Ordering::Greater), Value::from(1.5).compare_numbers(&1.into()));
+        assert_eq!(Some(Ordering::Equal), Value::decimal("1.00")?.compare_numbers(&1.into()));
+        assert_eq!(Some(Ordering::Equal), Value::decimal("0.1")?.compare_numbers(&0.1.into()));
+        assert_eq!(Some(Ordering::Less), Value::decimal("0.1")?.compare_numbers(&Value::decimal("0.10000000000000000001")?));
+        assert_eq!(None, Value::from(f64::NAN).compare_numbers(&1.into()));
+        assert_eq!(None, Value::decimal("1")?.compare_numbers(&f64::INFINITY.into()));
+        assert_eq!(None, Value::from(true).compare_numbers(&1.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_value() -> PureExecutionResult<()>
+    {
+        let s: Value = "hello".into();
+        assert_eq!(pure_type::Type::String, s.pure_type());
+        assert_eq!("hello", s.to_string());
+        assert_eq!("hello".to_string(), <Value as TryInto<String>>::try_into(s)?);
+
+        let s: Value = String::from("world").into();
+        assert_eq!(Value::String("world".into()), s);
+
+        let bad = <Value as TryInto<String>>::try_into(1.into());
+        assert!(bad.is_err());
+        assert_eq!("Values ois of the wrong type: expected String, found: Integer", bad.err().unwrap().to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn binary_value() -> PureExecutionResult<()>
+    {
+        let b: Value = vec![0xde_u8, 0xad, 0xbe, 0xef].into();
+        assert_eq!(pure_type::Type::Binary, b.pure_type());
+        assert_eq!("deadbeef", b.to_string());
+
+        let shared: Arc<[u8]> = b.clone().try_into()?;
+        assert_eq!(&[0xde, 0xad, 0xbe, 0xef], &shared[..]);
+        let copy = Value::from(shared.clone());
+        assert!(matches!(copy, Value::Binary(ref bytes) if Arc::ptr_eq(bytes, &shared)));
+        assert_eq!(b, copy);
+
+        let bad = <Value as TryInto<Arc<[u8]>>>::try_into("deadbeef".into());
+        assert!(bad.is_err());
+        assert_eq!("Values ois of the wrong type: expected Binary, found: String", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_from_cursor() -> PureExecutionResult<()>
+    {
+        use precursor::byte::ByteArrayCursor;
+
+        let data = [0x00_u8, 0x01, 0x02, 0x03, 0x04, 0x05];
+        let mut from = ByteArrayCursor::new(&data);
+        from.advance()?;
+        let mut to = from.clone();
+        to.advance_many(3)?;
+
+        assert_eq!(Value::from(&data[1..4]), Value::binary_between(&from, &to)?);
+        assert_eq!(Value::from(&data[1..4]), Value::binary_between(&to, &from)?);
+        assert_eq!(Value::from(vec![]), Value::binary_between(&from, &from)?);
+
+        let other = [0x00_u8];
+        let bad = Value::binary_between(&from, &ByteArrayCursor::new(&other));
+        assert!(bad.is_err());
+        assert_eq!("Cursor error: The supplied cursors is not compatible (e.g. a child of) this cursor", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_values() -> PureExecutionResult<()>
+    {
+        let d = Value::date("%2022-03")?;
+        assert_eq!(pure_type::Type::Date, d.pure_type());
+        assert_eq!("2022-03", d.to_string());
+
+        let d = Value::date("%2022-03-01")?;
+        assert_eq!(pure_type::Type::StrictDate, d.pure_type());
+        assert_eq!("%2022-03-01", <Value as TryInto<PureDate>>::try_into(d)?.literal());
+
+        let d = Value::date("%2022-03-01T10:15:00.000+0000")?;
+        assert_eq!(pure_type::Type::DateTime, d.pure_type());
+        assert_eq!("2022-03-01T10:15:00.000+0000", d.to_string());
+
+        let t = Value::date("%10:15")?;
+        assert_eq!(pure_type::Type::StrictTime, t.pure_type());
+        assert_eq!("10:15", t.to_string());
+        assert_eq!(PureTime::new(10, 15, None, None)?, t.try_into()?);
+
+        let bad = Value::date("%2022-02-30");
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Date: %2022-02-30", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn latest_date() -> PureExecutionResult<()>
+    {
+        let latest = Value::date("%latest")?;
+        assert_eq!(Value::LatestDate, latest);
+        assert_eq!(pure_type::Type::LatestDate, latest.pure_type());
+        assert_eq!("latest", latest.to_string());
+
+        let bad = <Value as TryInto<PureDate>>::try_into(latest);
+        assert!(bad.is_err());
+        assert_eq!("Cast exception: %latest cannot be cast to a concrete date", bad.err().unwrap().to_string());
+
+        let dates = CollectionBuilder::new(Type::Date, ZERO_MANY).push(Value::date("%2022-03-01")?)?.push(Value::LatestDate)?.build()?;
+        assert_eq!(Value::from(2), dates.size()?);
+
+        let bad = CollectionBuilder::new(Type::DateTime, ZERO_MANY).push(Value::LatestDate);
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: LatestDate value cannot be assigned to DateTime", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_dates() -> PureExecutionResult<()>
+    {
+        let latest = Value::LatestDate;
+        let day = Value::date("%2022-03-01")?;
+        let far_future = Value::date("%9999-12-31T23:59:59.999+0000")?;
+
+        assert_eq!(Some(Ordering::Equal), latest.compare_dates(&Value::LatestDate));
+        assert_eq!(Some(Ordering::Greater), latest.compare_dates(&far_future));
+        assert_eq!(Some(Ordering::Less), day.compare_dates(&latest));
+        assert_eq!(Some(Ordering::Less), day.compare_dates(&far_future));
+        assert_eq!(Some(Ordering::Equal), day.compare_dates(&Value::date("2022-03-01")?));
+        assert_eq!(Some(Ordering::Less), Value::date("%10:15")?.compare_dates(&Value::date("%10:15:01")?));
+        assert_eq!(None, Value::date("%10:15")?.compare_dates(&day));
+        assert_eq!(None, latest.compare_dates(&Value::date("%10:15")?));
+        assert_eq!(None, day.compare_dates(&1.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn numbers_are_equal_across_types() -> PureExecutionResult<()>
+    {
+        assert!(Value::from(1).equals(&Value::from(1.0)));
+        assert!(Value::from(1.0).equals(&Value::decimal("1.00")?));
+        assert!(Value::decimal("1")?.equals(&Value::from(1)));
+        assert!(!Value::from(1).equals(&Value::from(1.5)));
+        assert!(!Value::from(f64::NAN).equals(&Value::from(f64::NAN)));
+        assert!(!Value::from(1).equals(&Value::from("1")));
+        assert!(Value::from("a").equals(&Value::from("a")));
+        assert!(!Value::date("%2022")?.equals(&Value::date("%2022-01-01")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn value_keys_follow_pure_equality() -> PureExecutionResult<()>
+    {
+        use std::collections::HashSet;
+
+        let values = [
+            Value::from(1),
+            Value::from(1.0),
+            Value::decimal("1.00")?,
+            Value::from(0.1),
+            Value::decimal("0.1")?,
+            Value::decimal("0.10000000000000000001")?,
+            Value::from(0.0),
+            Value::from(-0.0),
+            Value::from(f64::NAN),
+            Value::from(f64::NAN),
+            Value::from("1"),
+            Value::date("%2022")?,
+            Value::date("%2022-01-01")?,
+            Value::date("%2022")?,
+        ];
+        let distinct: HashSet<ValueKey<&Value>> = values.iter().map(ValueKey).collect();
+        assert_eq!(8, distinct.len());
+
+        assert!(distinct.contains(&ValueKey(&Value::decimal("1")?)));
+        assert!(distinct.contains(&ValueKey(&Value::from(f64::NAN))));
+        assert!(!distinct.contains(&ValueKey(&Value::from(2))));
+        assert_eq!(ValueKey(Value::from(0.1)), ValueKey(Value::decimal("0.1000")?));
+        assert_ne!(ValueKey(Value::from(1)), ValueKey(Value::from("1")));
+
+        // Equality of numbers is exact, so it is transitive
+        let (a, b, d) = (Value::from(9007199254740993_i64), Value::from(9007199254740992.0), Value::from(9007199254740992_i64));
+        assert!(!a.equals(&b) && b.equals(&d) && !a.equals(&d));
+        let keys: HashSet<ValueKey<&Value>> = [&a, &b, &d].into_iter().map(ValueKey).collect();
+        assert_eq!(2, keys.len());
+        assert_eq!(ValueKey(Value::from(1e300)), ValueKey(Value::decimal(&format!("1{}", "0".repeat(300)))?));
+        assert_eq!(ValueKey(Value::from(2.5)), ValueKey(Value::decimal("2.500")?));
+
+        // The entries of maps are hashed whatever order they were put in, and maps of the same size with different entries differ
+        let any = || GenericType::from(Type::Any);
+        let one_two = PureMap::new(any(), any(), [(1.into(), "one".into()), (2.into(), "two".into())])?;
+        let two_one = PureMap::new(any(), any(), [(2.0.into(), "two".into()), (1.0.into(), "one".into())])?;
+        let maps: HashSet<ValueKey<Value>> =
+            [one_two.clone(), two_one, one_two.put(3.into(), "three".into())?].into_iter().map(|m| ValueKey(m.into())).collect();
+        assert_eq!(2, maps.len());
+        let hash = |map: PureMap| {
+            let mut hasher = DefaultHasher::new();
+            ValueKey(Value::from(map)).hash(&mut hasher);
+            hasher.finish()
+        };
+        let one_three = PureMap::new(any(), any(), [(1.into(), "one".into()), (3.into(), "three".into())])?;
+        assert_ne!(hash(one_two), hash(one_three));
+
+        Ok(())
+    }
+
+    #[test]
+    fn pure_strings() -> PureExecutionResult<()>
+    {
+        assert_eq!("1", Value::from(1).to_pure_string());
+        assert_eq!("1.0", Value::from(1.0).to_pure_string());
+        assert_eq!("-0.0", Value::from(-0.0).to_pure_string());
+        assert_eq!("0.001", Value::from(0.001).to_pure_string());
+        assert_eq!("1234567.5", Value::from(1234567.5).to_pure_string());
+        assert_eq!("1.0E7", Value::from(1e7).to_pure_string());
+        assert_eq!("1.25E-4", Value::from(0.000125).to_pure_string());
+        assert_eq!("-1.0E20", Value::from(-1e20).to_pure_string());
+        assert_eq!("NaN", Value::from(f64::NAN).to_pure_string());
+        assert_eq!("-Infinity", Value::from(f64::NEG_INFINITY).to_pure_string());
+        assert_eq!("12.50", Value::decimal("12.50D")?.to_pure_string());
+        assert_eq!("2022-03-01", Value::date("%2022-03-01")?.to_pure_string());
+        assert_eq!("true", Value::from(true).to_pure_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn values_are_compared_by_kind() -> PureExecutionResult<()>
+    {
+        assert_eq!(Some(Ordering::Less), Value::from(1).compare(&Value::from(1.5)));
+        assert_eq!(Some(Ordering::Greater), Value::from("b").compare(&Value::from("a")));
+        assert_eq!(Some(Ordering::Less), Value::from(false).compare(&Value::from(true)));
+        assert_eq!(Some(Ordering::Less), Value::date("%2022-03-01")?.compare(&Value::date("%latest")?));
+        assert_eq!(None, Value::from(1).compare(&Value::from("1")));
+        assert_eq!(None, Value::from("1").compare(&Value::from(1)));
+        assert_eq!(None, Value::date("%10:15")?.compare(&Value::date("%2022")?));
+
+        // Numbers are totally ordered so that they can be sorted
+        assert_eq!(Some(Ordering::Greater), Value::from(f64::NAN).compare(&Value::from(f64::INFINITY)));
+        assert_eq!(Some(Ordering::Less), Value::decimal("1E400")?.compare(&Value::from(f64::NAN)));
+        assert_eq!(Some(Ordering::Equal), Value::from(f64::NAN).compare(&Value::from(f64::NAN)));
+        assert_eq!(Some(Ordering::Less), Value::from(f64::NEG_INFINITY).compare(&Value::decimal("-1E400")?));
+        assert_eq!(Some(Ordering::Less), Value::decimal("1E400")?.compare(&Value::from(f64::INFINITY)));
+        assert_eq!(Some(Ordering::Equal), Value::from(-0.0).compare(&Value::from(0)));
+        assert_eq!(Some(Ordering::Greater), Value::decimal("0.10000000000000000001")?.compare(&Value::from(0.1)));
+        assert_eq!(None, Value::from(f64::NAN).compare(&Value::from("NaN")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn dates_honour_generalizations() -> PureExecutionResult<()>
+    {
+        let dates = CollectionBuilder::new(Type::Date, ZERO_MANY)
+            .push(Value::date("%2022")?)?
+            .push(Value::date("%2022-03-01")?)?
+            .push(Value::date("%2022-03-01T10+0000")?)?
+            .build()?;
+        assert_eq!(Value::from(3), dates.size()?);
+
+        let bad = CollectionBuilder::new(Type::StrictDate, ZERO_MANY).push(Value::date("%2022-03-01T10+0000")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: DateTime value cannot be assigned to StrictDate", bad.err().unwrap().to_string());
+
+        let bad = CollectionBuilder::new(Type::Date, ZERO_MANY).push(Value::date("%10:15")?);
+        assert!(bad.is_ok());
+
+        Ok(())
+    }
+
     #[test]
     fn build_empty_nil() -> PureExecutionResult<()>
     {
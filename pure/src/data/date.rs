@@ -0,0 +1,702 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::*;
+
+/// The precision to which a date is specified.  Pure dates may be given to any precision from a year down to a subsecond.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DatePrecision
+{
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Subsecond,
+}
+
+/// A Pure date with variable precision.  Depending on its precision it is a `Date` (year or year-month), a `StrictDate` (to the day)
+/// or a `DateTime` (to the hour or finer).  Times are always held in GMT.  The year may be negative, for a date before 1 BC.
+///
+/// Dates are compared field by field, with a date that lacks a field ordered before one that has it.  A subsecond is compared by
+/// value, so the subseconds `.1` and `.10` are equal although each keeps its own digits.
+#[derive(Clone, Debug)]
+pub struct PureDate
+{
+    year: i32,
+    month: Option<u8>,
+    day: Option<u8>,
+    hour: Option<u8>,
+    minute: Option<u8>,
+    second: Option<u8>,
+    subsecond: Option<String>,
+}
+
+impl PureDate
+{
+    pub fn year_only(year: i32) -> PureDate { PureDate { year, month: None, day: None, hour: None, minute: None, second: None, subsecond: None } }
+
+    pub fn year_month(year: i32, month: u8) -> PureExecutionResult<PureDate>
+    {
+        check_range(Type::Date, month, 1, 12)?;
+        Ok(PureDate { month: Some(month), ..PureDate::year_only(year) })
+    }
+
+    pub fn strict_date(year: i32, month: u8, day: u8) -> PureExecutionResult<PureDate>
+    {
+        check_range(Type::StrictDate, month, 1, 12)?;
+        check_range(Type::StrictDate, day, 1, days_in_month(year, month))?;
+        Ok(PureDate { month: Some(month), day: Some(day), ..PureDate::year_only(year) })
+    }
+
+    /// Creates a `DateTime` to the second, or to the subsecond if the digits of the subsecond are given.
+    pub fn date_time(year: i32, month: u8, day: u8, hour: u8, minute: u8, second: u8, subsecond: Option<&str>) -> PureExecutionResult<PureDate>
+    {
+        let date = PureDate::strict_date(year, month, day)?;
+        check_range(Type::DateTime, hour, 0, 23)?;
+        check_range(Type::DateTime, minute, 0, 59)?;
+        check_range(Type::DateTime, second, 0, 59)?;
+        let subsecond = subsecond.map(|digits| check_subsecond(Type::DateTime, digits)).transpose()?;
+        Ok(PureDate { hour: Some(hour), minute: Some(minute), second: Some(second), subsecond, ..date })
+    }
+
+    pub fn precision(&self) -> DatePrecision
+    {
+        if self.subsecond.is_some()
+        {
+            DatePrecision::Subsecond
+        }
+        else if self.second.is_some()
+        {
+            DatePrecision::Second
+        }
+        else if self.minute.is_some()
+        {
+            DatePrecision::Minute
+        }
+        else if self.hour.is_some()
+        {
+            DatePrecision::Hour
+        }
+        else if self.day.is_some()
+        {
+            DatePrecision::Day
+        }
+        else if self.month.is_some()
+        {
+            DatePrecision::Month
+        }
+        else
+        {
+            DatePrecision::Year
+        }
+    }
+
+    pub fn year(&self) -> i32 { self.year }
+    pub fn month(&self) -> Option<u8> { self.month }
+    pub fn day(&self) -> Option<u8> { self.day }
+    pub fn hour(&self) -> Option<u8> { self.hour }
+    pub fn minute(&self) -> Option<u8> { self.minute }
+    pub fn second(&self) -> Option<u8> { self.second }
+    pub fn subsecond(&self) -> Option<&str> { self.subsecond.as_deref() }
+
+    /// Returns the current time, to the millisecond.
+    pub fn now() -> PureDate
+    {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = since_epoch.as_secs() as i64;
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+        let second_of_day = seconds.rem_euclid(86400);
+        PureDate {
+            year,
+            month: Some(month),
+            day: Some(day),
+            hour: Some((second_of_day / 3600) as u8),
+            minute: Some((second_of_day % 3600 / 60) as u8),
+            second: Some((second_of_day % 60) as u8),
+            subsecond: Some(format!("{:03}", since_epoch.subsec_millis())),
+        }
+    }
+
+    /// Returns the date without any time component.  Dates with a precision coarser than a day are unaffected.
+    pub fn date_part(&self) -> PureDate { PureDate { hour: None, minute: None, second: None, subsecond: None, ..self.clone() } }
+
+    /// Returns the date as a Pure literal, e.g. `%2022-03-01T10:15:00.000+0000`.
+    pub fn literal(&self) -> String { format!("%{self}") }
+
+    /// The fields by which dates are compared, with the trailing zeros of the subsecond removed.
+    fn key(&self) -> DateKey<'_> { (self.year, self.month, self.day, self.hour, self.minute, self.second, subsecond_value(&self.subsecond)) }
+
+    /// Moves a date with a time component by a number of minutes, carrying into the date as required.
+    fn shift_minutes(self, minutes: i64) -> PureDate
+    {
+        let (Some(month), Some(day), Some(hour)) = (self.month, self.day, self.hour)
+        else
+        {
+            return self;
+        };
+
+        let total = days_from_civil(self.year, month, day) * 1440 + i64::from(hour) * 60 + i64::from(self.minute.unwrap_or(0)) + minutes;
+        let (year, month, day) = civil_from_days(total.div_euclid(1440));
+        let minute_of_day = total.rem_euclid(1440);
+        PureDate {
+            year,
+            month: Some(month),
+            day: Some(day),
+            hour: Some((minute_of_day / 60) as u8),
+            minute: self.minute.map(|_| (minute_of_day % 60) as u8),
+            ..self
+        }
+    }
+}
+
+/// The year, month, day, hour, minute, second and subsecond of a date.
+type DateKey<'a> = (i32, Option<u8>, Option<u8>, Option<u8>, Option<u8>, Option<u8>, Option<&'a str>);
+
+impl PartialEq for PureDate
+{
+    fn eq(&self, other: &Self) -> bool { self.key() == other.key() }
+}
+
+impl Eq for PureDate {}
+
+impl PartialOrd for PureDate
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PureDate
+{
+    fn cmp(&self, other: &Self) -> Ordering { self.key().cmp(&other.key()) }
+}
+
+impl Hash for PureDate
+{
+    fn hash<H: Hasher>(&self, state: &mut H) { self.key().hash(state) }
+}
+
+impl pure_type::Typed for PureDate
+{
+    fn pure_type(&self) -> Type
+    {
+        match self.precision()
+        {
+            DatePrecision::Year | DatePrecision::Month => Type::Date,
+            DatePrecision::Day => Type::StrictDate,
+            _ => Type::DateTime,
+        }
+    }
+}
+
+impl fmt::Display for PureDate
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.year < 0
+        {
+            write!(f, "-")?;
+        }
+        write!(f, "{:04}", self.year.unsigned_abs())?;
+        if let Some(month) = self.month
+        {
+            write!(f, "-{month:02}")?;
+        }
+        if let Some(day) = self.day
+        {
+            write!(f, "-{day:02}")?;
+        }
+        if let Some(hour) = self.hour
+        {
+            write!(f, "T{hour:02}")?;
+            write_time_fields(f, self.minute, self.second, self.subsecond.as_deref())?;
+            write!(f, "+0000")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PureDate
+{
+    type Err = PureExecutionError;
+
+    /// Parses a date in Pure literal syntax, with or without the leading `%`.  A year before 1 BC is preceded by a `-`.  Any timezone
+    /// offset is applied so that the result is in GMT.
+    fn from_str(text: &str) -> PureExecutionResult<PureDate>
+    {
+        let illegal = || PureExecutionError::IllegalValue { pure_type: Type::Date, value: text.to_string() };
+
+        let literal = text.strip_prefix('%').unwrap_or(text);
+        let (date_part, time_part) = literal.split_once('T').map_or((literal, None), |(d, t)| (d, Some(t)));
+
+        let (sign, date_part) = date_part.strip_prefix('-').map_or((1, date_part), |d| (-1, d));
+        let mut fields = date_part.splitn(3, '-');
+        let year = fields.next().and_then(|y| parse_digits::<i32>(y, 1, 9)).ok_or_else(illegal)? * sign;
+        let month = fields.next().map(|m| parse_digits::<u8>(m, 2, 2).ok_or_else(illegal)).transpose()?;
+        let day = fields.next().map(|d| parse_digits::<u8>(d, 2, 2).ok_or_else(illegal)).transpose()?;
+
+        let date = match (month, day)
+        {
+            (None, _) => PureDate::year_only(year),
+            (Some(month), None) => PureDate::year_month(year, month).map_err(|_| illegal())?,
+            (Some(month), Some(day)) => PureDate::strict_date(year, month, day).map_err(|_| illegal())?,
+        };
+
+        match time_part
+        {
+            None => Ok(date),
+            Some(_) if day.is_none() => Err(illegal()),
+            Some(time) =>
+            {
+                let (time, offset) = match time.find(['+', '-'])
+                {
+                    Some(idx) => (&time[..idx], Some(parse_offset(&time[idx..]).ok_or_else(illegal)?)),
+                    None => (time, None),
+                };
+                let (hour, minute, second, subsecond) = parse_time_fields(time).ok_or_else(illegal)?;
+                if offset.is_some_and(|o| o % 60 != 0) && minute.is_none()
+                {
+                    return Err(illegal());
+                }
+
+                let date_time = PureDate { hour: Some(hour), minute, second, subsecond, ..date };
+                Ok(date_time.shift_minutes(-offset.unwrap_or(0)))
+            }
+        }
+    }
+}
+
+/// A Pure `StrictTime`: a time of day given to the minute, second or subsecond.  Times are compared as dates are, so a subsecond is
+/// compared by value.
+#[derive(Clone, Debug)]
+pub struct PureTime
+{
+    hour: u8,
+    minute: u8,
+    second: Option<u8>,
+    subsecond: Option<String>,
+}
+
+impl PureTime
+{
+    pub fn new(hour: u8, minute: u8, second: Option<u8>, subsecond: Option<&str>) -> PureExecutionResult<PureTime>
+    {
+        check_range(Type::StrictTime, hour, 0, 23)?;
+        check_range(Type::StrictTime, minute, 0, 59)?;
+        second.map(|s| check_range(Type::StrictTime, s, 0, 59)).transpose()?;
+        if let (Some(digits), None) = (subsecond, second)
+        {
+            return Err(PureExecutionError::IllegalValue { pure_type: Type::StrictTime, value: format!("{hour:02}:{minute:02}.{digits}") });
+        }
+        let subsecond = subsecond.map(|digits| check_subsecond(Type::StrictTime, digits)).transpose()?;
+        Ok(PureTime { hour, minute, second, subsecond })
+    }
+
+    pub fn precision(&self) -> DatePrecision
+    {
+        match (&self.second, &self.subsecond)
+        {
+            (_, Some(_)) => DatePrecision::Subsecond,
+            (Some(_), None) => DatePrecision::Second,
+            (None, None) => DatePrecision::Minute,
+        }
+    }
+
+    pub fn hour(&self) -> u8 { self.hour }
+    pub fn minute(&self) -> u8 { self.minute }
+    pub fn second(&self) -> Option<u8> { self.second }
+    pub fn subsecond(&self) -> Option<&str> { self.subsecond.as_deref() }
+
+    /// Returns the time as a Pure literal, e.g. `%10:15:00`.
+    pub fn literal(&self) -> String { format!("%{self}") }
+
+    fn key(&self) -> (u8, u8, Option<u8>, Option<&str>) { (self.hour, self.minute, self.second, subsecond_value(&self.subsecond)) }
+}
+
+impl PartialEq for PureTime
+{
+    fn eq(&self, other: &Self) -> bool { self.key() == other.key() }
+}
+
+impl Eq for PureTime {}
+
+impl PartialOrd for PureTime
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for PureTime
+{
+    fn cmp(&self, other: &Self) -> Ordering { self.key().cmp(&other.key()) }
+}
+
+impl Hash for PureTime
+{
+    fn hash<H: Hasher>(&self, state: &mut H) { self.key().hash(state) }
+}
+
+impl pure_type::Typed for PureTime
+{
+    fn pure_type(&self) -> Type { Type::StrictTime }
+}
+
+impl fmt::Display for PureTime
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{:02}", self.hour)?;
+        write_time_fields(f, Some(self.minute), self.second, self.subsecond.as_deref())
+    }
+}
+
+impl FromStr for PureTime
+{
+    type Err = PureExecutionError;
+
+    /// Parses a time in Pure literal syntax, with or without the leading `%`.
+    fn from_str(text: &str) -> PureExecutionResult<PureTime>
+    {
+        let illegal = || PureExecutionError::IllegalValue { pure_type: Type::StrictTime, value: text.to_string() };
+
+        let literal = text.strip_prefix('%').unwrap_or(text);
+        match parse_time_fields(literal).ok_or_else(illegal)?
+        {
+            (hour, Some(minute), second, subsecond) => Ok(PureTime { hour, minute, second, subsecond }),
+            _ => Err(illegal()),
+        }
+    }
+}
+
+/// The digits of a subsecond without trailing zeros, which compare as the subsecond's value does.
+fn subsecond_value(subsecond: &Option<String>) -> Option<&str> { subsecond.as_deref().map(|digits| digits.trim_end_matches('0')) }
+
+fn write_time_fields(f: &mut fmt::Formatter<'_>, minute: Option<u8>, second: Option<u8>, subsecond: Option<&str>) -> fmt::Result
+{
+    if let Some(minute) = minute
+    {
+        write!(f, ":{minute:02}")?;
+    }
+    if let Some(second) = second
+    {
+        write!(f, ":{second:02}")?;
+    }
+    if let Some(subsecond) = subsecond
+    {
+        write!(f, ".{subsecond}")?;
+    }
+    Ok(())
+}
+
+/// The hour, minute, second and subsecond digits of a time.
+type TimeFields = (u8, Option<u8>, Option<u8>, Option<String>);
+
+/// Parses `HH[:MM[:SS[.fff]]]` validating the range of each field.
+fn parse_time_fields(text: &str) -> Option<TimeFields>
+{
+    let (hms, subsecond) = text.split_once('.').map_or((text, None), |(hms, sub)| (hms, Some(sub)));
+
+    let mut fields = hms.splitn(3, ':');
+    let hour = fields.next().and_then(|h| parse_digits::<u8>(h, 2, 2)).filter(|h| *h < 24)?;
+    let minute = fields.next().map(|m| parse_digits::<u8>(m, 2, 2).filter(|m| *m < 60)).map_or(Some(None), |m| m.map(Some))?;
+    let second = fields.next().map(|s| parse_digits::<u8>(s, 2, 2).filter(|s| *s < 60)).map_or(Some(None), |s| s.map(Some))?;
+    let subsecond = match subsecond
+    {
+        Some(digits) if second.is_some() && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) => Some(digits.to_string()),
+        Some(_) => return None,
+        None => None,
+    };
+    Some((hour, minute, second, subsecond))
+}
+
+/// Parses a timezone offset of the form `+HHMM` or `-HHMM` returning the offset in minutes.
+fn parse_offset(text: &str) -> Option<i64>
+{
+    let (sign, digits) = text.split_at(1);
+    let hours = parse_digits::<i64>(digits.get(..2)?, 2, 2)?;
+    let minutes = parse_digits::<i64>(digits.get(2..)?, 2, 2).filter(|m| *m < 60)?;
+    let offset = hours * 60 + minutes;
+    Some(if sign == "-" { -offset } else { offset })
+}
+
+fn parse_digits<T: FromStr>(text: &str, min_len: usize, max_len: usize) -> Option<T>
+{
+    if text.len() < min_len || text.len() > max_len || !text.chars().all(|c| c.is_ascii_digit())
+    {
+        None
+    }
+    else
+    {
+        text.parse().ok()
+    }
+}
+
+fn check_range(pure_type: Type, value: u8, min: u8, max: u8) -> PureExecutionResult<()>
+{
+    if value < min || value > max
+    {
+        Err(PureExecutionError::IllegalValue { pure_type, value: value.to_string() })
+    }
+    else
+    {
+        Ok(())
+    }
+}
+
+fn check_subsecond(pure_type: Type, digits: &str) -> PureExecutionResult<String>
+{
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        Err(PureExecutionError::IllegalValue { pure_type, value: digits.to_string() })
+    }
+    else
+    {
+        Ok(digits.to_string())
+    }
+}
+
+fn is_leap_year(year: i32) -> bool { (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 }
+
+pub(crate) fn days_in_month(year: i32, month: u8) -> u8
+{
+    match month
+    {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+pub(crate) fn days_from_civil(year: i32, month: u8, day: u8) -> i64
+{
+    let y = i64::from(year) - i64::from(month <= 2);
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The inverse of `days_from_civil`.
+pub(crate) fn civil_from_days(days: i64) -> (i32, u8, u8)
+{
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::pure_type::Typed;
+
+    #[test]
+    fn dates_of_each_precision() -> PureExecutionResult<()>
+    {
+        let cases = [
+            ("%2022", DatePrecision::Year, Type::Date),
+            ("%2022-03", DatePrecision::Month, Type::Date),
+            ("%2022-03-01", DatePrecision::Day, Type::StrictDate),
+            ("%2022-03-01T10+0000", DatePrecision::Hour, Type::DateTime),
+            ("%2022-03-01T10:15+0000", DatePrecision::Minute, Type::DateTime),
+            ("%2022-03-01T10:15:00+0000", DatePrecision::Second, Type::DateTime),
+            ("%2022-03-01T10:15:00.000+0000", DatePrecision::Subsecond, Type::DateTime),
+        ];
+
+        for (literal, precision, pure_type) in cases
+        {
+            let date: PureDate = literal.parse()?;
+            assert_eq!(precision, date.precision(), "{literal}");
+            assert_eq!(pure_type, date.pure_type(), "{literal}");
+            assert_eq!(literal, date.literal());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_without_percent_or_timezone() -> PureExecutionResult<()>
+    {
+        let date: PureDate = "2022-03-01T10:15:00.123456789".parse()?;
+        assert_eq!("2022-03-01T10:15:00.123456789+0000", date.to_string());
+        assert_eq!(2022, date.year());
+        assert_eq!(Some(3), date.month());
+        assert_eq!(Some(1), date.day());
+        assert_eq!(Some(10), date.hour());
+        assert_eq!(Some(15), date.minute());
+        assert_eq!(Some(0), date.second());
+        assert_eq!(Some("123456789"), date.subsecond());
+
+        assert_eq!("0033-01-01", "33-01-01".parse::<PureDate>()?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn years_before_1_bc() -> PureExecutionResult<()>
+    {
+        let date: PureDate = "%-0044-03-15".parse()?;
+        assert_eq!(-44, date.year());
+        assert_eq!("%-0044-03-15", date.literal());
+        assert_eq!("%-12345", "%-12345".parse::<PureDate>()?.literal());
+        assert_eq!("%-0001-12-31T23:00+0000", "%0000-01-01T01:00+0200".parse::<PureDate>()?.literal());
+        assert!("%-0004-02-29".parse::<PureDate>().is_ok());
+
+        for literal in ["%-", "%--2022", "%-2022-", "%+2022"]
+        {
+            let bad = literal.parse::<PureDate>();
+            assert!(bad.is_err(), "{literal}");
+            assert_eq!(format!("Illegal value for type Date: {literal}"), bad.err().unwrap().to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn timezones_are_converted_to_gmt() -> PureExecutionResult<()>
+    {
+        assert_eq!("2022-03-01T05:15:00+0000", "%2022-03-01T10:15:00+0500".parse::<PureDate>()?.to_string());
+        assert_eq!("2022-02-28T23:45+0000", "%2022-03-01T05:15+0530".parse::<PureDate>()?.to_string());
+        assert_eq!("2023-01-01T02+0000", "%2022-12-31T20-0600".parse::<PureDate>()?.to_string());
+        assert_eq!("2024-02-29T22:00:00.5+0000", "%2024-03-01T01:00:00.5+0300".parse::<PureDate>()?.to_string());
+
+        let bad = "%2022-03-01T05+0530".parse::<PureDate>();
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Date: %2022-03-01T05+0530", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_dates() -> PureExecutionResult<()>
+    {
+        for literal in [
+            "",
+            "%",
+            "%20x2",
+            "%2022-13",
+            "%2022-1",
+            "%2022-02-29",
+            "%2022-04-31",
+            "%2022-03T10",
+            "%2022-03-01T24",
+            "%2022-03-01T10:60",
+            "%2022-03-01T10:15.5",
+        ]
+        {
+            let bad = literal.parse::<PureDate>();
+            assert!(bad.is_err(), "{literal}");
+            assert_eq!(format!("Illegal value for type Date: {literal}"), bad.err().unwrap().to_string());
+        }
+
+        assert!("%2024-02-29".parse::<PureDate>().is_ok());
+        assert!("%2000-02-29".parse::<PureDate>().is_ok());
+        assert!("%1900-02-29".parse::<PureDate>().is_err());
+
+        let bad = PureDate::strict_date(2022, 2, 30);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type StrictDate: 30", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn constructed_dates() -> PureExecutionResult<()>
+    {
+        assert_eq!("%2022", PureDate::year_only(2022).literal());
+        assert_eq!("%2022-11", PureDate::year_month(2022, 11)?.literal());
+        assert_eq!("%2022-11-05", PureDate::strict_date(2022, 11, 5)?.literal());
+        assert_eq!("%2022-11-05T23:59:59+0000", PureDate::date_time(2022, 11, 5, 23, 59, 59, None)?.literal());
+        assert_eq!("%2022-11-05T23:59:59.01+0000", PureDate::date_time(2022, 11, 5, 23, 59, 59, Some("01"))?.literal());
+
+        assert!(PureDate::date_time(2022, 11, 5, 23, 59, 59, Some("x")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dates_order_by_field_then_precision() -> PureExecutionResult<()>
+    {
+        let parse = |s: &str| s.parse::<PureDate>();
+        assert!(parse("%2022-03-01")? < parse("%2022-03-02")?);
+        assert!(parse("%2021-12-31T23:59:59+0000")? < parse("%2022")?);
+        assert!(parse("%2022")? < parse("%2022-01")?);
+        assert!(parse("%2022-03-01T10:15:00.1+0000")? < parse("%2022-03-01T10:15:00.15+0000")?);
+        assert!(parse("%2022-03-01T10:15:00.15+0000")? < parse("%2022-03-01T10:15:00.2+0000")?);
+        assert!(parse("%2022-03-01T10:15:00+0000")? < parse("%2022-03-01T10:15:00.000+0000")?);
+        assert_eq!(parse("%2022-03-01T10:15:00.1+0000")?, parse("%2022-03-01T10:15:00.100+0000")?);
+        assert_eq!("%10:15:00.1".parse::<PureTime>()?, "%10:15:00.10".parse::<PureTime>()?);
+        assert!(parse("%-0044-03-15")? < parse("%0001")?);
+        assert_eq!(parse("%2022-03-01T10:15+0000")?, parse("%2022-03-01T11:15+0100")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_parts() -> PureExecutionResult<()>
+    {
+        assert_eq!("2022-03-01", "%2022-03-01T10:15:00.000+0000".parse::<PureDate>()?.date_part().to_string());
+        assert_eq!("2022-03", "%2022-03".parse::<PureDate>()?.date_part().to_string());
+
+        let now = PureDate::now();
+        assert_eq!(DatePrecision::Subsecond, now.precision());
+        assert_eq!(Some(3), now.subsecond().map(str::len));
+        assert!(now > "%2022-10-16".parse()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_times() -> PureExecutionResult<()>
+    {
+        let time: PureTime = "%10:15".parse()?;
+        assert_eq!(DatePrecision::Minute, time.precision());
+        assert_eq!(Type::StrictTime, time.pure_type());
+        assert_eq!("%10:15", time.literal());
+
+        let time: PureTime = "23:59:59.999".parse()?;
+        assert_eq!(DatePrecision::Subsecond, time.precision());
+        assert_eq!((23, 59, Some(59), Some("999")), (time.hour(), time.minute(), time.second(), time.subsecond()));
+        assert_eq!("23:59:59.999", time.to_string());
+
+        assert_eq!("%00:00:01", PureTime::new(0, 0, Some(1), None)?.literal());
+        assert!(PureTime::new(0, 0, None, Some("1")).is_err());
+        assert!(PureTime::new(24, 0, None, None).is_err());
+
+        for literal in ["%10", "%10:5", "%10:15:60", "%10:15.1", "%25:00"]
+        {
+            let bad = literal.parse::<PureTime>();
+            assert!(bad.is_err(), "{literal}");
+            assert_eq!(format!("Illegal value for type StrictTime: {literal}"), bad.err().unwrap().to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn civil_day_conversions()
+    {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!(-1, days_from_civil(1969, 12, 31));
+        assert_eq!(19052, days_from_civil(2022, 3, 1));
+        for days in [-800000, -1, 0, 1, 11016, 19052, 2932896]
+        {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days, days_from_civil(y, m, d));
+        }
+    }
+}
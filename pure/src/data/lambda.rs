@@ -0,0 +1,150 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::generic_type::{check_multiplicity, FunctionType, TypeBindings, Variance};
+use crate::pure_type::Typed;
+use crate::*;
+
+type LambdaBody = dyn Fn(&[&Collection]) -> PureExecutionResult<Collection> + Send + Sync;
+
+/// A function value, such as the `{x|$x > 1}` passed to `filter`.  A lambda has a signature, against which its arguments are
+/// checked when it is applied, and a body that computes its result from those arguments.  Lambdas are equal only to themselves.
+#[derive(Clone)]
+pub struct Lambda
+{
+    signature: Arc<FunctionType>,
+    body: Arc<LambdaBody>,
+}
+
+impl Lambda
+{
+    pub fn new<P, R, F>(parameters: Vec<(GenericType, P)>, returns: (GenericType, R), body: F) -> Lambda
+    where
+        P: Into<GenericMultiplicity>,
+        R: Into<GenericMultiplicity>,
+        F: Fn(&[&Collection]) -> PureExecutionResult<Collection> + Send + Sync + 'static,
+    {
+        let parameters = parameters.into_iter().map(|(ty, mult)| (ty, mult.into())).collect();
+        let returns = (returns.0, returns.1.into());
+        Lambda { signature: Arc::new(FunctionType { parameters, returns }), body: Arc::new(body) }
+    }
+
+    pub fn signature(&self) -> &FunctionType { &self.signature }
+
+    /// Applies the lambda after checking that the arguments match its parameters.
+    pub fn apply(&self, arguments: &[&Collection]) -> PureExecutionResult<Collection>
+    {
+        let mut bindings = TypeBindings::default();
+        let matches = self.signature.parameters.len() == arguments.len()
+            && self.signature.parameters.iter().zip(arguments).all(|((ty, mult), arg)| {
+                ty.is_assignable_from_binding(&arg.generic_type(), &mut bindings)
+                    && check_multiplicity(mult, &arg.multiplicity().into(), Variance::Covariant, &mut bindings)
+            });
+
+        if !matches
+        {
+            let arguments = arguments.iter().map(|arg| format!("{}{}", arg.pure_type(), arg.multiplicity())).collect::<Vec<_>>().join(", ");
+            return Err(PureExecutionError::IllegalLambdaArguments { signature: self.to_string(), arguments });
+        }
+        (self.body)(arguments)
+    }
+}
+
+impl Typed for Lambda
+{
+    fn pure_type(&self) -> Type { Type::Function }
+
+    fn generic_type(&self) -> GenericType
+    {
+        let function_type = GenericType::Function(self.signature.clone());
+        GenericType::Concrete { raw: Type::Function, type_arguments: Arc::from([function_type]) }
+    }
+}
+
+impl PartialEq for Lambda
+{
+    fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.body, &other.body) }
+}
+
+impl fmt::Debug for Lambda
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "Lambda({self})") }
+}
+
+impl fmt::Display for Lambda
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", GenericType::Function(self.signature.clone())) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn is_positive() -> Lambda
+    {
+        Lambda::new(vec![(GenericType::from(Type::Number), PURE_ONE)], (GenericType::from(Type::Boolean), PURE_ONE), |args| {
+            Collection::one(args[0].into_iter().all(|v| v.compare_numbers(&Value::from(0)).is_some_and(|o| o.is_gt())))
+        })
+    }
+
+    #[test]
+    fn lambda_apply() -> PureExecutionResult<()>
+    {
+        let lambda = is_positive();
+        assert_eq!("{Number[1]->Boolean[1]}", lambda.to_string());
+        assert_eq!("Function<{Number[1]->Boolean[1]}>", lambda.generic_type().to_string());
+        assert_eq!(Type::Function, lambda.pure_type());
+
+        assert_eq!(Collection::one(true)?, lambda.apply(&[&Collection::one(2)?])?);
+        assert_eq!(Collection::one(false)?, lambda.apply(&[&Collection::one(-1.5)?])?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn lambda_arguments_are_checked() -> PureExecutionResult<()>
+    {
+        let lambda = is_positive();
+
+        let bad = lambda.apply(&[&Collection::one("2")?]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal lambda arguments: {Number[1]->Boolean[1]} cannot be applied to (String[1])", bad.err().unwrap().to_string());
+
+        let bad = lambda.apply(&[&Collection::zero(Type::Integer)]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal lambda arguments: {Number[1]->Boolean[1]} cannot be applied to (Integer[0])", bad.err().unwrap().to_string());
+
+        let bad = lambda.apply(&[&Collection::one(1)?, &Collection::one(2)?]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal lambda arguments: {Number[1]->Boolean[1]} cannot be applied to (Integer[1], Integer[1])", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lambda_type_parameters_are_bound() -> PureExecutionResult<()>
+    {
+        let t = || GenericType::parameter("T");
+        let first = Lambda::new(vec![(t(), PURE_ONE), (t(), PURE_ONE)], (t(), PURE_ONE), |args| {
+            Ok(args[0].into_iter().next().unwrap().clone().to_collection())
+        });
+
+        assert_eq!(Collection::one(1)?, first.apply(&[&Collection::one(1)?, &Collection::one(2.5)?])?);
+        assert!(first.apply(&[&Collection::one(1)?, &ZERO_NIL]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lambda_equality() -> PureExecutionResult<()>
+    {
+        let lambda = is_positive();
+        assert_eq!(lambda, lambda.clone());
+        assert_ne!(lambda, is_positive());
+
+        Ok(())
+    }
+}
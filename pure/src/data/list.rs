@@ -0,0 +1,69 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::pure_type::Typed;
+use crate::*;
+
+/// A list of values held as a single value, such as each of the groups created by `groupBy`, of the type `List<T>` where `T` is the
+/// type of its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PureList
+{
+    element_type: GenericType,
+    values: Arc<[Value]>,
+}
+
+impl PureList
+{
+    /// Creates a list of values of the given type, checking that each value can be assigned to it.
+    pub fn new(element_type: GenericType, values: Vec<Value>) -> PureExecutionResult<PureList>
+    {
+        if let Some(bad) = values.iter().find(|value| !element_type.is_assignable_from(&value.generic_type()))
+        {
+            return Err(PureExecutionError::IllegalAssignment { from: bad.pure_type(), to: element_type.raw_type().unwrap_or(Type::Any) });
+        }
+        Ok(PureList { element_type, values: values.into() })
+    }
+
+    pub fn element_type(&self) -> &GenericType { &self.element_type }
+    pub fn values(&self) -> &[Value] { &self.values }
+}
+
+impl Typed for PureList
+{
+    fn pure_type(&self) -> Type { Type::List }
+
+    fn generic_type(&self) -> GenericType { GenericType::Concrete { raw: Type::List, type_arguments: Arc::from([self.element_type.clone()]) } }
+}
+
+impl fmt::Display for PureList
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "[{}]", self.values.iter().map(Value::to_string).collect::<Vec<_>>().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn list_values() -> PureExecutionResult<()>
+    {
+        let list = PureList::new(GenericType::from(Type::Number), vec![1.into(), 2.5.into()])?;
+        assert_eq!(&[Value::from(1), Value::from(2.5)], list.values());
+        assert_eq!("List<Number>", list.generic_type().to_string());
+        assert_eq!("[1, 2.5]", list.to_string());
+        assert_eq!("[]", PureList::new(GenericType::from(Type::String), vec![])?.to_string());
+
+        let bad = PureList::new(GenericType::from(Type::Number), vec![1.into(), "a".into()]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: String value cannot be assigned to Number", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+}
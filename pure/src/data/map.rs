@@ -0,0 +1,328 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::data::ValueKey;
+use crate::pure_type::Typed;
+use crate::*;
+
+/// A map from keys to values, such as the groups created by `groupBy`, of the type `Map<K, V>` where `K` and `V` are the types of
+/// its keys and values.  Keys are the same when they are equal as Pure tests equality, so `1` and `1.0` are the same key.
+///
+/// Maps are persistent: putting an entry into a map gives a new map that shares all but the path to the new entry with the
+/// original, which is unchanged.  The entries are held in a trie indexed by successive five bit chunks of the hashes of their keys,
+/// so that putting and getting an entry take a time proportional to the logarithm of the size of the map.
+#[derive(Debug, Clone)]
+pub struct PureMap
+{
+    key_type: GenericType,
+    value_type: GenericType,
+    len: usize,
+    root: Node,
+}
+
+#[derive(Debug, Clone)]
+enum Node
+{
+    Empty,
+    /// The entries whose keys all have the same hash, which is usually just one entry.
+    Entries
+    {
+        hash: u64,
+        entries: Arc<[(Value, Value)]>,
+    },
+    /// The nodes for each chunk of the hash that is present in the bitmap, in the order of the chunks.
+    Branch
+    {
+        bitmap: u32,
+        children: Arc<[Node]>,
+    },
+}
+
+const BITS: u32 = 5;
+
+impl PureMap
+{
+    pub fn empty(key_type: GenericType, value_type: GenericType) -> PureMap { PureMap { key_type, value_type, len: 0, root: Node::Empty } }
+
+    /// Creates a map of keys and values of the given types, checking that each key and value can be assigned to its type.  A later
+    /// entry for the same key replaces an earlier one.
+    pub fn new<I>(key_type: GenericType, value_type: GenericType, entries: I) -> PureExecutionResult<PureMap>
+    where
+        I: IntoIterator<Item = (Value, Value)>,
+    {
+        PureMap::empty(key_type, value_type).put_all(entries)
+    }
+
+    pub fn key_type(&self) -> &GenericType { &self.key_type }
+    pub fn value_type(&self) -> &GenericType { &self.value_type }
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    pub fn get(&self, key: &Value) -> Option<&Value>
+    {
+        let hash = hash_of(key);
+        let mut node = &self.root;
+        let mut shift = 0;
+        loop
+        {
+            match node
+            {
+                Node::Empty => return None,
+                Node::Entries { hash: h, entries } if *h == hash =>
+                {
+                    return entries.iter().find(|(k, _)| ValueKey(k) == ValueKey(key)).map(|(_, v)| v)
+                }
+                Node::Entries { .. } => return None,
+                Node::Branch { bitmap, children } =>
+                {
+                    let bit = bit_of(hash, shift);
+                    if bitmap & bit == 0
+                    {
+                        return None;
+                    }
+                    node = &children[position(*bitmap, bit)];
+                    shift += BITS;
+                }
+            }
+        }
+    }
+
+    /// A map with the entry added, replacing any entry for the same key, checking that the key and value can be assigned to the
+    /// types of the map.
+    pub fn put(&self, key: Value, value: Value) -> PureExecutionResult<PureMap>
+    {
+        for (ty, v) in [(&self.key_type, &key), (&self.value_type, &value)]
+        {
+            if !ty.is_assignable_from(&v.generic_type())
+            {
+                return Err(PureExecutionError::IllegalAssignment { from: v.pure_type(), to: ty.raw_type().unwrap_or(Type::Any) });
+            }
+        }
+
+        let hash = hash_of(&key);
+        let (root, added) = self.root.put(hash, 0, key, value);
+        Ok(PureMap { key_type: self.key_type.clone(), value_type: self.value_type.clone(), len: self.len + usize::from(added), root })
+    }
+
+    /// A map with all the entries added in turn.
+    pub fn put_all<I>(&self, entries: I) -> PureExecutionResult<PureMap>
+    where
+        I: IntoIterator<Item = (Value, Value)>,
+    {
+        entries.into_iter().try_fold(self.clone(), |map, (key, value)| map.put(key, value))
+    }
+
+    /// The same entries as a map of more general types, to which the keys and values of another map can then also be put.
+    pub fn widen(&self, key_type: &GenericType, value_type: &GenericType) -> PureMap
+    {
+        PureMap {
+            key_type: self.key_type.common_supertype(key_type),
+            value_type: self.value_type.common_supertype(value_type),
+            len: self.len,
+            root: self.root.clone(),
+        }
+    }
+
+    /// The keys and values of the map in no particular order.
+    pub fn iter(&self) -> Iter<'_> { Iter { stack: vec![std::slice::from_ref(&self.root).iter()], entries: [].iter() } }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Value> { self.iter().map(|(key, _)| key) }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> { self.iter().map(|(_, value)| value) }
+}
+
+impl Node
+{
+    /// The node with the entry added, and whether it was added rather than replacing an entry for the same key.
+    fn put(&self, hash: u64, shift: u32, key: Value, value: Value) -> (Node, bool)
+    {
+        match self
+        {
+            Node::Empty => (Node::Entries { hash, entries: Arc::from([(key, value)]) }, true),
+            Node::Entries { hash: h, entries } if *h == hash =>
+            {
+                let mut entries = entries.to_vec();
+                let added = match entries.iter().position(|(k, _)| ValueKey(k) == ValueKey(&key))
+                {
+                    Some(i) =>
+                    {
+                        entries[i].1 = value;
+                        false
+                    }
+                    None =>
+                    {
+                        entries.push((key, value));
+                        true
+                    }
+                };
+                (Node::Entries { hash, entries: entries.into() }, added)
+            }
+            Node::Entries { hash: h, .. } =>
+            {
+                // The hashes differ so they differ in some chunk and the entries can be separated by branching
+                let branch = Node::Branch { bitmap: bit_of(*h, shift), children: Arc::from([self.clone()]) };
+                branch.put(hash, shift, key, value)
+            }
+            Node::Branch { bitmap, children } =>
+            {
+                let bit = bit_of(hash, shift);
+                let pos = position(*bitmap, bit);
+                let mut children = children.to_vec();
+                let added = if bitmap & bit == 0
+                {
+                    children.insert(pos, Node::Entries { hash, entries: Arc::from([(key, value)]) });
+                    true
+                }
+                else
+                {
+                    let (child, added) = children[pos].put(hash, shift + BITS, key, value);
+                    children[pos] = child;
+                    added
+                };
+                (Node::Branch { bitmap: bitmap | bit, children: children.into() }, added)
+            }
+        }
+    }
+}
+
+fn hash_of(key: &Value) -> u64
+{
+    let mut hasher = DefaultHasher::new();
+    ValueKey(key).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The bit for the chunk of the hash at the given shift.
+fn bit_of(hash: u64, shift: u32) -> u32 { 1 << (hash.checked_shr(shift).unwrap_or(0) & 0x1f) }
+
+/// The position within a branch of the child for a bit, which is the number of children for lower bits.
+fn position(bitmap: u32, bit: u32) -> usize { (bitmap & (bit - 1)).count_ones() as usize }
+
+/// An iterator over the entries of a map, walking its trie depth first.
+pub struct Iter<'a>
+{
+    stack: Vec<std::slice::Iter<'a, Node>>,
+    entries: std::slice::Iter<'a, (Value, Value)>,
+}
+
+impl<'a> Iterator for Iter<'a>
+{
+    type Item = (&'a Value, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        loop
+        {
+            if let Some((key, value)) = self.entries.next()
+            {
+                return Some((key, value));
+            }
+            match self.stack.last_mut()?.next()
+            {
+                Some(Node::Empty) => (),
+                Some(Node::Entries { entries, .. }) => self.entries = entries.iter(),
+                Some(Node::Branch { children, .. }) => self.stack.push(children.iter()),
+                None =>
+                {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Maps are equal when they have the same entries, whatever their types, since putting entries into a map can widen its types.
+impl PartialEq for PureMap
+{
+    fn eq(&self, other: &Self) -> bool { self.len() == other.len() && self.iter().all(|(key, value)| other.get(key) == Some(value)) }
+}
+
+impl Typed for PureMap
+{
+    fn pure_type(&self) -> Type { Type::Map }
+
+    fn generic_type(&self) -> GenericType
+    {
+        GenericType::Concrete { raw: Type::Map, type_arguments: Arc::from([self.key_type.clone(), self.value_type.clone()]) }
+    }
+}
+
+impl fmt::Display for PureMap
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut entries = self.iter().map(|(key, value)| format!("{key}->{value}")).collect::<Vec<_>>();
+        entries.sort();
+        write!(f, "{{{}}}", entries.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn map_values() -> PureExecutionResult<()>
+    {
+        let entries = [(1.into(), "a".into()), ("b".into(), "b".into()), (1.0.into(), "c".into())];
+        let map = PureMap::new(GenericType::from(Type::Any), GenericType::from(Type::String), entries)?;
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&Value::from("c")), map.get(&Value::decimal("1.0")?));
+        assert_eq!(None, map.get(&Value::from(2)));
+        assert_eq!("Map<Any, String>", map.generic_type().to_string());
+        assert_eq!("{1->c, b->b}", map.to_string());
+
+        let bad = PureMap::new(GenericType::from(Type::Integer), GenericType::from(Type::String), [("a".into(), "a".into())]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: String value cannot be assigned to Integer", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_is_persistent() -> PureExecutionResult<()>
+    {
+        let integers = || GenericType::from(Type::Integer);
+        let empty = PureMap::empty(integers(), integers());
+
+        let mut maps = vec![empty.clone()];
+        for i in 0..2000
+        {
+            maps.push(maps.last().unwrap().put(i.into(), (i * 10).into())?);
+        }
+        let full = maps.last().unwrap();
+        assert_eq!(2000, full.len());
+        assert!((0..2000).all(|i| full.get(&i.into()) == Some(&Value::from(i * 10))));
+        assert_eq!(None, full.get(&2000.into()));
+
+        // Earlier maps are unchanged by later puts
+        assert!(empty.is_empty());
+        assert_eq!(100, maps[100].len());
+        assert_eq!(Some(&Value::from(990)), maps[100].get(&99.into()));
+        assert_eq!(None, maps[100].get(&100.into()));
+
+        let mut keys: Vec<i64> = full.keys().map(|k| k.clone().try_into()).collect::<PureExecutionResult<_>>()?;
+        keys.sort();
+        assert_eq!((0..2000).collect::<Vec<_>>(), keys);
+        assert_eq!(2000, full.values().count());
+
+        let replaced = full.put(5.into(), 0.into())?;
+        assert_eq!(2000, replaced.len());
+        assert_eq!(Some(&Value::from(0)), replaced.get(&5.into()));
+        assert_eq!(Some(&Value::from(50)), full.get(&5.into()));
+
+        let widened = full.widen(&GenericType::from(Type::Float), &GenericType::from(Type::String));
+        assert_eq!("Map<Number, Any>", widened.generic_type().to_string());
+        assert!(widened.put(1.5.into(), "x".into()).is_ok());
+
+        // Widening a map does not change its entries, so it is equal to the original
+        assert_eq!(full, &widened);
+
+        Ok(())
+    }
+}
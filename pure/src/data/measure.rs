@@ -0,0 +1,163 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::fmt;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+
+use crate::data::model::ElementId;
+use crate::pure_type::Typed;
+use crate::*;
+
+/// A unit of a measure.  A unit of a convertible measure holds the factor that converts an amount in the unit to the canonical unit
+/// of the measure.  Units of a non-convertible measure, such as a currency, have no factor and cannot be converted.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unit
+{
+    pub(crate) id: ElementId,
+    pub(crate) measure: ElementId,
+    name: Arc<str>,
+    to_canonical: Option<BigDecimal>,
+}
+
+impl Unit
+{
+    pub(crate) fn new(id: ElementId, measure: ElementId, name: &str, to_canonical: Option<BigDecimal>) -> Unit
+    {
+        Unit { id, measure, name: Arc::from(name), to_canonical }
+    }
+
+    pub fn id(&self) -> ElementId { self.id }
+    pub fn measure(&self) -> ElementId { self.measure }
+    pub fn name(&self) -> &str { &self.name }
+    pub fn to_canonical(&self) -> Option<&BigDecimal> { self.to_canonical.as_ref() }
+}
+
+impl Typed for Unit
+{
+    fn pure_type(&self) -> Type { Type::Unit { measure: self.measure, unit: self.id } }
+}
+
+/// A numeric amount in a unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity
+{
+    unit: Arc<Unit>,
+    amount: Box<Value>,
+}
+
+impl Quantity
+{
+    pub fn new(unit: &Arc<Unit>, amount: Value) -> PureExecutionResult<Quantity>
+    {
+        match amount
+        {
+            Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => Ok(Quantity { unit: unit.clone(), amount: Box::new(amount) }),
+            other => Err(PureExecutionError::UnexpectedValue { expected: "Number".into(), got: other.pure_type().to_string() }),
+        }
+    }
+
+    pub fn unit(&self) -> &Arc<Unit> { &self.unit }
+    pub fn amount(&self) -> &Value { &self.amount }
+
+    /// Converts this quantity to another unit of the same measure via the canonical unit.  A Float amount stays a Float and an
+    /// Integer amount stays an Integer when the conversion is exact; otherwise the amount becomes a Decimal.
+    pub fn convert(&self, unit: &Arc<Unit>) -> PureExecutionResult<Quantity>
+    {
+        if self.unit == *unit
+        {
+            return Ok(self.clone());
+        }
+
+        let incompatible = || PureExecutionError::IncompatibleUnits { from: self.unit.name.to_string(), to: unit.name.to_string() };
+        let (from, to) = match (&self.unit.to_canonical, &unit.to_canonical)
+        {
+            (Some(from), Some(to)) if self.unit.measure == unit.measure => (from, to),
+            _ => return Err(incompatible()),
+        };
+
+        let amount = match &*self.amount
+        {
+            Value::Float(v) => Value::Float(v * from.to_f64().ok_or_else(incompatible)? / to.to_f64().ok_or_else(incompatible)?),
+            other =>
+            {
+                let decimal = other.to_decimal().ok_or_else(incompatible)? * from / to;
+                match (other, decimal.is_integer().then(|| decimal.to_i64()).flatten())
+                {
+                    (Value::Integer(_), Some(integer)) => Value::Integer(integer),
+                    _ => Value::Decimal(decimal.normalized()),
+                }
+            }
+        };
+        Quantity::new(unit, amount)
+    }
+}
+
+impl Typed for Quantity
+{
+    fn pure_type(&self) -> Type { self.unit.pure_type() }
+}
+
+impl fmt::Display for Quantity
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{} {}", self.amount, self.unit.name) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::data::model::Model;
+
+    fn mass(model: &mut Model) -> PureExecutionResult<(Arc<Unit>, Arc<Unit>, Arc<Unit>)>
+    {
+        model.add_measure("domain::Mass", "Gram", &[("Kilogram", BigDecimal::from(1000)), ("Pound", BigDecimal::from_str("453.59237").unwrap())])?;
+        Ok((model.unit("domain::Mass~Gram")?, model.unit("domain::Mass~Kilogram")?, model.unit("domain::Mass~Pound")?))
+    }
+
+    #[test]
+    fn quantity_conversions() -> PureExecutionResult<()>
+    {
+        let (gram, kilogram, pound) = mass(&mut Model::new())?;
+
+        let two_kilos = Quantity::new(&kilogram, Value::from(2))?;
+        assert_eq!("2 Mass~Kilogram", two_kilos.to_string());
+        assert_eq!(two_kilos, two_kilos.convert(&kilogram)?);
+        assert_eq!(Quantity::new(&gram, Value::from(2000))?, two_kilos.convert(&gram)?);
+        assert_eq!(Quantity::new(&kilogram, Value::from(2))?, Quantity::new(&gram, Value::from(2000))?.convert(&kilogram)?);
+        assert_eq!(Quantity::new(&kilogram, Value::decimal("0.5")?)?, Quantity::new(&gram, Value::from(500))?.convert(&kilogram)?);
+        assert_eq!(Quantity::new(&gram, Value::decimal("907.18474")?)?, Quantity::new(&pound, Value::from(2))?.convert(&gram)?);
+        assert_eq!(Quantity::new(&gram, Value::from(2500.0))?, Quantity::new(&kilogram, Value::from(2.5))?.convert(&gram)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn quantity_incompatible_units() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let (gram, _, _) = mass(&mut model)?;
+        model.add_measure("domain::Length", "Metre", &[])?;
+        model.add_non_convertible_measure("domain::Currency", &["USD", "GBP"])?;
+        let metre = model.unit("domain::Length~Metre")?;
+        let usd = model.unit("domain::Currency~USD")?;
+        let gbp = model.unit("domain::Currency~GBP")?;
+
+        let bad = Quantity::new(&gram, Value::from(1))?.convert(&metre);
+        assert!(bad.is_err());
+        assert_eq!("Incompatible units: Mass~Gram cannot be converted to Length~Metre", bad.err().unwrap().to_string());
+
+        let bad = Quantity::new(&usd, Value::from(1))?.convert(&gbp);
+        assert!(bad.is_err());
+        assert_eq!("Incompatible units: Currency~USD cannot be converted to Currency~GBP", bad.err().unwrap().to_string());
+        assert_eq!(Quantity::new(&usd, Value::from(1))?, Quantity::new(&usd, Value::from(1))?.convert(&usd)?);
+
+        let bad = Quantity::new(&gram, Value::from("1"));
+        assert!(bad.is_err());
+        assert_eq!("Unexpected value: expected Number but got String", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
 // // Copyright 2022 Dave Wathen. All rights reserved.
 
 use std::fmt;
+use std::sync::Arc;
 
+use bigdecimal::{BigDecimal, Zero};
+
+use crate::data::measure::Unit;
+use crate::data::{EnumValue, Instance};
+use crate::pure_type::Typed;
 use crate::*;
 
 pub const ROOT: ElementId = ElementId(0);
@@ -19,18 +25,156 @@ impl fmt::Debug for Model
 
 impl Model
 {
-    pub fn new() -> Self { Model { elements: vec![ElementData { id: ROOT, name: "Root".to_string(), parent: ROOT, children: vec![] }] } }
+    pub fn new() -> Self
+    {
+        Model { elements: vec![ElementData { id: ROOT, name: "Root".to_string(), parent: ROOT, children: vec![], kind: ElementKind::Package }] }
+    }
+
+    pub fn add(&mut self, full_name: &str) -> PureExecutionResult<ElementId> { self.add_to(full_name, ROOT, ElementKind::Package) }
+
+    /// Adds an enumeration whose values are in the order given.
+    pub fn add_enumeration(&mut self, full_name: &str, values: &[&str]) -> PureExecutionResult<ElementId>
+    {
+        let mut names: Vec<Arc<str>> = Vec::with_capacity(values.len());
+        for value in values
+        {
+            if names.iter().any(|name| **name == **value)
+            {
+                return Err(PureExecutionError::DuplicateElementName { name: format!("{full_name}.{value}") });
+            }
+            names.push(Arc::from(*value));
+        }
+        self.add_to(full_name, ROOT, ElementKind::Enumeration { values: names })
+    }
+
+    /// Adds a class with the given properties.  Properties that refer to classes not yet in the model can be added later using
+    /// `add_property`.
+    pub fn add_class(&mut self, full_name: &str, properties: Vec<Property>) -> PureExecutionResult<ElementId>
+    {
+        let id = self.add_to(full_name, ROOT, ElementKind::Class { properties: vec![] })?;
+        for property in properties
+        {
+            self.add_property(id, property)?;
+        }
+        Ok(id)
+    }
+
+    pub fn add_property(&mut self, class: ElementId, property: Property) -> PureExecutionResult<()>
+    {
+        let path = self.path(class);
+        let ElementKind::Class { properties } = &mut self.elements[class.0].kind
+        else
+        {
+            return Err(PureExecutionError::WrongElementKind { name: path, expected: "a Class" });
+        };
+
+        if properties.iter().any(|p| p.name == property.name)
+        {
+            return Err(PureExecutionError::DuplicateElementName { name: format!("{path}.{}", property.name) });
+        }
+        properties.push(property);
+        Ok(())
+    }
+
+    /// Creates an instance of a class.  Each value must be assignable to the type and multiplicity of its property and any property
+    /// not given a value is empty.
+    pub fn new_instance(&self, class: ElementId, values: Vec<(&str, Collection)>) -> PureExecutionResult<Value>
+    {
+        let element = self.get_element(&class);
+        let properties = element.properties().ok_or_else(|| PureExecutionError::WrongElementKind { name: element.path(), expected: "a Class" })?;
+
+        if let Some((name, _)) = values.iter().find(|(name, _)| properties.iter().all(|p| p.name() != *name))
+        {
+            return Err(PureExecutionError::UnknownElement { name: format!("{}.{name}", element.path()) });
+        }
+        for (idx, (name, _)) in values.iter().enumerate()
+        {
+            if values[..idx].iter().any(|(other, _)| other == name)
+            {
+                return Err(PureExecutionError::DuplicateElementName { name: format!("{}.{name}", element.path()) });
+            }
+        }
+
+        let mut values = values;
+        let mut instance_values = Vec::with_capacity(properties.len());
+        for property in properties
+        {
+            let value = match values.iter().position(|(name, _)| *name == property.name())
+            {
+                Some(idx) => values.swap_remove(idx).1,
+                None => Collection::zero(property.pure_type),
+            };
+            property.check(&value)?;
+            instance_values.push((property.name.clone(), value));
+        }
+
+        Ok(Value::Instance(Arc::new(Instance { class, values: instance_values })))
+    }
+
+    /// Adds a convertible measure.  Each of the units gives the factor that converts an amount in that unit to the canonical unit.
+    /// Units are added alongside the measure and are named as in Pure, for example `Mass~Kilogram`.
+    pub fn add_measure(&mut self, full_name: &str, canonical: &str, units: &[(&str, BigDecimal)]) -> PureExecutionResult<ElementId>
+    {
+        let mut all = vec![(canonical, Some(BigDecimal::from(1)))];
+        for (name, factor) in units
+        {
+            if factor.is_zero()
+            {
+                return Err(PureExecutionError::IllegalValue { pure_type: Type::Decimal, value: factor.to_string() });
+            }
+            all.push((name, Some(factor.clone())));
+        }
+        self.add_measure_with_units(full_name, all)
+    }
+
+    /// Adds a measure, such as a currency, whose units cannot be converted between one another.
+    pub fn add_non_convertible_measure(&mut self, full_name: &str, units: &[&str]) -> PureExecutionResult<ElementId>
+    {
+        self.add_measure_with_units(full_name, units.iter().map(|name| (*name, None)).collect())
+    }
 
-    pub fn add(&mut self, full_name: &str) -> PureExecutionResult<ElementId> { self.add_to(full_name, ROOT) }
+    fn add_measure_with_units(&mut self, full_name: &str, units: Vec<(&str, Option<BigDecimal>)>) -> PureExecutionResult<ElementId>
+    {
+        for (idx, (name, _)) in units.iter().enumerate()
+        {
+            if units[..idx].iter().any(|(other, _)| other == name)
+            {
+                return Err(PureExecutionError::DuplicateElementName { name: format!("{full_name}~{name}") });
+            }
+        }
 
-    fn add_to(&mut self, full_name: &str, parent_id: ElementId) -> PureExecutionResult<ElementId>
+        let measure = self.add_to(full_name, ROOT, ElementKind::Measure { units: vec![] })?;
+        let parent = self.elements[measure.0].parent;
+        let measure_name = self.elements[measure.0].name.clone();
+        for (name, to_canonical) in units
+        {
+            // The unit is made once its element, and so its id, exists so that every lookup can share it
+            let unit = self.add_to(&format!("{measure_name}~{name}"), parent, ElementKind::Package)?;
+            let data = &mut self.elements[unit.0];
+            data.kind = ElementKind::Unit(Arc::new(Unit::new(unit, measure, &data.name, to_canonical)));
+            if let ElementKind::Measure { units } = &mut self.elements[measure.0].kind
+            {
+                units.push(unit);
+            }
+        }
+        Ok(measure)
+    }
+
+    /// Returns a unit given its full name, for example `domain::Mass~Kilogram`.
+    pub fn unit(&self, full_name: &str) -> PureExecutionResult<Arc<Unit>>
+    {
+        let element = self.get_element_by_name(full_name).ok_or_else(|| PureExecutionError::UnknownElement { name: full_name.to_string() })?;
+        element.unit()
+    }
+
+    fn add_to(&mut self, full_name: &str, parent_id: ElementId, kind: ElementKind) -> PureExecutionResult<ElementId>
     {
         if let Some(idx) = full_name.find(PACKAGE_SEPARATOR)
         {
             let head = &full_name[..idx];
             let tail = &full_name[(idx + 2)..];
-            let child_id = self.find_child_id(head, parent_id).unwrap_or_else(|| self.create(head, parent_id));
-            self.add_to(tail, child_id)
+            let child_id = self.find_child_id(head, parent_id).unwrap_or_else(|| self.create(head, parent_id, ElementKind::Package));
+            self.add_to(tail, child_id, kind)
         }
         else if let Some(existing_id) = self.find_child_id(full_name, parent_id)
         {
@@ -38,27 +182,47 @@ impl Model
         }
         else
         {
-            Ok(self.create(full_name, parent_id))
+            Ok(self.create(full_name, parent_id, kind))
         }
     }
 
-    fn create(&mut self, name: &str, parent_id: ElementId) -> ElementId
+    fn create(&mut self, name: &str, parent_id: ElementId, kind: ElementKind) -> ElementId
     {
         let new_id = ElementId(self.elements.len());
-        self.elements.push(ElementData { id: new_id, name: name.to_owned(), parent: parent_id, children: vec![] });
+        self.elements.push(ElementData { id: new_id, name: name.to_owned(), parent: parent_id, children: vec![], kind });
         self.elements[parent_id.0].children.push(new_id);
         new_id
     }
 
-    pub fn get_element(&self, id: &ElementId) -> Element
+    /// Returns the value of an enumeration given the full name of the enumeration and the name of the value.
+    pub fn enum_value(&self, enumeration: &str, value: &str) -> PureExecutionResult<Value>
+    {
+        let element = self.get_element_by_name(enumeration).ok_or_else(|| PureExecutionError::UnknownElement { name: enumeration.to_string() })?;
+        element.enum_value(value)
+    }
+
+    /// Returns the name of a type as it is written in Pure, using full paths for types that are elements of this model.
+    pub fn type_name(&self, pure_type: &Type) -> String
+    {
+        match pure_type
+        {
+            Type::Enum(id) | Type::Class(id) | Type::Measure(id) | Type::Unit { unit: id, .. } => self.path(*id),
+            _ => pure_type.to_string(),
+        }
+    }
+
+    /// Returns the name of a generic type as it is written in Pure, using full paths for types that are elements of this model.
+    pub fn generic_type_name(&self, generic_type: &GenericType) -> String { generic_type.to_string_with(&|t| self.type_name(t)) }
+
+    pub fn get_element(&self, id: &ElementId) -> Element<'_>
     {
         let data = &self.elements[id.0];
         Element { model: self, data }
     }
 
-    pub fn get_element_by_name(&self, full_name: &str) -> Option<Element> { self.find(full_name, ROOT) }
+    pub fn get_element_by_name(&self, full_name: &str) -> Option<Element<'_>> { self.find(full_name, ROOT) }
 
-    fn find(&self, full_name: &str, parent_id: ElementId) -> Option<Element>
+    fn find(&self, full_name: &str, parent_id: ElementId) -> Option<Element<'_>>
     {
         if let Some(idx) = full_name.find(PACKAGE_SEPARATOR)
         {
@@ -103,9 +267,14 @@ impl Default for Model
     fn default() -> Self { Self::new() }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ElementId(usize);
 
+impl fmt::Display for ElementId
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "#{}", self.0) }
+}
+
 #[derive(Debug)]
 pub struct ElementData
 {
@@ -113,6 +282,69 @@ pub struct ElementData
     name: String,
     parent: ElementId,
     children: Vec<ElementId>,
+    kind: ElementKind,
+}
+
+/// What an element of the model defines.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementKind
+{
+    Package,
+    Enumeration
+    {
+        values: Vec<Arc<str>>,
+    },
+    Class
+    {
+        properties: Vec<Property>,
+    },
+    Measure
+    {
+        units: Vec<ElementId>,
+    },
+    Unit(Arc<Unit>),
+}
+
+/// A property of a class: a name with the type and multiplicity of its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property
+{
+    name: Arc<str>,
+    pure_type: Type,
+    multiplicity: Multiplicity,
+}
+
+impl Property
+{
+    pub fn new(name: &str, pure_type: Type, multiplicity: Multiplicity) -> Property { Property { name: Arc::from(name), pure_type, multiplicity } }
+
+    pub fn name(&self) -> &str { &self.name }
+
+    /// Checks that a collection can be the value of this property.
+    pub fn check(&self, value: &Collection) -> PureExecutionResult<()>
+    {
+        if !self.pure_type.is_assignable_from(&value.pure_type())
+        {
+            return Err(PureExecutionError::IllegalAssignment { from: value.pure_type(), to: self.pure_type });
+        }
+
+        let size: i64 = value.size().and_then(|v| v.try_into())?;
+        if !self.multiplicity.contains(size)
+        {
+            return Err(PureExecutionError::IllegalMultiplicity { size, mult: self.multiplicity });
+        }
+        Ok(())
+    }
+}
+
+impl Typed for Property
+{
+    fn pure_type(&self) -> Type { self.pure_type }
+}
+
+impl Multiplicitied for Property
+{
+    fn multiplicity(&self) -> Multiplicity { self.multiplicity }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -130,19 +362,82 @@ impl Element<'_>
 
     pub fn path(&self) -> String { self.model.path(self.data.id) }
 
-    pub fn parent(&self) -> Element
+    pub fn parent(&self) -> Element<'_>
     {
         let data = &self.model.elements[self.data.parent.0];
         Element { model: self.model, data }
     }
 
-    pub fn children(&self) -> Vec<Element> { self.data.children.iter().map(|id| self.model.get_element(id)).collect() }
+    pub fn children(&self) -> Vec<Element<'_>> { self.data.children.iter().map(|id| self.model.get_element(id)).collect() }
+
+    pub fn kind(&self) -> &ElementKind { &self.data.kind }
+
+    /// Returns the type this element defines, if it defines one.
+    pub fn pure_type(&self) -> Option<Type>
+    {
+        match &self.data.kind
+        {
+            ElementKind::Package => None,
+            ElementKind::Enumeration { .. } => Some(Type::Enum(self.data.id)),
+            ElementKind::Class { .. } => Some(Type::Class(self.data.id)),
+            ElementKind::Measure { .. } => Some(Type::Measure(self.data.id)),
+            ElementKind::Unit(unit) => Some(unit.pure_type()),
+        }
+    }
+
+    /// Returns the units of this measure, or `None` if this is not a measure.  The canonical unit of a convertible measure is first.
+    pub fn units(&self) -> Option<Vec<Element<'_>>>
+    {
+        match &self.data.kind
+        {
+            ElementKind::Measure { units } => Some(units.iter().map(|id| self.model.get_element(id)).collect()),
+            _ => None,
+        }
+    }
+
+    /// Returns this unit, which every call shares rather than each making its own.
+    pub fn unit(&self) -> PureExecutionResult<Arc<Unit>>
+    {
+        let ElementKind::Unit(unit) = &self.data.kind
+        else
+        {
+            return Err(PureExecutionError::WrongElementKind { name: self.path(), expected: "a Unit" });
+        };
+        Ok(unit.clone())
+    }
+
+    /// Returns the properties of this class, or `None` if this is not a class.
+    pub fn properties(&self) -> Option<&[Property]>
+    {
+        match &self.data.kind
+        {
+            ElementKind::Class { properties } => Some(properties),
+            _ => None,
+        }
+    }
+
+    /// Returns the named value of this enumeration.
+    pub fn enum_value(&self, name: &str) -> PureExecutionResult<Value>
+    {
+        let ElementKind::Enumeration { values } = &self.data.kind
+        else
+        {
+            return Err(PureExecutionError::WrongElementKind { name: self.path(), expected: "an Enumeration" });
+        };
+
+        match values.iter().position(|value| &**value == name)
+        {
+            Some(ordinal) => Ok(Value::Enum(EnumValue { enumeration: self.data.id, ordinal, name: values[ordinal].clone() })),
+            None => Err(PureExecutionError::IllegalValue { pure_type: Type::Enum(self.data.id), value: format!("{}.{name}", self.path()) }),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests
 {
     use super::*;
+    use crate::data::CollectionBuilder;
 
     #[test]
     fn basic_model() -> PureExecutionResult<()>
@@ -172,6 +467,206 @@ mod tests
         Ok(())
     }
 
+    #[test]
+    fn enumerations() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let colour_id = model.add_enumeration("domain::Colour", &["RED", "GREEN", "BLUE"])?;
+
+        let colour = model.get_element(&colour_id);
+        assert_eq!("domain::Colour", colour.path());
+        assert_eq!(Some(Type::Enum(colour_id)), colour.pure_type());
+        assert_eq!(None, colour.parent().pure_type());
+        assert_eq!("domain::Colour", model.type_name(&Type::Enum(colour_id)));
+        assert_eq!("Integer", model.type_name(&Type::Integer));
+
+        let green = model.enum_value("domain::Colour", "GREEN")?;
+        assert_eq!(Type::Enum(colour_id), green.pure_type());
+        assert_eq!("GREEN", green.to_string());
+        assert!(matches!(green, Value::Enum(ref v) if v.ordinal() == 1 && v.enumeration() == colour_id));
+        assert_eq!(green, colour.enum_value("GREEN")?);
+        assert_ne!(green, colour.enum_value("BLUE")?);
+
+        let bad = model.enum_value("domain::Colour", "PINK");
+        assert!(bad.is_err());
+        assert_eq!(format!("Illegal value for type Enum{colour_id}: domain::Colour.PINK"), bad.err().unwrap().to_string());
+
+        let bad = model.enum_value("domain::Shape", "SQUARE");
+        assert!(bad.is_err());
+        assert_eq!("UnknownElement: domain::Shape", bad.err().unwrap().to_string());
+
+        let bad = model.enum_value("domain", "SQUARE");
+        assert!(bad.is_err());
+        assert_eq!("WrongElementKind: domain is not an Enumeration", bad.err().unwrap().to_string());
+
+        let bad = model.add_enumeration("domain::Shape", &["SQUARE", "CIRCLE", "SQUARE"]);
+        assert!(bad.is_err());
+        assert_eq!("DuplicateElementName: domain::Shape.SQUARE", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn enum_values_in_collections() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let colour_id = model.add_enumeration("domain::Colour", &["RED", "GREEN"])?;
+        let size_id = model.add_enumeration("domain::Size", &["SMALL", "LARGE"])?;
+        let colour = Type::Enum(colour_id);
+
+        let colours = CollectionBuilder::new(colour, ZERO_MANY).push(model.enum_value("domain::Colour", "RED")?)?.build()?;
+        assert_eq!(colour, colours.pure_type());
+
+        let bad = CollectionBuilder::new(colour, ZERO_MANY).push(model.enum_value("domain::Size", "SMALL")?);
+        assert!(bad.is_err());
+        assert_eq!(format!("Illegal assignment: Enum{size_id} value cannot be assigned to Enum{colour_id}"), bad.err().unwrap().to_string());
+
+        let anys = CollectionBuilder::new(Type::Any, ZERO_MANY).push(model.enum_value("domain::Size", "SMALL")?)?.push(1)?.build()?;
+        assert_eq!(Value::from(2), anys.size()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn classes() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let firm_id = model.add_class("domain::Firm", vec![Property::new("name", Type::String, PURE_ONE)])?;
+        let person_id = model
+            .add_class("domain::Person", vec![Property::new("name", Type::String, PURE_ONE), Property::new("nicknames", Type::String, ZERO_MANY)])?;
+        model.add_property(person_id, Property::new("employer", Type::Class(firm_id), ZERO_ONE))?;
+        model.add_property(firm_id, Property::new("employees", Type::Class(person_id), ZERO_MANY))?;
+
+        let person = model.get_element(&person_id);
+        assert_eq!(Some(Type::Class(person_id)), person.pure_type());
+        assert!(Type::Class(person_id).is_class());
+        assert_eq!("domain::Person", model.type_name(&Type::Class(person_id)));
+        assert_eq!("List<domain::Person>", model.generic_type_name(&GenericType::new(Type::List, vec![Type::Class(person_id).into()])?));
+
+        let properties = person.properties().unwrap();
+        assert_eq!(vec!["name", "nicknames", "employer"], properties.iter().map(Property::name).collect::<Vec<_>>());
+        assert_eq!("String[*]", format!("{}{}", properties[1].pure_type(), properties[1].multiplicity()));
+        assert!(model.get_element(&ROOT).properties().is_none());
+
+        let bad = model.add_property(person_id, Property::new("name", Type::String, PURE_ONE));
+        assert!(bad.is_err());
+        assert_eq!("DuplicateElementName: domain::Person.name", bad.err().unwrap().to_string());
+
+        let bad = model.add_property(ROOT, Property::new("name", Type::String, PURE_ONE));
+        assert!(bad.is_err());
+        assert_eq!("WrongElementKind:  is not a Class", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn class_instances() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let firm_id = model.add_class("domain::Firm", vec![Property::new("name", Type::String, PURE_ONE)])?;
+        let person_id = model.add_class(
+            "domain::Person",
+            vec![
+                Property::new("name", Type::String, PURE_ONE),
+                Property::new("nicknames", Type::String, ZERO_MANY),
+                Property::new("employer", Type::Class(firm_id), ZERO_ONE),
+            ],
+        )?;
+
+        let firm = model.new_instance(firm_id, vec![("name", Collection::one("Acme")?)])?;
+        assert_eq!(Type::Class(firm_id), firm.pure_type());
+
+        let person = model.new_instance(person_id, vec![("employer", firm.clone().to_collection()), ("name", Collection::one("Fred")?)])?;
+        let Value::Instance(instance) = &person
+        else
+        {
+            panic!("Expected an instance");
+        };
+        assert_eq!(person_id, instance.class());
+        assert_eq!(Some(&Collection::one("Fred")?), instance.get("name"));
+        assert_eq!(Some(&Collection::zero(Type::String)), instance.get("nicknames"));
+        assert_eq!(Some(&firm.to_collection()), instance.get("employer"));
+        assert_eq!(None, instance.get("age"));
+
+        let people = CollectionBuilder::new(Type::Class(person_id), ZERO_MANY).push(person.clone())?.push(person)?.build()?;
+        assert_eq!(Value::from(2), people.size()?);
+
+        let bad =
+            CollectionBuilder::new(Type::Class(person_id), ZERO_MANY).push(model.new_instance(firm_id, vec![("name", Collection::one("Acme")?)])?);
+        assert!(bad.is_err());
+        assert_eq!(format!("Illegal assignment: Class{firm_id} value cannot be assigned to Class{person_id}"), bad.err().unwrap().to_string());
+
+        let bad = model.new_instance(person_id, vec![]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal multiplicity: size of 0 cannot be assigned to [1]", bad.err().unwrap().to_string());
+
+        let bad = model.new_instance(person_id, vec![("name", Collection::one(42)?)]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: Integer value cannot be assigned to String", bad.err().unwrap().to_string());
+
+        let bad = model.new_instance(person_id, vec![("name", Collection::one("Fred")?), ("age", Collection::one(42)?)]);
+        assert!(bad.is_err());
+        assert_eq!("UnknownElement: domain::Person.age", bad.err().unwrap().to_string());
+
+        let bad = model.new_instance(person_id, vec![("name", Collection::one("Fred")?), ("name", Collection::one("Bert")?)]);
+        assert!(bad.is_err());
+        assert_eq!("DuplicateElementName: domain::Person.name", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn measures() -> PureExecutionResult<()>
+    {
+        let mut model = Model::new();
+        let mass_id = model.add_measure("domain::Mass", "Gram", &[("Kilogram", BigDecimal::from(1000))])?;
+        let currency_id = model.add_non_convertible_measure("domain::Currency", &["USD", "GBP"])?;
+
+        let mass = model.get_element(&mass_id);
+        assert_eq!(Some(Type::Measure(mass_id)), mass.pure_type());
+        assert_eq!(vec!["Mass", "Mass~Gram", "Mass~Kilogram", "Currency", "Currency~USD", "Currency~GBP"], names(&mass.parent().children()));
+        assert_eq!(vec!["Mass~Gram", "Mass~Kilogram"], names(&mass.units().unwrap()));
+        assert!(model.get_element(&currency_id).units().is_some());
+        assert!(model.get_element(&ROOT).units().is_none());
+
+        let kilogram = model.unit("domain::Mass~Kilogram")?;
+        assert_eq!(mass_id, kilogram.measure());
+        assert_eq!(Some(&BigDecimal::from(1000)), kilogram.to_canonical());
+        assert_eq!(Some(&BigDecimal::from(1)), model.unit("domain::Mass~Gram")?.to_canonical());
+        assert_eq!(None, model.unit("domain::Currency~USD")?.to_canonical());
+        assert_eq!("Mass~Kilogram", kilogram.name());
+        assert!(Arc::ptr_eq(&kilogram, &model.unit("domain::Mass~Kilogram")?));
+
+        let kilogram_type = Type::Unit { measure: mass_id, unit: kilogram.id() };
+        assert_eq!(Some(kilogram_type), model.get_element(&kilogram.id()).pure_type());
+        assert_eq!("domain::Mass~Kilogram", model.type_name(&kilogram_type));
+        assert_eq!("domain::Mass", model.type_name(&Type::Measure(mass_id)));
+        assert!(Type::Measure(mass_id).is_assignable_from(&kilogram_type));
+        assert!(!Type::Measure(currency_id).is_assignable_from(&kilogram_type));
+        let gram_type = model.get_element(&model.unit("domain::Mass~Gram")?.id()).pure_type().unwrap();
+        assert_eq!(Type::Measure(mass_id), kilogram_type.common_supertype(&gram_type));
+
+        let bad = model.unit("domain::Mass");
+        assert!(bad.is_err());
+        assert_eq!("WrongElementKind: domain::Mass is not a Unit", bad.err().unwrap().to_string());
+
+        let bad = model.unit("domain::Mass~Tonne");
+        assert!(bad.is_err());
+        assert_eq!("UnknownElement: domain::Mass~Tonne", bad.err().unwrap().to_string());
+
+        let bad = model.add_non_convertible_measure("domain::Crypto", &["BTC", "BTC"]);
+        assert!(bad.is_err());
+        assert_eq!("DuplicateElementName: domain::Crypto~BTC", bad.err().unwrap().to_string());
+
+        let bad = model.add_measure("domain::Length", "Metre", &[("Nothing", BigDecimal::from(0))]);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Decimal: 0", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    fn names(elements: &[Element]) -> Vec<String> { elements.iter().map(|e| e.name().to_string()).collect() }
+
     #[test]
     fn cannot_add_duplicate() -> PureExecutionResult<()>
     {
@@ -0,0 +1,74 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::pure_type::Typed;
+use crate::*;
+
+/// A pair of values, such as those created by `zip`, of the type `Pair<U, V>` where `U` and `V` are the types of its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurePair
+{
+    first_type: GenericType,
+    second_type: GenericType,
+    values: Arc<(Value, Value)>,
+}
+
+impl PurePair
+{
+    /// Creates a pair whose values have the given types, checking that each value can be assigned to its type.
+    pub fn new(first_type: GenericType, first: Value, second_type: GenericType, second: Value) -> PureExecutionResult<PurePair>
+    {
+        for (ty, value) in [(&first_type, &first), (&second_type, &second)]
+        {
+            if !ty.is_assignable_from(&value.generic_type())
+            {
+                return Err(PureExecutionError::IllegalAssignment { from: value.pure_type(), to: ty.raw_type().unwrap_or(Type::Any) });
+            }
+        }
+        Ok(PurePair { first_type, second_type, values: Arc::new((first, second)) })
+    }
+
+    pub fn first(&self) -> &Value { &self.values.0 }
+    pub fn second(&self) -> &Value { &self.values.1 }
+    pub fn first_type(&self) -> &GenericType { &self.first_type }
+    pub fn second_type(&self) -> &GenericType { &self.second_type }
+}
+
+impl Typed for PurePair
+{
+    fn pure_type(&self) -> Type { Type::Pair }
+
+    fn generic_type(&self) -> GenericType
+    {
+        GenericType::Concrete { raw: Type::Pair, type_arguments: Arc::from([self.first_type.clone(), self.second_type.clone()]) }
+    }
+}
+
+impl fmt::Display for PurePair
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "<{}, {}>", self.first(), self.second()) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn pair_values() -> PureExecutionResult<()>
+    {
+        let pair = PurePair::new(GenericType::from(Type::Number), 1.into(), GenericType::from(Type::String), "a".into())?;
+        assert_eq!(&Value::from(1), pair.first());
+        assert_eq!(&Value::from("a"), pair.second());
+        assert_eq!("Pair<Number, String>", pair.generic_type().to_string());
+        assert_eq!("<1, a>", pair.to_string());
+
+        let bad = PurePair::new(GenericType::from(Type::Number), 1.into(), GenericType::from(Type::Integer), "a".into());
+        assert!(bad.is_err());
+        assert_eq!("Illegal assignment: String value cannot be assigned to Integer", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+}
@@ -37,6 +37,51 @@ pub enum PureExecutionError
     {
         pure_type: Type, value: String
     },
+    #[error("Integer overflow in {func}")]
+    IntegerOverflow
+    {
+        func: String
+    },
+    #[error("Incompatible units: {from} cannot be converted to {to}")]
+    IncompatibleUnits
+    {
+        from: String, to: String
+    },
+    #[error("Wrong type arguments: {pure_type} expects {expected} type arguments but got {got}")]
+    WrongTypeArguments
+    {
+        pure_type: Type, expected: usize, got: usize
+    },
+    #[error("Unparseable type {text}: {problem} at position {position}")]
+    UnparseableType
+    {
+        text: String, position: usize, problem: String
+    },
+    #[error("Wrong number of arguments: {func} expects {expected} arguments but got {got}")]
+    WrongArgumentCount
+    {
+        func: String, expected: usize, got: usize
+    },
+    #[error("No matching function: {name}({arguments}) does not match any of {candidates}")]
+    NoMatchingFunction
+    {
+        name: String, arguments: String, candidates: String
+    },
+    #[error("Ambiguous function: {name}({arguments}) matches all of {candidates}")]
+    AmbiguousFunction
+    {
+        name: String, arguments: String, candidates: String
+    },
+    #[error("Illegal lambda arguments: {signature} cannot be applied to ({arguments})")]
+    IllegalLambdaArguments
+    {
+        signature: String, arguments: String
+    },
+    #[error("Illegal result from the lambda passed to arg {arg} of {func}: {cause}")]
+    IllegalLambdaResult
+    {
+        func: String, arg: usize, cause: String
+    },
     #[error("Values ois of the wrong type: expected {expected}, found: {found}")]
     WrongType
     {
@@ -62,11 +107,23 @@ pub enum PureExecutionError
     {
         name: String
     },
+    #[error("UnknownElement: {name}")]
+    UnknownElement
+    {
+        name: String
+    },
+    #[error("WrongElementKind: {name} is not {expected}")]
+    WrongElementKind
+    {
+        name: String, expected: &'static str
+    },
     #[error("UnexpectedError: {problem}")]
     UnexpectedError
     {
         problem: &'static str
     },
+    #[error("Cursor error: {0}")]
+    CursorError(#[from] precursor::CursorError),
     #[error("Infallible")]
     Infallible(#[from] std::convert::Infallible),
 }
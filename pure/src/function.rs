@@ -1,5 +1,6 @@
 // Copyright 2022 Dave Wathen. All rights reserved.
 
+use std::cell::OnceCell;
 use std::marker::PhantomData;
 
 use crate::data::*;
@@ -7,11 +8,17 @@ use crate::pure_type::*;
 use crate::*;
 
 pub mod native;
+pub mod overload;
+pub mod registry;
 
 pub trait FunctionArgument
 {
     fn size(&self) -> PureExecutionResult<i64>;
 
+    fn type_of(&self) -> PureExecutionResult<Type>;
+
+    fn multiplicity_of(&self) -> PureExecutionResult<Multiplicity>;
+
     fn one<T>(&self, func: &str, arg: usize) -> PureExecutionResult<T>
     where
         T: 'static + TryFrom<Value, Error = PureExecutionError>;
@@ -20,54 +27,175 @@ pub trait FunctionArgument
     where
         T: 'static + TryFrom<Value, Error = PureExecutionError>;
 
-    fn many_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter>;
+    fn one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<&Value>;
+
+    fn zero_or_one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Option<&Value>>;
+
+    fn many_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter<'_>>;
 }
 
 impl FunctionArgument for &Collection
 {
     fn size(&self) -> PureExecutionResult<i64> { Collection::size(self).and_then(|v| v.try_into()) }
 
+    fn type_of(&self) -> PureExecutionResult<Type> { Ok(self.pure_type()) }
+
+    fn multiplicity_of(&self) -> PureExecutionResult<Multiplicity> { Ok(self.multiplicity()) }
+
     fn one<T>(&self, func: &str, arg: usize) -> PureExecutionResult<T>
     where
         T: 'static + TryFrom<Value, Error = PureExecutionError>,
     {
-        let result = if let CollectionContents::One(value) = &self.contents { T::try_from(value.clone()).ok() } else { None };
-
-        result.ok_or_else(|| {
-            let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{}[1]", pure_type_of::<T>()), got: self.full_type_as_string() };
-            PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() }
-        })
+        one(self, func, arg)
     }
 
     fn many<T>(&self, func: &str, arg: usize) -> PureExecutionResult<IterAdaptor<'_, T>>
     where
         T: 'static + TryFrom<Value, Error = PureExecutionError>,
     {
-        if self.pure_type() == pure_type_of::<T>()
+        many(self, func, arg)
+    }
+
+    fn one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<&Value> { one_raw(self, func, arg, pure_type) }
+
+    fn zero_or_one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Option<&Value>>
+    {
+        zero_or_one_raw(self, func, arg, pure_type)
+    }
+
+    fn many_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter<'_>> { many_raw(self, func, arg, pure_type) }
+}
+
+/// An argument that is only evaluated when a native first needs its value, so that natives such as `and` and `or` can avoid
+/// evaluating an argument whose value would not change their result.  The value is kept once evaluated.
+pub struct Deferred<F>
+where
+    F: Fn() -> PureExecutionResult<Collection>,
+{
+    thunk: F,
+    value: OnceCell<Collection>,
+}
+
+impl<F> Deferred<F>
+where
+    F: Fn() -> PureExecutionResult<Collection>,
+{
+    pub fn new(thunk: F) -> Deferred<F> { Deferred { thunk, value: OnceCell::new() } }
+
+    pub fn is_evaluated(&self) -> bool { self.value.get().is_some() }
+
+    fn force(&self) -> PureExecutionResult<&Collection>
+    {
+        if let Some(value) = self.value.get()
         {
-            Ok(IterAdaptor { iter: self.into_iter(), phantom: PhantomData })
+            return Ok(value);
         }
-        else
+        let value = (self.thunk)()?;
+        Ok(self.value.get_or_init(|| value))
+    }
+}
+
+impl<F> FunctionArgument for &Deferred<F>
+where
+    F: Fn() -> PureExecutionResult<Collection>,
+{
+    fn size(&self) -> PureExecutionResult<i64> { self.force()?.size().and_then(|v| v.try_into()) }
+
+    fn type_of(&self) -> PureExecutionResult<Type> { Ok(self.force()?.pure_type()) }
+
+    fn multiplicity_of(&self) -> PureExecutionResult<Multiplicity> { Ok(self.force()?.multiplicity()) }
+
+    fn one<T>(&self, func: &str, arg: usize) -> PureExecutionResult<T>
+    where
+        T: 'static + TryFrom<Value, Error = PureExecutionError>,
+    {
+        one(self.force()?, func, arg)
+    }
+
+    fn many<T>(&self, func: &str, arg: usize) -> PureExecutionResult<IterAdaptor<'_, T>>
+    where
+        T: 'static + TryFrom<Value, Error = PureExecutionError>,
+    {
+        many(self.force()?, func, arg)
+    }
+
+    fn one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<&Value> { one_raw(self.force()?, func, arg, pure_type) }
+
+    fn zero_or_one_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Option<&Value>>
+    {
+        zero_or_one_raw(self.force()?, func, arg, pure_type)
+    }
+
+    fn many_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter<'_>> { many_raw(self.force()?, func, arg, pure_type) }
+}
+
+fn one<T>(col: &Collection, func: &str, arg: usize) -> PureExecutionResult<T>
+where
+    T: 'static + TryFrom<Value, Error = PureExecutionError>,
+{
+    let result = if let CollectionContents::One(value) = &col.contents { T::try_from(value.clone()).ok() } else { None };
+
+    result.ok_or_else(|| {
+        let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{}[1]", pure_type_of::<T>()), got: col.full_type_as_string() };
+        PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() }
+    })
+}
+
+fn many<'a, T>(col: &'a Collection, func: &str, arg: usize) -> PureExecutionResult<IterAdaptor<'a, T>>
+where
+    T: 'static + TryFrom<Value, Error = PureExecutionError>,
+{
+    if col.pure_type() == pure_type_of::<T>()
+    {
+        Ok(IterAdaptor { iter: col.into_iter(), phantom: PhantomData })
+    }
+    else
+    {
+        let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{}[*]", pure_type_of::<T>()), got: col.full_type_as_string() };
+        Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() })
+    }
+}
+
+fn one_raw<'a>(col: &'a Collection, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<&'a Value>
+{
+    match &col.contents
+    {
+        CollectionContents::One(value) if pure_type.is_assignable_from(&value.pure_type()) => Ok(value),
+        _ =>
         {
-            let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{}[*]", pure_type_of::<T>()), got: self.full_type_as_string() };
+            let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{pure_type}[1]"), got: col.full_type_as_string() };
             Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() })
         }
     }
+}
 
-    fn many_raw(&self, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter>
+fn zero_or_one_raw<'a>(col: &'a Collection, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Option<&'a Value>>
+{
+    match &col.contents
     {
-        if pure_type.is_assignable_from(&self.pure_type())
+        CollectionContents::Zero if pure_type.is_assignable_from(&col.pure_type()) => Ok(None),
+        CollectionContents::One(value) if pure_type.is_assignable_from(&value.pure_type()) => Ok(Some(value)),
+        _ =>
         {
-            Ok(self.into_iter())
-        }
-        else
-        {
-            let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{pure_type}[*]"), got: self.full_type_as_string() };
+            let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{pure_type}[0..1]"), got: col.full_type_as_string() };
             Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() })
         }
     }
 }
 
+fn many_raw<'a>(col: &'a Collection, func: &str, arg: usize, pure_type: Type) -> PureExecutionResult<Iter<'a>>
+{
+    if pure_type.is_assignable_from(&col.pure_type())
+    {
+        Ok(col.into_iter())
+    }
+    else
+    {
+        let unexpected = PureExecutionError::UnexpectedValue { expected: format!("{pure_type}[*]"), got: col.full_type_as_string() };
+        Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() })
+    }
+}
+
 pub struct IterAdaptor<'a, T>
 where
     T: TryFrom<Value, Error = PureExecutionError>,
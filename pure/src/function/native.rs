@@ -1,112 +1,76 @@
 // // Copyright 2022 Dave Wathen. All& rights reserved.
 
+use pure_macros::pure_native;
+
+use crate::function::registry::NativeRegistry;
 use crate::function::*;
 
-pub use meta_pure_functions_boolean_and_Boolean_1__Boolean_1__Boolean_1_ as and;
-pub use meta_pure_functions_boolean_not_Boolean_1__Boolean_1_ as not;
-pub use meta_pure_functions_boolean_or_Boolean_1__Boolean_1__Boolean_1_ as or;
-pub use meta_pure_functions_collection_isEmpty_Any_MANY__Boolean_1_ as is_empty;
-pub use meta_pure_functions_collection_isNotEmpty_Any_MANY__Boolean_1_ as is_not_empty;
-pub use meta_pure_functions_math_plus_Float_MANY__Float_1__ as fplus;
-pub use meta_pure_functions_math_plus_Integer_MANY__Integer_1__ as iplus;
-pub use meta_pure_functions_math_plus_Number_MANY__Number_1__ as nplus;
-
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_boolean_and_Boolean_1__Boolean_1__Boolean_1_<L, R>(left: L, right: R) -> PureExecutionResult<Collection>
-where
-    L: FunctionArgument,
-    R: FunctionArgument,
+pub use self::binary::*;
+pub use self::boolean::*;
+pub use self::collection::*;
+pub use self::date::*;
+pub use self::math::*;
+pub use self::measure::*;
+pub use self::string::*;
+
+pub mod binary;
+pub mod boolean;
+pub mod collection;
+pub mod date;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod math;
+pub mod measure;
+pub mod string;
+
+/// Tests whether both arguments are true.  The right argument is only evaluated, if it is `Deferred`, when the left is true.
+#[pure_native("meta::pure::functions::boolean::and(Boolean[1], Boolean[1]):Boolean[1]")]
+fn and(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
 {
-    const FUNC: &str = "meta::pure::functions::boolean::and_Boolean_1__Boolean_1__Boolean_1_";
-
     Collection::one(left.one(FUNC, 0)? && right.one(FUNC, 1)?)
 }
 
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_boolean_or_Boolean_1__Boolean_1__Boolean_1_<L, R>(left: L, right: R) -> PureExecutionResult<Collection>
-where
-    L: FunctionArgument,
-    R: FunctionArgument,
+/// Tests whether either argument is true.  The right argument is only evaluated, if it is `Deferred`, when the left is false.
+#[pure_native("meta::pure::functions::boolean::or(Boolean[1], Boolean[1]):Boolean[1]")]
+fn or(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
 {
-    const FUNC: &str = "meta::pure::functions::boolean::or_Boolean_1__Boolean_1__Boolean_1_";
-
     Collection::one(left.one(FUNC, 0)? || right.one(FUNC, 1)?)
 }
 
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_boolean_not_Boolean_1__Boolean_1_<C>(col: C) -> PureExecutionResult<Collection>
-where
-    C: FunctionArgument,
-{
-    const FUNC: &str = "meta::pure::functions::boolean::not_Boolean_1__Boolean_1_";
-
-    Collection::one(!col.one::<bool>(FUNC, 0)?)
-}
+#[pure_native("meta::pure::functions::boolean::not(Boolean[1]):Boolean[1]")]
+fn not(col: bool) -> PureExecutionResult<Collection> { Collection::one(!col) }
 
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_math_plus_Integer_MANY__Integer_1__<C>(col: C) -> PureExecutionResult<Collection>
-where
-    C: FunctionArgument,
-{
-    const FUNC: &str = "meta::pure::functions::math::plus_Integer_MANY__Integer_1_";
+#[pure_native("meta::pure::functions::collection::isEmpty(Any[*]):Boolean[1]")]
+fn is_empty(c: impl FunctionArgument) -> PureExecutionResult<Collection> { Collection::one(c.size()? == 0) }
 
-    let iter = col.many::<i64>(FUNC, 0)?;
-    let mut error = None;
-    let sum: i64 = iter.filter_map(|x| x.map_err(|e| error = Some(e)).ok()).sum();
-    error.map_or_else(|| Collection::one(sum), Err)
-}
+#[pure_native("meta::pure::functions::collection::isNotEmpty(Any[*]):Boolean[1]")]
+fn is_not_empty(c: impl FunctionArgument) -> PureExecutionResult<Collection> { not(&is_empty(c)?) }
 
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_math_plus_Float_MANY__Float_1__<C>(col: C) -> PureExecutionResult<Collection>
-where
-    C: FunctionArgument,
+/// Adds all the natives that take only collections as arguments to a registry, each module's overloads in the order they are
+/// preferred.  The natives of `measure` that take a unit are not registered since a unit is a type rather than a value.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
 {
-    const FUNC: &str = "meta::pure::functions::math::plus_Float_MANY__Float_1_";
-
-    let iter = col.many::<f64>(FUNC, 0)?;
-    let mut error = None;
-    let sum: f64 = iter.filter_map(|x| x.map_err(|e| error = Some(e)).ok()).sum();
-    error.map_or_else(|| Collection::one(sum), Err)
-}
-
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_math_plus_Number_MANY__Number_1__<C>(col: C) -> PureExecutionResult<Collection>
-where
-    C: FunctionArgument,
-{
-    const FUNC: &str = "meta::pure::functions::math::plus_Number_MANY__Number_1_";
-
-    let mut error = None;
-    let sum = col.many_raw(FUNC, 0, Type::Number)?.fold(0.into(), |a, n| match (a, n)
-    {
-        (Value::Integer(l), Value::Integer(r)) => Value::Integer(l + r),
-        (Value::Integer(l), Value::Float(r)) => Value::Float(l as f64 + r),
-        (Value::Float(l), Value::Integer(r)) => Value::Float(l + *r as f64),
-        (Value::Float(l), Value::Float(r)) => Value::Float(l + r),
-        _ =>
-        {
-            error = Some(PureExecutionError::UnexpectedValue { expected: "Number".into(), got: n.pure_type().to_string() });
-            0.into()
-        }
-    });
-
-    error.map_or_else(|| Ok(sum.to_collection()), Err)
+    register_and(registry)?;
+    register_or(registry)?;
+    register_not(registry)?;
+    register_is_empty(registry)?;
+    register_is_not_empty(registry)?;
+    binary::register(registry)?;
+    boolean::register(registry)?;
+    collection::register(registry)?;
+    date::register(registry)?;
+    math::register(registry)?;
+    measure::register(registry)?;
+    string::register(registry)?;
+    Ok(())
 }
 
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_collection_isEmpty_Any_MANY__Boolean_1_<C>(c: C) -> PureExecutionResult<Collection>
+/// Builds a collection of the declared type and multiplicity of the result of a native.
+pub(crate) fn collection_of<I>(pure_type: Type, multiplicity: Multiplicity, values: I) -> PureExecutionResult<Collection>
 where
-    C: FunctionArgument,
+    I: IntoIterator<Item = Value>,
 {
-    Collection::one(c.size()? == 0)
-}
-
-#[allow(non_snake_case)]
-pub fn meta_pure_functions_collection_isNotEmpty_Any_MANY__Boolean_1_<C>(c: C) -> PureExecutionResult<Collection>
-where
-    C: FunctionArgument,
-{
-    not(&is_empty(c)?)
+    values.into_iter().try_fold(CollectionBuilder::new(pure_type, multiplicity), |builder, value| builder.push(value))?.build()
 }
 
 #[cfg(test)]
@@ -136,8 +100,7 @@ mod tests
             bad.err().unwrap().to_string()
         );
 
-        // Error undetected due to lazy eval
-        // TODO Lazy eval for function as argument
+        // The right argument is not needed so is not checked
         assert_eq!(Collection::one(false)?, and(&Collection::one(false)?, &Collection::zero(Type::Integer))?);
 
         Ok(())
@@ -165,80 +128,62 @@ mod tests
             format!("{}", bad.err().unwrap())
         );
 
-        // Error undetected due to lazy eval
-        // TODO Lazy eval for function as argument
+        // The right argument is not needed so is not checked
         assert_eq!(Collection::one(true)?, or(&Collection::one(true)?, &Collection::zero(Type::Integer))?);
 
         Ok(())
     }
 
     #[test]
-    fn boolean_not() -> PureExecutionResult<()>
+    fn boolean_and_or_defer_their_right_argument() -> PureExecutionResult<()>
     {
-        assert_eq!(Collection::one(true)?, not(&Collection::one(false)?)?);
-        assert_eq!(Collection::one(false)?, not(&Collection::one(true)?)?);
+        let unusable = Deferred::new(|| Err(PureExecutionError::UnexpectedError { problem: "should not be evaluated" }));
+        assert_eq!(Collection::one(false)?, and(&Collection::one(false)?, &unusable)?);
+        assert_eq!(Collection::one(true)?, or(&Collection::one(true)?, &unusable)?);
+        assert!(!unusable.is_evaluated());
 
-        let bad = not(&ZERO_NIL);
+        let bad = and(&Collection::one(true)?, &unusable);
         assert!(bad.is_err());
-        assert_eq!(
-            "Invalid argument passed to arg 0 of meta::pure::functions::boolean::not_Boolean_1__Boolean_1_: Unexpected value: expected Boolean[1] but got Nil[0]",
-            format!("{}", bad.err().unwrap())
-        );
+        assert_eq!("UnexpectedError: should not be evaluated", bad.err().unwrap().to_string());
 
-        Ok(())
-    }
-
-    #[test]
-    fn integer_plus() -> PureExecutionResult<()>
-    {
-        assert_eq!(Collection::one(0)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?)?);
-        assert_eq!(Collection::one(1)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.build()?)?);
-        assert_eq!(Collection::one(6)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.push(2)?.push(3)?.build()?)?);
-
-        let bad = iplus(&ZERO_NIL);
+        let wrong_type = Deferred::new(|| Collection::one(1));
+        let bad = or(&Collection::one(false)?, &wrong_type);
         assert!(bad.is_err());
         assert_eq!(
-            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Integer_MANY__Integer_1_: Unexpected value: expected Integer[*] but got Nil[0]",
-            format!("{}", bad.err().unwrap())
+            "Invalid argument passed to arg 1 of meta::pure::functions::boolean::or_Boolean_1__Boolean_1__Boolean_1_: Unexpected value: expected Boolean[1] but got Integer[1]",
+            bad.err().unwrap().to_string()
         );
+        assert!(wrong_type.is_evaluated());
 
         Ok(())
     }
 
     #[test]
-    fn float_plus() -> PureExecutionResult<()>
+    fn boolean_and_guards_its_right_argument() -> PureExecutionResult<()>
     {
-        assert_eq!(Collection::one(0.0)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).build()?)?);
-        assert_eq!(Collection::one(1.1)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.build()?)?);
-        assert_eq!(Collection::one(6.6)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.push(2.2)?.push(3.3)?.build()?)?);
+        // $x->isNotEmpty() && $x->at(0) > 0
+        let guarded = |x: &Collection| -> PureExecutionResult<Collection> {
+            let positive = Deferred::new(|| greater_than_number(&at(x, &Collection::one(0)?)?, &Collection::one(0)?));
+            and(&is_not_empty(x)?, &positive)
+        };
 
-        let bad = fplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?);
-        assert!(bad.is_err());
-        assert_eq!(
-            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Float_MANY__Float_1_: Unexpected value: expected Float[*] but got Integer[*]",
-            format!("{}", bad.err().unwrap())
-        );
+        assert_eq!(Collection::one(true)?, guarded(&Collection::one(5)?)?);
+        assert_eq!(Collection::one(false)?, guarded(&Collection::one(-5)?)?);
+        assert_eq!(Collection::one(false)?, guarded(&Collection::zero(Type::Integer))?);
 
         Ok(())
     }
 
     #[test]
-    fn number_plus() -> PureExecutionResult<()>
+    fn boolean_not() -> PureExecutionResult<()>
     {
-        assert_eq!(Collection::one(0)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?)?);
-        assert_eq!(Collection::one(1)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.build()?)?);
-        assert_eq!(Collection::one(6)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.push(2)?.push(3)?.build()?)?);
-
-        assert_eq!(Collection::one(0)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).build()?)?);
-        assert_eq!(Collection::one(1.1)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.build()?)?);
-        assert_eq!(Collection::one(6.6)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.push(2.2)?.push(3.3)?.build()?)?);
-
-        assert_eq!(Collection::one(6.4)?, nplus(&CollectionBuilder::new(Type::Number, ZERO_MANY).push(1.1)?.push(2)?.push(3.3)?.build()?)?);
+        assert_eq!(Collection::one(true)?, not(&Collection::one(false)?)?);
+        assert_eq!(Collection::one(false)?, not(&Collection::one(true)?)?);
 
-        let bad = nplus(&CollectionBuilder::new(Type::Boolean, ZERO_MANY).build()?);
+        let bad = not(&ZERO_NIL);
         assert!(bad.is_err());
         assert_eq!(
-            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Number_MANY__Number_1_: Unexpected value: expected Number[*] but got Boolean[*]",
+            "Invalid argument passed to arg 0 of meta::pure::functions::boolean::not_Boolean_1__Boolean_1_: Unexpected value: expected Boolean[1] but got Nil[0]",
             format!("{}", bad.err().unwrap())
         );
 
@@ -0,0 +1,126 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use pure_macros::pure_native;
+
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+#[pure_native("meta::pure::functions::binary::length(Binary[1]):Integer[1]")]
+fn binary_length(binary: Arc<[u8]>) -> PureExecutionResult<Collection> { Collection::one(binary.len()) }
+
+#[pure_native("meta::pure::functions::binary::toBase64(Binary[1]):String[1]")]
+fn to_base64(binary: Arc<[u8]>) -> PureExecutionResult<Collection> { Collection::one(STANDARD.encode(binary)) }
+
+#[pure_native("meta::pure::functions::binary::fromBase64(String[1]):Binary[1]")]
+fn from_base64(text: String) -> PureExecutionResult<Collection>
+{
+    let bytes = STANDARD.decode(&text).map_err(|_| PureExecutionError::IllegalValue { pure_type: Type::Binary, value: text })?;
+    Collection::one(bytes)
+}
+
+#[pure_native("meta::pure::functions::binary::toHex(Binary[1]):String[1]")]
+fn to_hex(binary: Arc<[u8]>) -> PureExecutionResult<Collection> { Collection::one(hex::encode(binary)) }
+
+/// Decodes hexadecimal text, in either case, into a Binary.
+#[pure_native("meta::pure::functions::binary::fromHex(String[1]):Binary[1]")]
+fn from_hex(text: String) -> PureExecutionResult<Collection>
+{
+    let bytes = hex::decode(&text).map_err(|_| PureExecutionError::IllegalValue { pure_type: Type::Binary, value: text })?;
+    Collection::one(bytes)
+}
+
+/// Compares two binaries byte by byte giving -1, 0 or 1.  A binary that is a prefix of another is less than it.
+#[pure_native("meta::pure::functions::binary::compare(Binary[1], Binary[1]):Integer[1]")]
+fn compare_binary(left: Arc<[u8]>, right: Arc<[u8]>) -> PureExecutionResult<Collection> { Collection::one(left.cmp(&right) as i64) }
+
+/// Adds the binary natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_binary_length(registry)?;
+    register_to_base64(registry)?;
+    register_from_base64(registry)?;
+    register_to_hex(registry)?;
+    register_from_hex(registry)?;
+    register_compare_binary(registry)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn binary(bytes: &[u8]) -> PureExecutionResult<Collection> { Collection::one(bytes) }
+
+    #[test]
+    fn binary_length() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0)?, super::binary_length(&binary(&[])?)?);
+        assert_eq!(Collection::one(3)?, super::binary_length(&binary(&[1, 2, 3])?)?);
+
+        let bad = super::binary_length(&Collection::one("abc")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::binary::length_Binary_1__Integer_1_: Unexpected value: expected Binary[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_base64() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one("")?, to_base64(&binary(&[])?)?);
+        assert_eq!(Collection::one("SGVsbG8=")?, to_base64(&binary(b"Hello")?)?);
+        assert_eq!(binary(b"Hello")?, from_base64(&Collection::one("SGVsbG8=")?)?);
+        assert_eq!(binary(&[0xff, 0x00, 0x7f])?, from_base64(&to_base64(&binary(&[0xff, 0x00, 0x7f])?)?)?);
+
+        let bad = from_base64(&Collection::one("SGVsbG8")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Binary: SGVsbG8", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_hex() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one("")?, to_hex(&binary(&[])?)?);
+        assert_eq!(Collection::one("00ff7f")?, to_hex(&binary(&[0x00, 0xff, 0x7f])?)?);
+        assert_eq!(binary(&[0xde, 0xad, 0xbe, 0xef])?, from_hex(&Collection::one("DEADbeef")?)?);
+
+        let bad = from_hex(&Collection::one("abc")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Binary: abc", bad.err().unwrap().to_string());
+
+        let bad = from_hex(&Collection::one("zz")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Binary: zz", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_compare() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0)?, compare_binary(&binary(&[1, 2])?, &binary(&[1, 2])?)?);
+        assert_eq!(Collection::one(-1)?, compare_binary(&binary(&[1, 2])?, &binary(&[1, 3])?)?);
+        assert_eq!(Collection::one(1)?, compare_binary(&binary(&[2])?, &binary(&[1, 3])?)?);
+        assert_eq!(Collection::one(-1)?, compare_binary(&binary(&[1])?, &binary(&[1, 0])?)?);
+        assert_eq!(Collection::one(1)?, compare_binary(&binary(&[1])?, &binary(&[])?)?);
+
+        let bad = compare_binary(&binary(&[1])?, &Collection::zero(Type::Binary));
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::binary::compare_Binary_1__Binary_1__Integer_1_: Unexpected value: expected Binary[1] but got Binary[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
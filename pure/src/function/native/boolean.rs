@@ -0,0 +1,308 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use pure_macros::pure_native;
+
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+/// Tests whether two values are the same.  Primitives are compared by value, with numbers of different types equal when they have
+/// the same value, while a class instance is only the same as itself, as Java's `==` compares objects.  Two instances with equal
+/// property values are therefore not `eq`, although they are `equal`.
+#[pure_native("meta::pure::functions::boolean::eq(Any[1], Any[1]):Boolean[1]")]
+fn eq(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let same = match (left, right)
+    {
+        (Value::Instance(l), Value::Instance(r)) => Arc::ptr_eq(l, r),
+        _ => left.equals(right),
+    };
+    Collection::one(same)
+}
+
+/// Tests whether two collections have equal values in the same order.  Values are compared as by `eq` except that class instances
+/// are compared by their contents: instances are equal when they are of the same class and have equal values for each property.
+#[pure_native("meta::pure::functions::boolean::equal(Any[*], Any[*]):Boolean[1]")]
+fn equal(left: Vec<&Value>, right: Vec<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(left.len() == right.len() && left.iter().zip(&right).all(|(l, r)| l.equals(r)))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThan(Number[0..1], Number[0..1]):Boolean[1]")]
+fn less_than_number(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_numbers_of)?.is_some_and(Ordering::is_lt))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThanEqual(Number[0..1], Number[0..1]):Boolean[1]")]
+fn less_than_equal_number(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_numbers_of)?.is_some_and(Ordering::is_le))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThan(Number[0..1], Number[0..1]):Boolean[1]")]
+fn greater_than_number(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_numbers_of)?.is_some_and(Ordering::is_gt))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThanEqual(Number[0..1], Number[0..1]):Boolean[1]")]
+fn greater_than_equal_number(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_numbers_of)?.is_some_and(Ordering::is_ge))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThan(Date[0..1], Date[0..1]):Boolean[1]")]
+fn less_than_date(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_dates_of)?.is_some_and(Ordering::is_lt))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThanEqual(Date[0..1], Date[0..1]):Boolean[1]")]
+fn less_than_equal_date(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_dates_of)?.is_some_and(Ordering::is_le))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThan(Date[0..1], Date[0..1]):Boolean[1]")]
+fn greater_than_date(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_dates_of)?.is_some_and(Ordering::is_gt))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThanEqual(Date[0..1], Date[0..1]):Boolean[1]")]
+fn greater_than_equal_date(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_dates_of)?.is_some_and(Ordering::is_ge))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThan(String[0..1], String[0..1]):Boolean[1]")]
+fn less_than_string(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_strings_of)?.is_some_and(Ordering::is_lt))
+}
+
+#[pure_native("meta::pure::functions::boolean::lessThanEqual(String[0..1], String[0..1]):Boolean[1]")]
+fn less_than_equal_string(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_strings_of)?.is_some_and(Ordering::is_le))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThan(String[0..1], String[0..1]):Boolean[1]")]
+fn greater_than_string(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_strings_of)?.is_some_and(Ordering::is_gt))
+}
+
+#[pure_native("meta::pure::functions::boolean::greaterThanEqual(String[0..1], String[0..1]):Boolean[1]")]
+fn greater_than_equal_string(left: Option<&Value>, right: Option<&Value>) -> PureExecutionResult<Collection>
+{
+    Collection::one(compare(FUNC, left, right, compare_strings_of)?.is_some_and(Ordering::is_ge))
+}
+
+/// Adds the equality and comparison natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_eq(registry)?;
+    register_equal(registry)?;
+    register_less_than_number(registry)?;
+    register_less_than_equal_number(registry)?;
+    register_greater_than_number(registry)?;
+    register_greater_than_equal_number(registry)?;
+    register_less_than_date(registry)?;
+    register_less_than_equal_date(registry)?;
+    register_greater_than_date(registry)?;
+    register_greater_than_equal_date(registry)?;
+    register_less_than_string(registry)?;
+    register_less_than_equal_string(registry)?;
+    register_greater_than_string(registry)?;
+    register_greater_than_equal_string(registry)?;
+    Ok(())
+}
+
+/// Compares two optional values.  If either is empty, or the values are unordered such as a NaN, there is no ordering and every
+/// comparison is false.
+fn compare<C>(func: &str, left: Option<&Value>, right: Option<&Value>, comparator: C) -> PureExecutionResult<Option<Ordering>>
+where
+    C: Fn(&str, &Value, &Value) -> PureExecutionResult<Option<Ordering>>,
+{
+    match (left, right)
+    {
+        (Some(left), Some(right)) => comparator(func, left, right),
+        _ => Ok(None),
+    }
+}
+
+fn compare_numbers_of(_: &str, left: &Value, right: &Value) -> PureExecutionResult<Option<Ordering>> { Ok(left.compare_numbers(right)) }
+
+fn compare_dates_of(func: &str, left: &Value, right: &Value) -> PureExecutionResult<Option<Ordering>>
+{
+    let cause = || format!("{} {left} cannot be compared with {} {right}", left.pure_type(), right.pure_type());
+    left.compare_dates(right).map(Some).ok_or_else(|| PureExecutionError::IllegalArgument { func: func.to_string(), arg: 1, cause: cause() })
+}
+
+fn compare_strings_of(_: &str, left: &Value, right: &Value) -> PureExecutionResult<Option<Ordering>>
+{
+    match (left, right)
+    {
+        (Value::String(l), Value::String(r)) => Ok(Some(l.cmp(r))),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::date;
+
+    fn many(values: &[Value]) -> PureExecutionResult<Collection>
+    {
+        values.iter().try_fold(CollectionBuilder::new(Type::Any, ZERO_MANY), |builder, v| builder.push(v.clone()))?.build()
+    }
+
+    #[test]
+    fn boolean_eq() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(true)?, eq(&Collection::one(1)?, &Collection::one(1)?)?);
+        assert_eq!(Collection::one(true)?, eq(&Collection::one(1)?, &Collection::one(1.0)?)?);
+        assert_eq!(Collection::one(true)?, eq(&Collection::one(Value::decimal("1.0")?)?, &Collection::one(1)?)?);
+        assert_eq!(Collection::one(false)?, eq(&Collection::one(1)?, &Collection::one("1")?)?);
+        assert_eq!(Collection::one(true)?, eq(&Collection::one("a")?, &Collection::one("a")?)?);
+        assert_eq!(Collection::one(true)?, eq(&date("%2022-03-01")?, &date("%2022-03-01")?)?);
+
+        let bad = eq(&Collection::zero(Type::Integer), &Collection::one(1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::boolean::eq_Any_1__Any_1__Boolean_1_: Unexpected value: expected Any[1] but got Integer[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_eq_compares_instances_by_identity_and_equal_by_contents() -> PureExecutionResult<()>
+    {
+        let mut model = crate::data::model::Model::new();
+        let firm = model.add_class("domain::Firm", vec![crate::data::model::Property::new("name", Type::String, PURE_ONE)])?;
+        let person = model.add_class("domain::Person", vec![crate::data::model::Property::new("name", Type::String, PURE_ONE)])?;
+        let acme = model.new_instance(firm, vec![("name", Collection::one("Acme")?)])?;
+        let other_acme = model.new_instance(firm, vec![("name", Collection::one("Acme")?)])?;
+        let globex = model.new_instance(firm, vec![("name", Collection::one("Globex")?)])?;
+        let person_acme = model.new_instance(person, vec![("name", Collection::one("Acme")?)])?;
+
+        assert_eq!(Collection::one(true)?, eq(&acme.clone().to_collection(), &acme.clone().to_collection())?);
+        assert_eq!(Collection::one(false)?, eq(&acme.clone().to_collection(), &other_acme.clone().to_collection())?);
+        assert_eq!(Collection::one(true)?, equal(&acme.clone().to_collection(), &other_acme.clone().to_collection())?);
+        assert_eq!(Collection::one(false)?, equal(&acme.clone().to_collection(), &globex.to_collection())?);
+        assert_eq!(Collection::one(false)?, equal(&acme.to_collection(), &person_acme.to_collection())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_equal() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(true)?, equal(&many(&[])?, &ZERO_NIL)?);
+        assert_eq!(Collection::one(true)?, equal(&many(&[Value::from(1), Value::from("a")])?, &many(&[Value::from(1.0), Value::from("a")])?)?);
+        assert_eq!(Collection::one(false)?, equal(&many(&[Value::from(1), Value::from("a")])?, &many(&[Value::from("a"), Value::from(1)])?)?);
+        assert_eq!(Collection::one(false)?, equal(&many(&[Value::from(1)])?, &many(&[Value::from(1), Value::from(1)])?)?);
+        assert_eq!(Collection::one(false)?, equal(&Collection::one(1)?, &ZERO_NIL)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_number_comparisons() -> PureExecutionResult<()>
+    {
+        let one = Collection::one(1)?;
+        let one_and_half = Collection::one(1.5)?;
+        let decimal_one = Collection::one(Value::decimal("1.00")?)?;
+
+        assert_eq!(Collection::one(true)?, less_than_number(&one, &one_and_half)?);
+        assert_eq!(Collection::one(false)?, less_than_number(&one, &decimal_one)?);
+        assert_eq!(Collection::one(true)?, less_than_equal_number(&one, &decimal_one)?);
+        assert_eq!(Collection::one(true)?, greater_than_number(&one_and_half, &decimal_one)?);
+        assert_eq!(Collection::one(false)?, greater_than_number(&decimal_one, &one)?);
+        assert_eq!(Collection::one(true)?, greater_than_equal_number(&decimal_one, &one)?);
+        assert_eq!(Collection::one(false)?, greater_than_equal_number(&one, &one_and_half)?);
+
+        let nan = Collection::one(f64::NAN)?;
+        assert_eq!(Collection::one(false)?, less_than_number(&nan, &one)?);
+        assert_eq!(Collection::one(false)?, greater_than_equal_number(&nan, &one)?);
+
+        let bad = less_than_number(&one, &Collection::one("2")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::boolean::lessThan_Number_$0_1$__Number_$0_1$__Boolean_1_: Unexpected value: expected Number[0..1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_comparisons_with_empty_are_false() -> PureExecutionResult<()>
+    {
+        let one = Collection::one(1)?;
+        let empty = Collection::zero(Type::Integer);
+
+        assert_eq!(Collection::one(false)?, less_than_number(&empty, &one)?);
+        assert_eq!(Collection::one(false)?, less_than_equal_number(&one, &empty)?);
+        assert_eq!(Collection::one(false)?, greater_than_number(&empty, &empty)?);
+        assert_eq!(Collection::one(false)?, greater_than_equal_number(&ZERO_NIL, &one)?);
+        assert_eq!(Collection::one(false)?, less_than_date(&Collection::zero(Type::Date), &date("%2022-03-01")?)?);
+        assert_eq!(Collection::one(false)?, less_than_string(&Collection::zero(Type::String), &Collection::one("a")?)?);
+
+        let bad = less_than_number(&many(&[Value::from(1), Value::from(2)])?, &one);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::boolean::lessThan_Number_$0_1$__Number_$0_1$__Boolean_1_: Unexpected value: expected Number[0..1] but got Any[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_date_comparisons() -> PureExecutionResult<()>
+    {
+        let day = date("%2022-03-01")?;
+        let later = date("%2022-03-01T10:00:00+0000")?;
+        let latest = date("%latest")?;
+
+        assert_eq!(Collection::one(true)?, less_than_date(&day, &later)?);
+        assert_eq!(Collection::one(true)?, less_than_equal_date(&day, &day)?);
+        assert_eq!(Collection::one(true)?, greater_than_date(&latest, &later)?);
+        assert_eq!(Collection::one(true)?, greater_than_equal_date(&latest, &latest)?);
+        assert_eq!(Collection::one(false)?, greater_than_date(&day, &later)?);
+        assert_eq!(Collection::one(true)?, less_than_date(&date("%10:15")?, &date("%10:16")?)?);
+
+        let bad = less_than_date(&day, &date("%10:15")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::boolean::lessThan_Date_$0_1$__Date_$0_1$__Boolean_1_: StrictDate 2022-03-01 cannot be compared with StrictTime 10:15",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_string_comparisons() -> PureExecutionResult<()>
+    {
+        let apple = Collection::one("apple")?;
+        let banana = Collection::one("banana")?;
+
+        assert_eq!(Collection::one(true)?, less_than_string(&apple, &banana)?);
+        assert_eq!(Collection::one(true)?, less_than_string(&Collection::one("Banana")?, &apple)?);
+        assert_eq!(Collection::one(true)?, less_than_equal_string(&apple, &apple)?);
+        assert_eq!(Collection::one(false)?, greater_than_string(&apple, &banana)?);
+        assert_eq!(Collection::one(true)?, greater_than_equal_string(&banana, &apple)?);
+
+        Ok(())
+    }
+}
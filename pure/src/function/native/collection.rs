@@ -0,0 +1,963 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use pure_macros::pure_native;
+
+use crate::data::lambda::Lambda;
+use crate::data::ValueKey;
+use crate::function::native::collection_of;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+pub use self::grouping::*;
+pub use self::maps::*;
+
+pub mod grouping;
+pub mod maps;
+
+/// The value at a zero based index, which must be within the collection.
+#[pure_native("meta::pure::functions::collection::at(T[*], Integer[1]):T[1]")]
+fn at(col: impl FunctionArgument, index: i64) -> PureExecutionResult<Collection>
+{
+    let size = col.size()?;
+    if index < 0 || index >= size
+    {
+        let cause = format!("index {index} is out of bounds for a collection of size {size}");
+        return Err(PureExecutionError::IllegalArgument { func: FUNC.to_string(), arg: 1, cause });
+    }
+    collection_of(col.type_of()?, PURE_ONE, col.many_raw(FUNC, 0, Type::Any)?.nth(index as usize).cloned())
+}
+
+#[pure_native("meta::pure::functions::collection::first(T[*]):T[0..1]")]
+fn first(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_ONE, col.many_raw(FUNC, 0, Type::Any)?.next().cloned())
+}
+
+#[pure_native("meta::pure::functions::collection::last(T[*]):T[0..1]")]
+fn last(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_ONE, col.many_raw(FUNC, 0, Type::Any)?.last().cloned())
+}
+
+#[pure_native("meta::pure::functions::collection::head(T[*]):T[0..1]")]
+fn head(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_ONE, col.many_raw(FUNC, 0, Type::Any)?.next().cloned())
+}
+
+/// All but the first value.
+#[pure_native("meta::pure::functions::collection::tail(T[*]):T[*]")]
+fn tail(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_MANY, col.many_raw(FUNC, 0, Type::Any)?.skip(1).cloned())
+}
+
+/// The first n values, or all of them if there are fewer.  Taking a negative number of values gives an empty collection.
+#[pure_native("meta::pure::functions::collection::take(T[*], Integer[1]):T[*]")]
+fn take(col: impl FunctionArgument, n: i64) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_MANY, col.many_raw(FUNC, 0, Type::Any)?.take(n.max(0) as usize).cloned())
+}
+
+/// All but the first n values.  Dropping a negative number of values gives all of them.
+#[pure_native("meta::pure::functions::collection::drop(T[*], Integer[1]):T[*]")]
+fn drop(col: impl FunctionArgument, n: i64) -> PureExecutionResult<Collection>
+{
+    collection_of(col.type_of()?, ZERO_MANY, col.many_raw(FUNC, 0, Type::Any)?.skip(n.max(0) as usize).cloned())
+}
+
+/// The values from the zero based start index up to, but excluding, the end index.  Indexes beyond the end of the collection are
+/// limited to its size but negative indexes are not allowed.
+#[pure_native("meta::pure::functions::collection::slice(T[*], Integer[1], Integer[1]):T[*]")]
+fn slice(col: impl FunctionArgument, start: i64, end: i64) -> PureExecutionResult<Collection>
+{
+    let start = non_negative(FUNC, 1, start)?;
+    let end = non_negative(FUNC, 2, end)?;
+    collection_of(col.type_of()?, ZERO_MANY, col.many_raw(FUNC, 0, Type::Any)?.take(end).skip(start).cloned())
+}
+
+/// The values in reverse order, which has the same multiplicity as the original.
+#[pure_native("meta::pure::functions::collection::reverse(T[m]):T[m]")]
+fn reverse(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let values: Vec<&Value> = col.many_raw(FUNC, 0, Type::Any)?.collect();
+    collection_of(col.type_of()?, col.multiplicity_of()?, values.into_iter().rev().cloned())
+}
+
+/// The values of both collections, as a collection of their common supertype.
+#[pure_native("meta::pure::functions::collection::concatenate(T[*], T[*]):T[*]")]
+fn concatenate(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let pure_type = left.type_of()?.common_supertype(&right.type_of()?);
+    collection_of(pure_type, ZERO_MANY, left.many_raw(FUNC, 0, Type::Any)?.chain(right.many_raw(FUNC, 1, Type::Any)?).cloned())
+}
+
+/// Applies a function that gives exactly one value to each value, giving a collection of the same multiplicity.
+#[pure_native("meta::pure::functions::collection::map(T[m], Function<{T[1]->V[1]}>[1]):V[m]")]
+fn map(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let results = col
+        .many_raw(FUNC, 0, Type::Any)?
+        .map(|value| one_raw_result(FUNC, 1, &lambda.apply(&[&value.clone().to_collection()])?))
+        .collect::<PureExecutionResult<Vec<_>>>()?;
+    collection_of(result_type(lambda, &results), col.multiplicity_of()?, results)
+}
+
+/// Applies a function to each value, concatenating the results.
+#[pure_native("meta::pure::functions::collection::map(T[*], Function<{T[1]->V[*]}>[1]):V[*]")]
+fn map_many(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let mut results = vec![];
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        results.extend(lambda.apply(&[&value.clone().to_collection()])?.into_iter().cloned());
+    }
+    collection_of(result_type(lambda, &results), ZERO_MANY, results)
+}
+
+/// The values for which a predicate is true.
+#[pure_native("meta::pure::functions::collection::filter(T[*], Function<{T[1]->Boolean[1]}>[1]):T[*]")]
+fn filter(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let mut results = vec![];
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        if test(FUNC, lambda, value)?
+        {
+            results.push(value.clone());
+        }
+    }
+    collection_of(col.type_of()?, ZERO_MANY, results)
+}
+
+/// Combines the values in order, starting from the initial value, by applying a function to each value and the result so far.
+#[pure_native("meta::pure::functions::collection::fold(T[*], Function<{T[1], V[m]->V[m]}>[1], V[m]):V[m]")]
+fn fold(col: impl FunctionArgument, func: impl FunctionArgument, init: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let mut result = collection_of(init.type_of()?, init.multiplicity_of()?, init.many_raw(FUNC, 2, Type::Any)?.cloned())?;
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        result = lambda.apply(&[&value.clone().to_collection(), &result])?;
+    }
+    Ok(result)
+}
+
+/// Tests whether a predicate is true for any value, which it is not for an empty collection.
+#[pure_native("meta::pure::functions::collection::exists(T[*], Function<{T[1]->Boolean[1]}>[1]):Boolean[1]")]
+fn exists(col: Vec<&Value>, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    for value in col
+    {
+        if test(FUNC, lambda, value)?
+        {
+            return Collection::one(true);
+        }
+    }
+    Collection::one(false)
+}
+
+/// Tests whether a predicate is true for every value, which it is for an empty collection.
+#[pure_native("meta::pure::functions::collection::forAll(T[*], Function<{T[1]->Boolean[1]}>[1]):Boolean[1]")]
+fn for_all(col: Vec<&Value>, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    for value in col
+    {
+        if !test(FUNC, lambda, value)?
+        {
+            return Collection::one(false);
+        }
+    }
+    Collection::one(true)
+}
+
+/// The first value for which a predicate is true.
+#[pure_native("meta::pure::functions::collection::find(T[*], Function<{T[1]->Boolean[1]}>[1]):T[0..1]")]
+fn find(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        if test(FUNC, lambda, value)?
+        {
+            return collection_of(col.type_of()?, ZERO_ONE, Some(value.clone()));
+        }
+    }
+    collection_of(col.type_of()?, ZERO_ONE, None)
+}
+
+/// Sorts the values into ascending order.  The sort is stable and the values must be comparable with each other.
+#[pure_native("meta::pure::functions::collection::sort(T[m]):T[m]")]
+fn sort(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    sort_values(FUNC, &col, Sorting { key: None, comparator: None, descending: false })
+}
+
+/// Sorts the values into descending order.  The sort is stable and the values must be comparable with each other.
+#[pure_native("meta::pure::functions::collection::sortReversed(T[m]):T[m]")]
+fn sort_reversed(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    sort_values(FUNC, &col, Sorting { key: None, comparator: None, descending: true })
+}
+
+/// Sorts the values using a comparator, which gives a negative, zero or positive Integer as its first argument is less than, equal
+/// to or greater than its second.  Without a comparator the values are sorted into ascending order.  The sort is stable.
+#[pure_native("meta::pure::functions::collection::sort(T[m], Function<{T[1], T[1]->Integer[1]}>[0..1]):T[m]")]
+fn sort_with(col: impl FunctionArgument, comparator: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let comparator = optional_lambda(FUNC, 1, &comparator)?.map(|lambda| (1, lambda));
+    sort_values(FUNC, &col, Sorting { key: None, comparator, descending: false })
+}
+
+/// Sorts the values by comparing a key computed from each of them using a comparator.  Either function may be omitted, in which
+/// case the values themselves are compared or the keys are sorted into ascending order.  The sort is stable.
+#[pure_native("meta::pure::functions::collection::sort(T[m], Function<{T[1]->U[1]}>[0..1], Function<{U[1], U[1]->Integer[1]}>[0..1]):T[m]")]
+fn sort_by_with(col: impl FunctionArgument, key: impl FunctionArgument, comparator: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let key = optional_lambda(FUNC, 1, &key)?.map(|lambda| (1, lambda));
+    let comparator = optional_lambda(FUNC, 2, &comparator)?.map(|lambda| (2, lambda));
+    sort_values(FUNC, &col, Sorting { key, comparator, descending: false })
+}
+
+/// Sorts the values in ascending order of a key computed from each of them.  The sort is stable and the keys must be comparable
+/// with each other.
+#[pure_native("meta::pure::functions::collection::sortBy(T[m], Function<{T[1]->U[1]}>[0..1]):T[m]")]
+fn sort_by(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let key = optional_lambda(FUNC, 1, &func)?.map(|lambda| (1, lambda));
+    sort_values(FUNC, &col, Sorting { key, comparator: None, descending: false })
+}
+
+/// Sorts the values in descending order of a key computed from each of them.  The sort is stable, so values with equal keys keep
+/// their order, and the keys must be comparable with each other.
+#[pure_native("meta::pure::functions::collection::sortByReversed(T[m], Function<{T[1]->U[1]}>[0..1]):T[m]")]
+fn sort_by_reversed(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let key = optional_lambda(FUNC, 1, &func)?.map(|lambda| (1, lambda));
+    sort_values(FUNC, &col, Sorting { key, comparator: None, descending: true })
+}
+
+/// The values without any that are equal to an earlier value, keeping the first of each.
+#[pure_native("meta::pure::functions::collection::removeDuplicates(T[*]):T[*]")]
+fn remove_duplicates(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let mut seen = HashSet::new();
+    collection_of(col.type_of()?, ZERO_MANY, col.many_raw(FUNC, 0, Type::Any)?.filter(|value| seen.insert(ValueKey(*value))).cloned())
+}
+
+/// The values without any whose key, computed by a function, is equal to the key of an earlier value, keeping the first of each.
+#[pure_native("meta::pure::functions::collection::removeDuplicatesBy(T[*], Function<{T[1]->V[1]}>[1]):T[*]")]
+fn remove_duplicates_by(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let mut seen = HashSet::new();
+    let mut results = vec![];
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        let key = one_raw_result(FUNC, 1, &lambda.apply(&[&value.clone().to_collection()])?)?;
+        if seen.insert(ValueKey(key))
+        {
+            results.push(value.clone());
+        }
+    }
+    collection_of(col.type_of()?, ZERO_MANY, results)
+}
+
+/// The distinct values in the order they first appear, which is the same as `removeDuplicates`.
+#[pure_native("meta::pure::functions::collection::distinct(T[*]):T[*]")]
+fn distinct(col: impl FunctionArgument) -> PureExecutionResult<Collection> { remove_duplicates(col) }
+
+/// The distinct values of either collection, those of the first followed by those only in the second, as a collection of their
+/// common supertype.
+#[pure_native("meta::pure::functions::collection::union(T[*], T[*]):T[*]")]
+fn union(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let pure_type = left.type_of()?.common_supertype(&right.type_of()?);
+    let mut seen = HashSet::new();
+    let values = left.many_raw(FUNC, 0, Type::Any)?.chain(right.many_raw(FUNC, 1, Type::Any)?);
+    collection_of(pure_type, ZERO_MANY, values.filter(|value| seen.insert(ValueKey(*value))).cloned())
+}
+
+/// The distinct values of the first collection that are also in the second, in the order of the first.
+#[pure_native("meta::pure::functions::collection::intersection(T[*], T[*]):T[*]")]
+fn intersection(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let mut others: HashSet<_> = right.many_raw(FUNC, 1, Type::Any)?.map(ValueKey).collect();
+    let values = left.many_raw(FUNC, 0, Type::Any)?.filter(|value| others.remove(&ValueKey(*value)));
+    collection_of(left.type_of()?, ZERO_MANY, values.cloned())
+}
+
+/// Tests whether any of the values is equal to the given value.
+#[pure_native("meta::pure::functions::collection::contains(Any[*], Any[1]):Boolean[1]")]
+fn contains_value(col: Vec<&Value>, value: &Value) -> PureExecutionResult<Collection>
+{
+    Collection::one(col.into_iter().any(|v| ValueKey(v) == ValueKey(value)))
+}
+
+/// Tests whether every one of the given values is equal to one of the values of the collection.
+#[pure_native("meta::pure::functions::collection::containsAll(Any[*], Any[*]):Boolean[1]")]
+fn contains_all(col: Vec<&Value>, values: Vec<&Value>) -> PureExecutionResult<Collection>
+{
+    let contained: HashSet<_> = col.into_iter().map(ValueKey).collect();
+    Collection::one(values.into_iter().all(|value| contained.contains(&ValueKey(value))))
+}
+
+/// The zero based index of the first value that is equal to the given value, or -1 if there is none.
+#[pure_native("meta::pure::functions::collection::indexOf(T[*], T[1]):Integer[1]")]
+fn index_of_value(col: Vec<&Value>, value: &Value) -> PureExecutionResult<Collection>
+{
+    match col.into_iter().position(|v| ValueKey(v) == ValueKey(value))
+    {
+        Some(index) => Collection::one(index),
+        None => Collection::one(-1),
+    }
+}
+
+/// Adds the collection natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_at(registry)?;
+    register_first(registry)?;
+    register_last(registry)?;
+    register_head(registry)?;
+    register_tail(registry)?;
+    register_take(registry)?;
+    register_drop(registry)?;
+    register_slice(registry)?;
+    register_reverse(registry)?;
+    register_concatenate(registry)?;
+    register_map(registry)?;
+    register_map_many(registry)?;
+    register_filter(registry)?;
+    register_fold(registry)?;
+    register_exists(registry)?;
+    register_for_all(registry)?;
+    register_find(registry)?;
+    register_sort_by(registry)?;
+    register_sort_by_reversed(registry)?;
+    register_sort(registry)?;
+    register_sort_reversed(registry)?;
+    register_sort_with(registry)?;
+    register_sort_by_with(registry)?;
+    register_remove_duplicates(registry)?;
+    register_remove_duplicates_by(registry)?;
+    register_distinct(registry)?;
+    register_union(registry)?;
+    register_intersection(registry)?;
+    register_contains_value(registry)?;
+    register_contains_all(registry)?;
+    register_index_of_value(registry)?;
+    grouping::register(registry)?;
+    maps::register(registry)?;
+    Ok(())
+}
+
+/// How to sort values: the key and comparator functions, if any, each with the argument that it was passed as.
+struct Sorting<'a>
+{
+    key: Option<(usize, &'a Lambda)>,
+    comparator: Option<(usize, &'a Lambda)>,
+    descending: bool,
+}
+
+/// Sorts the values of a collection stably, giving a collection of the same type and multiplicity.  The values are sorted by
+/// reference and only cloned into the result, and each is only cloned beforehand if it must be passed to a function.
+fn sort_values<C>(func: &str, col: &C, sorting: Sorting) -> PureExecutionResult<Collection>
+where
+    C: FunctionArgument,
+{
+    let values: Vec<&Value> = col.many_raw(func, 0, Type::Any)?.collect();
+
+    // The collections that are compared, if the values themselves are not
+    let keys = match (sorting.key, sorting.comparator)
+    {
+        (Some((arg, key)), _) => Some(
+            values
+                .iter()
+                .map(|value| {
+                    let result = key.apply(&[&(*value).clone().to_collection()])?;
+                    one_raw_result(func, arg, &result)?;
+                    Ok(result)
+                })
+                .collect::<PureExecutionResult<Vec<_>>>()?,
+        ),
+        (None, Some(_)) => Some(values.iter().map(|value| (*value).clone().to_collection()).collect()),
+        (None, None) => None,
+    };
+    let compared = |i: usize| keys.as_ref().and_then(|keys| keys[i].into_iter().next()).unwrap_or(values[i]);
+
+    let compare = |l: usize, r: usize| match sorting.comparator
+    {
+        Some((arg, comparator)) =>
+        {
+            let keys = keys.as_deref().unwrap_or_default();
+            Ok(one_result::<i64>(func, arg, &comparator.apply(&[&keys[l], &keys[r]])?)?.cmp(&0))
+        }
+        None =>
+        {
+            let (l, r) = (compared(l), compared(r));
+            let arg = sorting.key.map_or(0, |(arg, _)| arg);
+            let cause = || format!("{} {l} cannot be compared with {} {r}", l.pure_type(), r.pure_type());
+            l.compare(r).ok_or_else(|| PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: cause() })
+        }
+    };
+
+    // Once a comparison fails the remaining comparisons are skipped, leaving the order unfinished, and the failure is reported
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    let mut failure = None;
+    order.sort_by(|&l, &r| {
+        let (l, r) = if sorting.descending { (r, l) } else { (l, r) };
+        match failure
+        {
+            Some(_) => Ordering::Equal,
+            None => compare(l, r).unwrap_or_else(|e| {
+                failure = Some(e);
+                Ordering::Equal
+            }),
+        }
+    });
+    if let Some(e) = failure
+    {
+        return Err(e);
+    }
+    collection_of(col.type_of()?, col.multiplicity_of()?, order.into_iter().map(|i| values[i].clone()))
+}
+
+fn non_negative(func: &str, arg: usize, index: i64) -> PureExecutionResult<usize>
+{
+    let negative = || PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("index {index} is negative") };
+    usize::try_from(index).map_err(|_| negative())
+}
+
+fn lambda<'a, C>(func: &str, arg: usize, col: &'a C) -> PureExecutionResult<&'a Lambda>
+where
+    C: FunctionArgument,
+{
+    match col.one_raw(func, arg, Type::Function)?
+    {
+        Value::Lambda(lambda) => Ok(lambda),
+        other => Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("{other} is not a lambda") }),
+    }
+}
+
+fn optional_lambda<'a, C>(func: &str, arg: usize, col: &'a C) -> PureExecutionResult<Option<&'a Lambda>>
+where
+    C: FunctionArgument,
+{
+    match col.zero_or_one_raw(func, arg, Type::Function)?
+    {
+        Some(Value::Lambda(lambda)) => Ok(Some(lambda)),
+        Some(other) => Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("{other} is not a lambda") }),
+        None => Ok(None),
+    }
+}
+
+/// Applies a predicate to a value, which must give exactly one Boolean.
+fn test(func: &str, lambda: &Lambda, value: &Value) -> PureExecutionResult<bool>
+{
+    one_result(func, 1, &lambda.apply(&[&value.clone().to_collection()])?)
+}
+
+/// The single value of the result of a lambda passed as an argument, any other result being reported as the lambda's.
+fn one_result<T>(func: &str, arg: usize, result: &Collection) -> PureExecutionResult<T>
+where
+    T: 'static + TryFrom<Value, Error = PureExecutionError>,
+{
+    result.one(func, arg).map_err(illegal_result)
+}
+
+/// The single value of the result of a lambda passed as an argument, whatever its type.
+fn one_raw_result(func: &str, arg: usize, result: &Collection) -> PureExecutionResult<Value>
+{
+    result.one_raw(func, arg, Type::Any).cloned().map_err(illegal_result)
+}
+
+fn illegal_result(e: PureExecutionError) -> PureExecutionError
+{
+    match e
+    {
+        PureExecutionError::IllegalArgument { func, arg, cause } => PureExecutionError::IllegalLambdaResult { func, arg, cause },
+        other => other,
+    }
+}
+
+/// The type of the results of a lambda, which is its declared return type unless that is a type parameter.
+fn result_type(lambda: &Lambda, results: &[Value]) -> Type
+{
+    lambda
+        .signature()
+        .returns
+        .0
+        .raw_type()
+        .unwrap_or_else(|| results.iter().map(|v| v.pure_type()).reduce(|l, r| l.common_supertype(&r)).unwrap_or(Type::Nil))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::{integers, numbers, strings, unary, zero_one};
+
+    #[test]
+    fn collection_at() -> PureExecutionResult<()>
+    {
+        let values = integers(&[10, 20, 30])?;
+
+        assert_eq!(Collection::one(10)?, at(&values, &Collection::one(0)?)?);
+        assert_eq!(Collection::one(30)?, at(&values, &Collection::one(2)?)?);
+        assert_eq!(Collection::one(5)?, at(&Collection::one(5)?, &Collection::one(0)?)?);
+
+        let bad = at(&values, &Collection::one(3)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::at_T_MANY__Integer_1__T_1_: index 3 is out of bounds for a collection of size 3",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = at(&values, &Collection::one(-1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::at_T_MANY__Integer_1__T_1_: index -1 is out of bounds for a collection of size 3",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = at(&values, &Collection::zero(Type::Integer));
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::at_T_MANY__Integer_1__T_1_: Unexpected value: expected Integer[1] but got Integer[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_first_last_and_head() -> PureExecutionResult<()>
+    {
+        let values = integers(&[10, 20, 30])?;
+
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(10)))?, first(&values)?);
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(30)))?, last(&values)?);
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(10)))?, head(&values)?);
+        assert_eq!(ZERO_ONE, first(&values)?.multiplicity());
+
+        let empty = Collection::zero(Type::Integer);
+        assert_eq!(collection_of(Type::Integer, ZERO_ONE, None)?, first(&empty)?);
+        assert_eq!(collection_of(Type::Integer, ZERO_ONE, None)?, last(&empty)?);
+        assert_eq!(collection_of(Type::Nil, ZERO_ONE, None)?, head(&ZERO_NIL)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_tail() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[20, 30])?, tail(&integers(&[10, 20, 30])?)?);
+        assert_eq!(integers(&[])?, tail(&Collection::one(10)?)?);
+        assert_eq!(integers(&[])?, tail(&Collection::zero(Type::Integer))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_take_and_drop() -> PureExecutionResult<()>
+    {
+        let values = integers(&[10, 20, 30])?;
+
+        assert_eq!(integers(&[10, 20])?, take(&values, &Collection::one(2)?)?);
+        assert_eq!(integers(&[10, 20, 30])?, take(&values, &Collection::one(5)?)?);
+        assert_eq!(integers(&[])?, take(&values, &Collection::one(0)?)?);
+        assert_eq!(integers(&[])?, take(&values, &Collection::one(-1)?)?);
+
+        assert_eq!(integers(&[30])?, drop(&values, &Collection::one(2)?)?);
+        assert_eq!(integers(&[])?, drop(&values, &Collection::one(5)?)?);
+        assert_eq!(integers(&[10, 20, 30])?, drop(&values, &Collection::one(-1)?)?);
+
+        let bad = take(&values, &Collection::one("2")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::take_T_MANY__Integer_1__T_MANY_: Unexpected value: expected Integer[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_slice() -> PureExecutionResult<()>
+    {
+        let values = integers(&[10, 20, 30, 40])?;
+
+        assert_eq!(integers(&[20, 30])?, slice(&values, &Collection::one(1)?, &Collection::one(3)?)?);
+        assert_eq!(integers(&[30, 40])?, slice(&values, &Collection::one(2)?, &Collection::one(10)?)?);
+        assert_eq!(integers(&[])?, slice(&values, &Collection::one(3)?, &Collection::one(1)?)?);
+        assert_eq!(integers(&[])?, slice(&values, &Collection::one(5)?, &Collection::one(7)?)?);
+
+        let bad = slice(&values, &Collection::one(-1)?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::slice_T_MANY__Integer_1__Integer_1__T_MANY_: index -1 is negative",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = slice(&values, &Collection::one(0)?, &Collection::one(-2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 2 of meta::pure::functions::collection::slice_T_MANY__Integer_1__Integer_1__T_MANY_: index -2 is negative",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_reverse() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[30, 20, 10])?, reverse(&integers(&[10, 20, 30])?)?);
+        assert_eq!(Collection::one(10)?, reverse(&Collection::one(10)?)?);
+        assert_eq!(ZERO_NIL, reverse(&ZERO_NIL)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_concatenate() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[10, 20, 30])?, concatenate(&integers(&[10, 20])?, &Collection::one(30)?)?);
+        assert_eq!(integers(&[10])?, concatenate(&ZERO_NIL, &Collection::one(10)?)?);
+
+        let mixed = concatenate(&Collection::one(1)?, &Collection::one(2.5)?)?;
+        assert_eq!(Type::Number, mixed.pure_type());
+        assert_eq!(collection_of(Type::Number, ZERO_MANY, [Value::from(1), Value::from(2.5)])?, mixed);
+
+        assert_eq!(Type::Any, concatenate(&Collection::one(1)?, &Collection::one("a")?)?.pure_type());
+
+        Ok(())
+    }
+
+    fn of(raw: Type) -> GenericType { GenericType::from(raw) }
+
+    fn is_even() -> PureExecutionResult<Collection>
+    {
+        unary(Type::Integer, Type::Boolean, PURE_ONE, |v| Collection::one(matches!(v, Value::Integer(i) if i % 2 == 0)))
+    }
+
+    fn doubled() -> PureExecutionResult<Collection>
+    {
+        unary(Type::Integer, Type::Integer, PURE_ONE, |v| Collection::one(i64::try_from(v.clone())? * 2))
+    }
+
+    #[test]
+    fn collection_map() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[2, 4, 6])?, map(&integers(&[1, 2, 3])?, &doubled()?)?);
+        assert_eq!(Collection::one(10)?, map(&Collection::one(5)?, &doubled()?)?);
+        assert_eq!(integers(&[])?, map(&integers(&[])?, &doubled()?)?);
+
+        let repeated = unary(Type::Integer, Type::Integer, ZERO_MANY, |v| integers(&[i64::try_from(v.clone())?; 2]))?;
+        assert_eq!(integers(&[1, 1, 2, 2])?, map_many(&integers(&[1, 2])?, &repeated)?);
+
+        let bad = map(&integers(&[1, 2])?, &repeated);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Illegal result from the lambda passed to arg 1 of meta::pure::functions::collection::map_T_m__Function_1__V_m_: Unexpected value: expected Any[1] but got Integer[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = map(&Collection::one("a")?, &doubled()?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal lambda arguments: {Integer[1]->Integer[1]} cannot be applied to (String[1])", bad.err().unwrap().to_string());
+
+        let bad = map(&integers(&[1])?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::map_T_m__Function_1__V_m_: Unexpected value: expected Function[1] but got Integer[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_map_with_type_parameter_result() -> PureExecutionResult<()>
+    {
+        let identity = Lambda::new(vec![(GenericType::parameter("T"), PURE_ONE)], (GenericType::parameter("T"), PURE_ONE), |args| {
+            Ok(args[0].into_iter().next().unwrap().clone().to_collection())
+        });
+        let mixed = concatenate(&Collection::one(1)?, &Collection::one(2.5)?)?;
+
+        let result = map(&mixed, &Collection::one(identity)?)?;
+        assert_eq!(Type::Number, result.pure_type());
+        assert_eq!(mixed, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_filter_and_find() -> PureExecutionResult<()>
+    {
+        let values = integers(&[1, 2, 3, 4])?;
+
+        assert_eq!(integers(&[2, 4])?, filter(&values, &is_even()?)?);
+        assert_eq!(integers(&[])?, filter(&integers(&[1, 3])?, &is_even()?)?);
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(2)))?, find(&values, &is_even()?)?);
+        assert_eq!(collection_of(Type::Integer, ZERO_ONE, None)?, find(&integers(&[1, 3])?, &is_even()?)?);
+
+        let bad = filter(&values, &doubled()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Illegal result from the lambda passed to arg 1 of meta::pure::functions::collection::filter_T_MANY__Function_1__T_MANY_: Unexpected value: expected Boolean[1] but got Integer[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_exists_and_for_all() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(true)?, exists(&integers(&[1, 2])?, &is_even()?)?);
+        assert_eq!(Collection::one(false)?, exists(&integers(&[1, 3])?, &is_even()?)?);
+        assert_eq!(Collection::one(false)?, exists(&integers(&[])?, &is_even()?)?);
+
+        assert_eq!(Collection::one(true)?, for_all(&integers(&[2, 4])?, &is_even()?)?);
+        assert_eq!(Collection::one(false)?, for_all(&integers(&[2, 3])?, &is_even()?)?);
+        assert_eq!(Collection::one(true)?, for_all(&integers(&[])?, &is_even()?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_fold() -> PureExecutionResult<()>
+    {
+        let sum = Lambda::new(vec![(of(Type::Integer), PURE_ONE), (of(Type::Integer), PURE_ONE)], (of(Type::Integer), PURE_ONE), |args| {
+            let total = args
+                .iter()
+                .map(|arg| arg.into_iter().map(|v| i64::try_from(v.clone())).sum::<PureExecutionResult<i64>>())
+                .sum::<PureExecutionResult<i64>>()?;
+            Collection::one(total)
+        });
+        let sum = Collection::one(sum)?;
+
+        assert_eq!(Collection::one(10)?, fold(&integers(&[1, 2, 3, 4])?, &sum, &Collection::one(0)?)?);
+        assert_eq!(Collection::one(7)?, fold(&integers(&[])?, &sum, &Collection::one(7)?)?);
+
+        let bad = fold(&integers(&[1])?, &sum, &Collection::one("0")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Illegal lambda arguments: {Integer[1], Integer[1]->Integer[1]} cannot be applied to (Integer[1], String[1])",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    fn pure_strings(col: &Collection) -> Vec<String> { col.into_iter().map(Value::to_pure_string).collect() }
+
+    /// A comparator of Strings by their lengths.
+    fn by_length() -> PureExecutionResult<Collection>
+    {
+        let lambda = Lambda::new(vec![(of(Type::String), PURE_ONE), (of(Type::String), PURE_ONE)], (of(Type::Integer), PURE_ONE), |args| {
+            let length = |arg: &Collection| arg.into_iter().next().map_or(0, |v| v.to_string().chars().count() as i64);
+            Collection::one(length(args[0]) - length(args[1]))
+        });
+        Collection::one(lambda)
+    }
+
+    #[test]
+    fn collection_sort() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[1, 2, 3, 3])?, sort(&integers(&[3, 1, 3, 2])?)?);
+        assert_eq!(integers(&[3, 3, 2, 1])?, sort_reversed(&integers(&[3, 1, 3, 2])?)?);
+        assert_eq!(Collection::one(1)?, sort(&Collection::one(1)?)?);
+        assert_eq!(ZERO_NIL, sort(&ZERO_NIL)?);
+
+        let mixed = numbers(&[
+            Value::from(f64::NAN),
+            Value::from(2),
+            Value::decimal("1.5")?,
+            Value::from(f64::NEG_INFINITY),
+            Value::from(1.0),
+            Value::from(1),
+        ])?;
+        assert_eq!(vec!["-Infinity", "1.0", "1", "1.5", "2", "NaN"], pure_strings(&sort(&mixed)?));
+        assert_eq!(vec!["NaN", "2", "1.5", "1.0", "1", "-Infinity"], pure_strings(&sort_reversed(&mixed)?));
+
+        // Numbers of different types are ordered exactly, so the order does not depend on the order they are given in
+        let (a, b, c) = (Value::from(9007199254740993_i64), Value::from(9007199254740992.0), Value::decimal("9007199254740992.5")?);
+        assert_eq!(Some(Ordering::Greater), a.compare(&b));
+        assert_eq!(Some(Ordering::Less), b.compare(&c));
+        assert_eq!(Some(Ordering::Greater), a.compare(&c));
+        let ordered = numbers(&[b.clone(), c.clone(), a.clone()])?;
+        assert_eq!(ordered, sort(&numbers(&[a.clone(), b.clone(), c.clone()])?)?);
+        assert_eq!(ordered, sort(&numbers(&[c, b, a])?)?);
+
+        let dates = collection_of(Type::Date, ZERO_MANY, [Value::date("%latest")?, Value::date("%2022-03-01")?, Value::date("%2021")?])?;
+        assert_eq!(vec!["2021", "2022-03-01", "latest"], pure_strings(&sort(&dates)?));
+
+        let bad = sort(&collection_of(Type::Any, ZERO_MANY, [Value::from(1), Value::from("a")])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::collection::sort_T_m__T_m_: String a cannot be compared with Integer 1",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_sort_with_comparator() -> PureExecutionResult<()>
+    {
+        let words = strings(&["ccc", "a", "bb", "d", "ee"])?;
+
+        assert_eq!(strings(&["a", "d", "bb", "ee", "ccc"])?, sort_with(&words, &by_length()?)?);
+        assert_eq!(strings(&["a", "bb", "ccc", "d", "ee"])?, sort_with(&words, &collection_of(Type::Function, ZERO_ONE, None)?)?);
+
+        let reversed = unary(Type::String, Type::String, PURE_ONE, |v| Collection::one(v.to_string().chars().rev().collect::<String>()))?;
+        let no_function = collection_of(Type::Function, ZERO_ONE, None)?;
+        assert_eq!(strings(&["a", "d", "bb", "ee", "ccc"])?, sort_by_with(&words, &reversed, &by_length()?)?);
+        assert_eq!(strings(&["a", "d", "bb", "ee", "ccc"])?, sort_by_with(&words, &no_function, &by_length()?)?);
+        assert_eq!(strings(&["a", "bb", "ccc", "d", "ee"])?, sort_by_with(&words, &reversed, &no_function)?);
+
+        let bad = sort_with(&words, &unary(Type::String, Type::Integer, PURE_ONE, |_| Collection::one(0))?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal lambda arguments: {String[1]->Integer[1]} cannot be applied to (String[1], String[1])", bad.err().unwrap().to_string());
+
+        let no_order = Lambda::new(vec![(of(Type::String), PURE_ONE), (of(Type::String), PURE_ONE)], (of(Type::Integer), ZERO_ONE), |_| {
+            Ok(Collection::zero(Type::Integer))
+        });
+        let bad = sort_with(&words, &Collection::one(no_order)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Illegal result from the lambda passed to arg 1 of meta::pure::functions::collection::sort_T_m__Function_$0_1$__T_m_: Unexpected value: expected Integer[1] but got Integer[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_sort_by() -> PureExecutionResult<()>
+    {
+        let negated = unary(Type::Integer, Type::Integer, PURE_ONE, |v| Collection::one(-i64::try_from(v.clone())?))?;
+        assert_eq!(integers(&[3, 2, 1])?, sort_by(&integers(&[2, 3, 1])?, &negated)?);
+        assert_eq!(integers(&[1, 2, 3])?, sort_by_reversed(&integers(&[2, 3, 1])?, &negated)?);
+        assert_eq!(integers(&[1, 2, 3])?, sort_by(&integers(&[2, 3, 1])?, &collection_of(Type::Function, ZERO_ONE, None)?)?);
+
+        let parity = unary(Type::Integer, Type::Boolean, PURE_ONE, |v| Collection::one(matches!(v, Value::Integer(i) if i % 2 != 0)))?;
+        assert_eq!(integers(&[4, 2, 3, 1])?, sort_by(&integers(&[3, 4, 1, 2])?, &parity)?);
+        assert_eq!(integers(&[3, 1, 4, 2])?, sort_by_reversed(&integers(&[3, 4, 1, 2])?, &parity)?);
+
+        let mixed = unary(Type::Integer, Type::Any, PURE_ONE, |v| {
+            if v == &Value::from(1)
+            {
+                Collection::one("one")
+            }
+            else
+            {
+                Collection::one(v.clone())
+            }
+        })?;
+        let bad = sort_by(&integers(&[1, 2])?, &mixed);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::sortBy_T_m__Function_$0_1$__T_m_: Integer 2 cannot be compared with String one",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_remove_duplicates() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[3, 1, 2])?, remove_duplicates(&integers(&[3, 1, 3, 2, 1, 3])?)?);
+        assert_eq!(integers(&[3, 1, 2])?, distinct(&integers(&[3, 1, 3, 2, 1, 3])?)?);
+        assert_eq!(integers(&[5])?, remove_duplicates(&Collection::one(5)?)?);
+        assert_eq!(collection_of(Type::Nil, ZERO_MANY, None)?, remove_duplicates(&ZERO_NIL)?);
+
+        // Numbers are equal across types and NaN is a duplicate of itself
+        let mixed = numbers(&[
+            Value::from(1),
+            Value::from(1.0),
+            Value::decimal("1.00")?,
+            Value::from(f64::NAN),
+            Value::from(f64::NAN),
+            0.0.into(),
+            (-0.0).into(),
+        ])?;
+        let unique = remove_duplicates(&mixed)?;
+        assert_eq!(vec!["1", "NaN", "0.0"], unique.into_iter().map(Value::to_pure_string).collect::<Vec<_>>());
+
+        // Numbers are equal only when their values are exactly equal, whatever the order they are met in
+        let (a, b, d) = (Value::from(9007199254740993_i64), Value::from(9007199254740992.0), Value::from(9007199254740992_i64));
+        assert_eq!(numbers(&[a.clone(), b.clone()])?, distinct(&numbers(&[a.clone(), b.clone(), d.clone()])?)?);
+        assert_eq!(numbers(&[b.clone(), a.clone()])?, distinct(&numbers(&[b, a, d])?)?);
+
+        let parity = unary(Type::Integer, Type::Boolean, PURE_ONE, |v| Collection::one(matches!(v, Value::Integer(i) if i % 2 != 0)))?;
+        assert_eq!(integers(&[4, 3])?, remove_duplicates_by(&integers(&[4, 3, 2, 1, 6])?, &parity)?);
+        assert_eq!(integers(&[])?, remove_duplicates_by(&integers(&[])?, &parity)?);
+
+        let bad =
+            remove_duplicates_by(&integers(&[1, 2])?, &unary(Type::Integer, Type::Integer, ZERO_MANY, |_| Ok(Collection::zero(Type::Integer)))?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Illegal result from the lambda passed to arg 1 of meta::pure::functions::collection::removeDuplicatesBy_T_MANY__Function_1__T_MANY_: Unexpected value: expected Any[1] but got Integer[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_union_and_intersection() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[1, 2, 3, 4])?, union(&integers(&[1, 2, 1, 3])?, &integers(&[3, 4, 2, 4])?)?);
+        assert_eq!(integers(&[2])?, union(&ZERO_NIL, &integers(&[2, 2])?)?);
+
+        let mixed = union(&Collection::one(1)?, &numbers(&[Value::from(1.0), Value::from(2.5)])?)?;
+        assert_eq!(numbers(&[Value::from(1), Value::from(2.5)])?, mixed);
+
+        assert_eq!(integers(&[3, 1])?, intersection(&integers(&[3, 1, 3, 2, 1])?, &integers(&[1, 4, 3])?)?);
+        assert_eq!(integers(&[])?, intersection(&integers(&[1, 2])?, &ZERO_NIL)?);
+        assert_eq!(integers(&[2])?, intersection(&integers(&[1, 2])?, &Collection::one(2.0)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn collection_contains_and_index_of() -> PureExecutionResult<()>
+    {
+        let values = integers(&[10, 20, 30, 20])?;
+
+        assert_eq!(Collection::one(true)?, contains_value(&values, &Collection::one(20)?)?);
+        assert_eq!(Collection::one(true)?, contains_value(&values, &Value::decimal("30.0")?.to_collection())?);
+        assert_eq!(Collection::one(false)?, contains_value(&values, &Collection::one("20")?)?);
+        assert_eq!(Collection::one(false)?, contains_value(&ZERO_NIL, &Collection::one(1)?)?);
+
+        assert_eq!(Collection::one(true)?, contains_all(&values, &integers(&[30, 10, 30])?)?);
+        assert_eq!(Collection::one(true)?, contains_all(&values, &ZERO_NIL)?);
+        assert_eq!(Collection::one(false)?, contains_all(&values, &integers(&[10, 40])?)?);
+
+        assert_eq!(Collection::one(1)?, index_of_value(&values, &Collection::one(20)?)?);
+        assert_eq!(Collection::one(0)?, index_of_value(&values, &Collection::one(10.0)?)?);
+        assert_eq!(Collection::one(-1)?, index_of_value(&values, &Collection::one(40)?)?);
+
+        let bad = index_of_value(&values, &integers(&[10, 20])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::indexOf_T_MANY__T_1__Integer_1_: Unexpected value: expected Any[1] but got Integer[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use pure_macros::pure_native;
+
+use crate::data::list::PureList;
+use crate::data::map::PureMap;
+use crate::data::pair::PurePair;
+use crate::data::ValueKey;
+use crate::function::native::collection::{lambda, one_raw_result, result_type, test};
+use crate::function::native::collection_of;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+/// The Integers from the start up to, but excluding, the stop.  A range cannot have more than `MAX_RANGE_SIZE` Integers.
+#[pure_native("meta::pure::functions::collection::range(Integer[1], Integer[1]):Integer[*]")]
+fn range(start: i64, stop: i64) -> PureExecutionResult<Collection> { integers_between(FUNC, start, stop, 1) }
+
+/// The Integers from the start up to, but excluding, the stop, separated by the step.  A negative step counts down from the start to
+/// a lower stop.  The step cannot be zero, and the range is limited in size as it is without one.
+#[pure_native("meta::pure::functions::collection::range(Integer[1], Integer[1], Integer[1]):Integer[*]")]
+fn range_step(start: i64, stop: i64, step: i64) -> PureExecutionResult<Collection>
+{
+    if step == 0
+    {
+        return Err(PureExecutionError::IllegalArgument { func: FUNC.to_string(), arg: 2, cause: "step cannot be zero".to_string() });
+    }
+    integers_between(FUNC, start, stop, step)
+}
+
+/// Pairs each value of the first collection with the value at the same position in the second, for as many values as the shorter
+/// collection has.
+#[pure_native("meta::pure::functions::collection::zip(T[*], U[*]):Pair<T, U>[*]")]
+fn zip(left: impl FunctionArgument, right: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let (left_type, right_type) = (values_type(FUNC, 0, &left)?, values_type(FUNC, 1, &right)?);
+    let pairs = left
+        .many_raw(FUNC, 0, Type::Any)?
+        .zip(right.many_raw(FUNC, 1, Type::Any)?)
+        .map(|(l, r)| Ok(Value::from(PurePair::new(left_type.clone(), l.clone(), right_type.clone(), r.clone())?)))
+        .collect::<PureExecutionResult<Vec<_>>>()?;
+    collection_of(Type::Pair, ZERO_MANY, pairs)
+}
+
+#[pure_native("meta::pure::functions::collection::pair(U[1], V[1]):Pair<U, V>[1]")]
+fn pair(first: &Value, second: &Value) -> PureExecutionResult<Collection>
+{
+    Collection::one(PurePair::new(first.generic_type(), first.clone(), second.generic_type(), second.clone())?)
+}
+
+/// The values as a single List value.
+#[pure_native("meta::pure::functions::collection::list(U[*]):List<U>[1]")]
+fn list(col: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    Collection::one(PureList::new(values_type(FUNC, 0, &col)?, col.many_raw(FUNC, 0, Type::Any)?.cloned().collect())?)
+}
+
+/// Splits the values into a Pair of Lists, the first of the values for which a predicate is true and the second of the others, each
+/// in their original order.
+#[pure_native("meta::pure::functions::collection::partition(T[*], Function<{T[1]->Boolean[1]}>[1]):Pair<List<T>, List<T>>[1]")]
+fn partition(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let (mut accepted, mut rejected) = (vec![], vec![]);
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        if test(FUNC, lambda, value)?
+        {
+            accepted.push(value.clone());
+        }
+        else
+        {
+            rejected.push(value.clone());
+        }
+    }
+
+    let element_type = values_type(FUNC, 0, &col)?;
+    let list_type = GenericType::new(Type::List, vec![element_type.clone()])?;
+    let accepted = PureList::new(element_type.clone(), accepted)?;
+    let rejected = PureList::new(element_type, rejected)?;
+    Collection::one(PurePair::new(list_type.clone(), accepted.into(), list_type, rejected.into())?)
+}
+
+/// Groups the values by a key computed from each of them, giving a Map from each key to the List of values with that key in their
+/// original order.
+#[pure_native("meta::pure::functions::collection::groupBy(X[*], Function<{X[1]->Y[1]}>[1]):Map<Y, List<X>>[1]")]
+fn group_by(col: impl FunctionArgument, func: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let lambda = lambda(FUNC, 1, &func)?;
+    let mut groups: Vec<(Value, Vec<Value>)> = vec![];
+    let mut positions: HashMap<ValueKey<Value>, usize> = HashMap::new();
+    for value in col.many_raw(FUNC, 0, Type::Any)?
+    {
+        let key = one_raw_result(FUNC, 1, &lambda.apply(&[&value.clone().to_collection()])?)?;
+        match positions.entry(ValueKey(key))
+        {
+            Entry::Occupied(position) => groups[*position.get()].1.push(value.clone()),
+            Entry::Vacant(position) =>
+            {
+                groups.push((position.key().0.clone(), vec![value.clone()]));
+                position.insert(groups.len() - 1);
+            }
+        }
+    }
+
+    let keys: Vec<Value> = groups.iter().map(|(key, _)| key.clone()).collect();
+    let key_type = common_type(&keys).unwrap_or_else(|| GenericType::from(result_type(lambda, &keys)));
+    let element_type = values_type(FUNC, 0, &col)?;
+    let entries = groups
+        .into_iter()
+        .map(|(key, values)| Ok((key, Value::from(PureList::new(element_type.clone(), values)?))))
+        .collect::<PureExecutionResult<Vec<_>>>()?;
+    let map = PureMap::new(key_type, GenericType::new(Type::List, vec![element_type])?, entries)?;
+    Collection::one(map)
+}
+
+/// Adds the grouping natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_range(registry)?;
+    register_range_step(registry)?;
+    register_zip(registry)?;
+    register_pair(registry)?;
+    register_list(registry)?;
+    register_partition(registry)?;
+    register_group_by(registry)?;
+    Ok(())
+}
+
+/// The generic type of the values of a collection, so that the type arguments of any Pairs, Lists or Maps among them are kept, or
+/// the type of the collection if it is empty.
+fn values_type<C>(func: &str, arg: usize, col: &C) -> PureExecutionResult<GenericType>
+where
+    C: FunctionArgument,
+{
+    match common_type(col.many_raw(func, arg, Type::Any)?)
+    {
+        Some(generic_type) => Ok(generic_type),
+        None => Ok(GenericType::from(col.type_of()?)),
+    }
+}
+
+/// The common supertype of the generic types of the values, if there are any.
+fn common_type<'a, I>(values: I) -> Option<GenericType>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    values.into_iter().map(Value::generic_type).reduce(|l, r| l.common_supertype(&r))
+}
+
+/// The most Integers a range may have, since each is held in memory.
+const MAX_RANGE_SIZE: i128 = 10_000_000;
+
+fn integers_between(func: &str, start: i64, stop: i64, step: i64) -> PureExecutionResult<Collection>
+{
+    // Counted as i128, in which neither the span nor the rounded up quotient can overflow
+    let span = i128::from(stop) - i128::from(start);
+    let step_size = i128::from(step).abs();
+    let size = if span.signum() == step.signum() as i128 { (span.abs() + step_size - 1) / step_size } else { 0 };
+    if size > MAX_RANGE_SIZE
+    {
+        let cause = format!("the range from {start} to {stop} by {step} would have {size} Integers, more than {MAX_RANGE_SIZE}");
+        return Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg: 1, cause });
+    }
+
+    let in_range = |i: &i64| (step > 0 && *i < stop) || (step < 0 && *i > stop);
+    let integers = std::iter::successors(Some(start), |i| i.checked_add(step)).take_while(in_range);
+    collection_of(Type::Integer, ZERO_MANY, integers.map(Value::from))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::{integers, unary};
+
+    fn integer_list(values: &[i64]) -> PureExecutionResult<Value>
+    {
+        Ok(PureList::new(GenericType::from(Type::Integer), values.iter().map(|v| Value::from(*v)).collect())?.into())
+    }
+
+    #[test]
+    fn grouping_range() -> PureExecutionResult<()>
+    {
+        assert_eq!(integers(&[0, 1, 2])?, range(&Collection::one(0)?, &Collection::one(3)?)?);
+        assert_eq!(integers(&[])?, range(&Collection::one(3)?, &Collection::one(3)?)?);
+        assert_eq!(integers(&[1, 4, 7])?, range_step(&Collection::one(1)?, &Collection::one(8)?, &Collection::one(3)?)?);
+        assert_eq!(integers(&[5, 3, 1])?, range_step(&Collection::one(5)?, &Collection::one(0)?, &Collection::one(-2)?)?);
+        assert_eq!(integers(&[])?, range_step(&Collection::one(0)?, &Collection::one(5)?, &Collection::one(-1)?)?);
+        assert_eq!(integers(&[i64::MAX - 1])?, range_step(&Collection::one(i64::MAX - 1)?, &Collection::one(i64::MAX)?, &Collection::one(5)?)?);
+
+        let bad = range_step(&Collection::one(0)?, &Collection::one(5)?, &Collection::one(0)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 2 of meta::pure::functions::collection::range_Integer_1__Integer_1__Integer_1__Integer_MANY_: step cannot be zero",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = range(&Collection::one(0)?, &Collection::one(i64::MAX)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::range_Integer_1__Integer_1__Integer_MANY_: the range from 0 to 9223372036854775807 by 1 would have 9223372036854775807 Integers, more than 10000000",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = range_step(&Collection::one(i64::MIN)?, &Collection::one(i64::MAX)?, &Collection::one(1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::collection::range_Integer_1__Integer_1__Integer_1__Integer_MANY_: the range from -9223372036854775808 to 9223372036854775807 by 1 would have 18446744073709551615 Integers, more than 10000000",
+            bad.err().unwrap().to_string()
+        );
+
+        assert_eq!(
+            integers(&[i64::MIN, -1, i64::MAX - 1])?,
+            range_step(&Collection::one(i64::MIN)?, &Collection::one(i64::MAX)?, &Collection::one(i64::MAX)?)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn grouping_zip_and_pair() -> PureExecutionResult<()>
+    {
+        let zipped = zip(&integers(&[1, 2, 3])?, &collection_of(Type::String, ZERO_MANY, ["a".into(), "b".into()])?)?;
+        assert_eq!(Type::Pair, zipped.pure_type());
+        assert_eq!(vec!["<1, a>", "<2, b>"], zipped.into_iter().map(Value::to_string).collect::<Vec<_>>());
+        assert_eq!(Some("Pair<Integer, String>".to_string()), zipped.into_iter().next().map(|p| p.generic_type().to_string()));
+        assert_eq!(collection_of(Type::Pair, ZERO_MANY, None)?, zip(&ZERO_NIL, &integers(&[1])?)?);
+
+        let pair = pair(&Collection::one(1)?, &Collection::one(2.5)?)?;
+        assert_eq!(PURE_ONE, pair.multiplicity());
+        let Some(Value::Pair(pair)) = pair.into_iter().next()
+        else
+        {
+            panic!("pair did not give a Pair")
+        };
+        assert_eq!((&Value::from(1), &Value::from(2.5)), (pair.first(), pair.second()));
+        assert_eq!("Pair<Integer, Float>", pair.generic_type().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn grouping_list_and_partition() -> PureExecutionResult<()>
+    {
+        assert_eq!(integer_list(&[1, 2])?.to_collection(), list(&integers(&[1, 2])?)?);
+
+        // The types of the values are kept, not just the type of the collection
+        let zipped = zip(&integers(&[1, 2])?, &collection_of(Type::String, ZERO_MANY, ["a".into(), "b".into()])?)?;
+        let pairs = list(&zipped)?;
+        assert_eq!(Some("List<Pair<Integer, String>>".to_string()), pairs.into_iter().next().map(|l| l.generic_type().to_string()));
+        let nested = zip(&pairs, &list(&integers(&[])?)?)?;
+        assert_eq!(
+            Some("Pair<List<Pair<Integer, String>>, List<Integer>>".to_string()),
+            nested.into_iter().next().map(|p| p.generic_type().to_string())
+        );
+        let groups = group_by(&zipped, &unary(Type::Pair, Type::Integer, PURE_ONE, |_| Collection::one(0))?)?;
+        assert_eq!(Some("Map<Integer, List<Pair<Integer, String>>>".to_string()), groups.into_iter().next().map(|m| m.generic_type().to_string()));
+
+        let parts = partition(
+            &integers(&[1, 2, 3, 4, 5])?,
+            &unary(Type::Integer, Type::Boolean, PURE_ONE, |v| Collection::one(i64::try_from(v.clone())? % 2 == 0))?,
+        )?;
+        let expected = PurePair::new(of_list(Type::Integer)?, integer_list(&[2, 4])?, of_list(Type::Integer)?, integer_list(&[1, 3, 5])?)?;
+        assert_eq!(Value::from(expected).to_collection(), parts);
+        assert_eq!(Some("Pair<List<Integer>, List<Integer>>".to_string()), parts.into_iter().next().map(|p| p.generic_type().to_string()));
+
+        Ok(())
+    }
+
+    fn of_list(element_type: Type) -> PureExecutionResult<GenericType> { GenericType::new(Type::List, vec![GenericType::from(element_type)]) }
+
+    #[test]
+    fn grouping_group_by() -> PureExecutionResult<()>
+    {
+        let groups = group_by(
+            &integers(&[1, 2, 3, 4, 5, 6, 7])?,
+            &unary(Type::Integer, Type::Integer, PURE_ONE, |v| Collection::one(i64::try_from(v.clone())? % 3))?,
+        )?;
+        let Some(Value::Map(groups)) = groups.into_iter().next()
+        else
+        {
+            panic!("groupBy did not give a Map")
+        };
+        assert_eq!("Map<Integer, List<Integer>>", groups.generic_type().to_string());
+        assert_eq!(3, groups.len());
+        assert_eq!(Some(&integer_list(&[1, 4, 7])?), groups.get(&Value::from(1)));
+        assert_eq!(Some(&integer_list(&[3, 6])?), groups.get(&Value::from(0.0)));
+        assert_eq!(None, groups.get(&Value::from(3)));
+
+        let empty = group_by(&integers(&[])?, &unary(Type::Integer, Type::Integer, PURE_ONE, |v| Collection::one(v.clone()))?)?;
+        assert!(matches!(empty.into_iter().next(), Some(Value::Map(map)) if map.is_empty()));
+
+        let bad = group_by(&integers(&[1])?, &unary(Type::Integer, Type::Integer, PURE_ONE, |_| Ok(Collection::zero(Type::Integer)))?);
+        assert!(bad.is_err());
+
+        Ok(())
+    }
+}
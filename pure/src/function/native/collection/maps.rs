@@ -0,0 +1,202 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use pure_macros::pure_native;
+
+use crate::data::map::PureMap;
+use crate::data::pair::PurePair;
+use crate::function::native::collection_of;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+// The types of a map are those of its entries, as far as they are known, and are widened as entries of other types are put into it.
+
+/// A map of the pairs' first values to their second values.  A later pair for the same key replaces an earlier one.
+#[pure_native("meta::pure::functions::collection::newMap(Pair<K, V>[*]):Map<K, V>[1]")]
+fn new_map(pairs: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let nil = || GenericType::from(Type::Nil);
+    let map = put_pairs(FUNC, 0, &PureMap::empty(nil(), nil()), &pairs)?;
+    Collection::one(map)
+}
+
+/// The value for a key, if the map has one.
+#[pure_native("meta::pure::functions::collection::get(Map<U, V>[1], U[1]):V[0..1]")]
+fn map_get(map: impl FunctionArgument, key: &Value) -> PureExecutionResult<Collection>
+{
+    let map = map_of(FUNC, 0, &map)?;
+    collection_of(raw_type(map.value_type()), ZERO_ONE, map.get(key).cloned())
+}
+
+/// A map with the entry for a key added or replaced, leaving the original map unchanged.
+#[pure_native("meta::pure::functions::collection::put(Map<U, V>[1], U[1], V[1]):Map<U, V>[1]")]
+fn map_put(map: impl FunctionArgument, key: &Value, value: &Value) -> PureExecutionResult<Collection>
+{
+    let map = map_of(FUNC, 0, &map)?.widen(&key.generic_type(), &value.generic_type());
+    Collection::one(map.put(key.clone(), value.clone())?)
+}
+
+/// A map with the entries for the pairs' first values added or replaced by their second values, leaving the original map unchanged.
+#[pure_native("meta::pure::functions::collection::putAll(Map<U, V>[1], Pair<U, V>[*]):Map<U, V>[1]")]
+fn map_put_all(map: impl FunctionArgument, pairs: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let map = put_pairs(FUNC, 1, map_of(FUNC, 0, &map)?, &pairs)?;
+    Collection::one(map)
+}
+
+/// A map with the entries of another map added, replacing any for the same keys, leaving the original map unchanged.
+#[pure_native("meta::pure::functions::collection::putAll(Map<U, V>[1], Map<U, V>[1]):Map<U, V>[1]")]
+fn map_put_all_map(map: impl FunctionArgument, other: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let other = map_of(FUNC, 1, &other)?;
+    let map = map_of(FUNC, 0, &map)?.widen(other.key_type(), other.value_type());
+    Collection::one(map.put_all(other.iter().map(|(key, value)| (key.clone(), value.clone())))?)
+}
+
+/// The keys of the map in no particular order.
+#[pure_native("meta::pure::functions::collection::keys(Map<U, V>[1]):U[*]")]
+fn map_keys(map: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let map = map_of(FUNC, 0, &map)?;
+    collection_of(raw_type(map.key_type()), ZERO_MANY, map.keys().cloned())
+}
+
+/// The values of the map in no particular order.
+#[pure_native("meta::pure::functions::collection::values(Map<U, V>[1]):V[*]")]
+fn map_values(map: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let map = map_of(FUNC, 0, &map)?;
+    collection_of(raw_type(map.value_type()), ZERO_MANY, map.values().cloned())
+}
+
+/// The entries of the map as Pairs of their keys and values, in no particular order.
+#[pure_native("meta::pure::functions::collection::keyValues(Map<U, V>[1]):Pair<U, V>[*]")]
+fn key_values(map: impl FunctionArgument) -> PureExecutionResult<Collection>
+{
+    let map = map_of(FUNC, 0, &map)?;
+    let pairs = map
+        .iter()
+        .map(|(key, value)| Ok(Value::from(PurePair::new(map.key_type().clone(), key.clone(), map.value_type().clone(), value.clone())?)))
+        .collect::<PureExecutionResult<Vec<_>>>()?;
+    collection_of(Type::Pair, ZERO_MANY, pairs)
+}
+
+/// Adds the map natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_new_map(registry)?;
+    register_map_get(registry)?;
+    register_map_put(registry)?;
+    register_map_put_all(registry)?;
+    register_map_put_all_map(registry)?;
+    register_map_keys(registry)?;
+    register_map_values(registry)?;
+    register_key_values(registry)?;
+    Ok(())
+}
+
+fn map_of<'a, C>(func: &str, arg: usize, col: &'a C) -> PureExecutionResult<&'a PureMap>
+where
+    C: FunctionArgument,
+{
+    match col.one_raw(func, arg, Type::Map)?
+    {
+        Value::Map(map) => Ok(map),
+        other => Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("{other} is not a Map") }),
+    }
+}
+
+/// Puts the pairs into a map, widening its types to those of the pairs.
+fn put_pairs<C>(func: &str, arg: usize, map: &PureMap, pairs: &C) -> PureExecutionResult<PureMap>
+where
+    C: FunctionArgument,
+{
+    let pairs = pairs
+        .many_raw(func, arg, Type::Pair)?
+        .map(|value| match value
+        {
+            Value::Pair(pair) => Ok(pair),
+            other => Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("{other} is not a Pair") }),
+        })
+        .collect::<PureExecutionResult<Vec<_>>>()?;
+
+    let map = pairs.iter().fold(map.clone(), |map, pair| map.widen(pair.first_type(), pair.second_type()));
+    map.put_all(pairs.into_iter().map(|pair| (pair.first().clone(), pair.second().clone())))
+}
+
+fn raw_type(generic_type: &GenericType) -> Type { generic_type.raw_type().unwrap_or(Type::Any) }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn pairs(entries: &[(&str, i64)]) -> PureExecutionResult<Collection>
+    {
+        let of = GenericType::from;
+        let pairs = entries
+            .iter()
+            .map(|(key, value)| Ok(Value::from(PurePair::new(of(Type::String), (*key).into(), of(Type::Integer), (*value).into())?)))
+            .collect::<PureExecutionResult<Vec<_>>>()?;
+        collection_of(Type::Pair, ZERO_MANY, pairs)
+    }
+
+    fn sorted(col: &Collection) -> Vec<String>
+    {
+        let mut values = col.into_iter().map(Value::to_string).collect::<Vec<_>>();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn maps_new_and_get() -> PureExecutionResult<()>
+    {
+        let map = new_map(&pairs(&[("a", 1), ("b", 2), ("a", 3)])?)?;
+        assert_eq!(Some("Map<String, Integer>".to_string()), map.into_iter().next().map(|m| m.generic_type().to_string()));
+
+        assert_eq!(collection_of(Type::Integer, ZERO_ONE, Some(3.into()))?, map_get(&map, &Collection::one("a")?)?);
+        assert_eq!(collection_of(Type::Integer, ZERO_ONE, None)?, map_get(&map, &Collection::one("c")?)?);
+        assert_eq!(ZERO_ONE, map_get(&map, &Collection::one("c")?)?.multiplicity());
+
+        let empty = new_map(&ZERO_NIL)?;
+        assert_eq!(Some("Map<Nil, Nil>".to_string()), empty.into_iter().next().map(|m| m.generic_type().to_string()));
+        assert_eq!(collection_of(Type::Nil, ZERO_ONE, None)?, map_get(&empty, &Collection::one(1)?)?);
+
+        let bad = map_get(&Collection::one("a")?, &Collection::one("a")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::collection::get_Map_1__U_1__V_$0_1$_: Unexpected value: expected Map[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn maps_put_and_put_all() -> PureExecutionResult<()>
+    {
+        let map = new_map(&pairs(&[("a", 1)])?)?;
+
+        let put = map_put(&map, &Collection::one("b")?, &Collection::one(2)?)?;
+        assert_eq!(vec!["a", "b"], sorted(&map_keys(&put)?));
+        assert_eq!(vec!["a"], sorted(&map_keys(&map)?));
+
+        let widened = map_put(&put, &Collection::one("c")?, &Collection::one(2.5)?)?;
+        assert_eq!(Some("Map<String, Number>".to_string()), widened.into_iter().next().map(|m| m.generic_type().to_string()));
+        assert_eq!(vec!["1", "2", "2.5"], sorted(&map_values(&widened)?));
+
+        let all = map_put_all(&map, &pairs(&[("a", 10), ("z", 26)])?)?;
+        assert_eq!(vec!["<a, 10>", "<z, 26>"], sorted(&key_values(&all)?));
+        assert_eq!(vec!["<a, 1>"], sorted(&key_values(&map)?));
+
+        let merged = map_put_all_map(&put, &all)?;
+        assert_eq!(vec!["<a, 10>", "<b, 2>", "<z, 26>"], sorted(&key_values(&merged)?));
+
+        // Keys are equal as Pure tests equality
+        let numbers = map_put(&new_map(&ZERO_NIL)?, &Collection::one(1)?, &Collection::one("one")?)?;
+        let numbers = map_put(&numbers, &Collection::one(1.0)?, &Collection::one("uno")?)?;
+        assert_eq!(vec!["uno"], sorted(&map_values(&numbers)?));
+        assert_eq!(collection_of(Type::String, ZERO_ONE, Some("uno".into()))?, map_get(&numbers, &Value::decimal("1.00")?.to_collection())?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+
+use pure_macros::pure_native;
+
+use crate::data::date::PureDate;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+#[pure_native("meta::pure::functions::date::now():DateTime[1]")]
+fn now() -> PureExecutionResult<Collection> { Collection::one(PureDate::now()) }
+
+#[pure_native("meta::pure::functions::date::today():StrictDate[1]")]
+fn today() -> PureExecutionResult<Collection> { Collection::one(PureDate::now().date_part()) }
+
+/// Removes the time from a date.  `%latest` has no time and is returned unchanged.
+#[pure_native("meta::pure::functions::date::datePart(Date[1]):Date[1]")]
+fn date_part(col: &Value) -> PureExecutionResult<Collection>
+{
+    match col
+    {
+        Value::Date(date) => Collection::one(date.date_part()),
+        Value::LatestDate => Collection::one(Value::LatestDate),
+        other => Err(no_day(FUNC, 0, other)),
+    }
+}
+
+#[pure_native("meta::pure::functions::date::isOnDay(Date[1], Date[1]):Boolean[1]")]
+fn is_on_day(left: &Value, right: &Value) -> PureExecutionResult<Collection> { Collection::one(compare_days(FUNC, left, right)?.is_eq()) }
+
+#[pure_native("meta::pure::functions::date::isAfterDay(Date[1], Date[1]):Boolean[1]")]
+fn is_after_day(left: &Value, right: &Value) -> PureExecutionResult<Collection> { Collection::one(compare_days(FUNC, left, right)?.is_gt()) }
+
+#[pure_native("meta::pure::functions::date::isBeforeDay(Date[1], Date[1]):Boolean[1]")]
+fn is_before_day(left: &Value, right: &Value) -> PureExecutionResult<Collection> { Collection::one(compare_days(FUNC, left, right)?.is_lt()) }
+
+#[pure_native("meta::pure::functions::date::isOnOrAfterDay(Date[1], Date[1]):Boolean[1]")]
+fn is_on_or_after_day(left: &Value, right: &Value) -> PureExecutionResult<Collection> { Collection::one(compare_days(FUNC, left, right)?.is_ge()) }
+
+#[pure_native("meta::pure::functions::date::isOnOrBeforeDay(Date[1], Date[1]):Boolean[1]")]
+fn is_on_or_before_day(left: &Value, right: &Value) -> PureExecutionResult<Collection> { Collection::one(compare_days(FUNC, left, right)?.is_le()) }
+
+/// Adds the date natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_now(registry)?;
+    register_today(registry)?;
+    register_date_part(registry)?;
+    register_is_on_day(registry)?;
+    register_is_after_day(registry)?;
+    register_is_before_day(registry)?;
+    register_is_on_or_after_day(registry)?;
+    register_is_on_or_before_day(registry)?;
+    Ok(())
+}
+
+/// Compares the days of two dates ignoring any time.  Both dates must have a day, though either may be `%latest`, which is after
+/// every concrete day.
+fn compare_days(func: &str, left: &Value, right: &Value) -> PureExecutionResult<Ordering>
+{
+    let left = day_of(func, 0, left)?;
+    let right = day_of(func, 1, right)?;
+    left.compare_dates(&right).ok_or(PureExecutionError::UnexpectedError { problem: "days should always be comparable" })
+}
+
+fn day_of(func: &str, arg: usize, value: &Value) -> PureExecutionResult<Value>
+{
+    match value
+    {
+        Value::Date(date) if date.day().is_some() => Ok(Value::Date(date.date_part())),
+        Value::LatestDate => Ok(Value::LatestDate),
+        other => Err(no_day(func, arg, other)),
+    }
+}
+
+fn no_day(func: &str, arg: usize, value: &Value) -> PureExecutionError
+{
+    PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: format!("{} {value} does not have a day", value.pure_type()) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::data::date::DatePrecision;
+    use crate::function::native::fixtures::date;
+
+    #[test]
+    fn date_now_and_today() -> PureExecutionResult<()>
+    {
+        let now: PureDate = match now()?.contents
+        {
+            CollectionContents::One(v) => v.try_into()?,
+            _ => unreachable!(),
+        };
+        assert_eq!(DatePrecision::Subsecond, now.precision());
+
+        let today = today()?;
+        assert_eq!(Type::StrictDate, today.pure_type());
+        assert_eq!(Collection::one(true)?, is_on_day(&today, &Value::from(now).to_collection())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_date_part() -> PureExecutionResult<()>
+    {
+        assert_eq!(date("%2022-03-01")?, date_part(&date("%2022-03-01T10:15:00.000+0000")?)?);
+        assert_eq!(date("%2022-03-01")?, date_part(&date("%2022-03-01")?)?);
+        assert_eq!(date("%latest")?, date_part(&date("%latest")?)?);
+
+        let bad = date_part(&date("%10:15")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::date::datePart_Date_1__Date_1_: StrictTime 10:15 does not have a day",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = date_part(&Collection::one("2022-03-01")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::date::datePart_Date_1__Date_1_: Unexpected value: expected Date[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_day_comparisons() -> PureExecutionResult<()>
+    {
+        let morning = date("%2022-03-01T09:00+0000")?;
+        let evening = date("%2022-03-01T21:00+0000")?;
+        let next_day = date("%2022-03-02")?;
+
+        assert_eq!(Collection::one(true)?, is_on_day(&morning, &evening)?);
+        assert_eq!(Collection::one(false)?, is_on_day(&morning, &next_day)?);
+        assert_eq!(Collection::one(true)?, is_after_day(&next_day, &evening)?);
+        assert_eq!(Collection::one(false)?, is_after_day(&evening, &morning)?);
+        assert_eq!(Collection::one(true)?, is_before_day(&evening, &next_day)?);
+        assert_eq!(Collection::one(false)?, is_before_day(&morning, &evening)?);
+        assert_eq!(Collection::one(true)?, is_on_or_after_day(&evening, &morning)?);
+        assert_eq!(Collection::one(false)?, is_on_or_after_day(&evening, &next_day)?);
+        assert_eq!(Collection::one(true)?, is_on_or_before_day(&evening, &morning)?);
+        assert_eq!(Collection::one(false)?, is_on_or_before_day(&next_day, &morning)?);
+
+        let bad = is_on_day(&morning, &date("%2022-03")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::date::isOnDay_Date_1__Date_1__Boolean_1_: Date 2022-03 does not have a day",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn date_latest_is_after_every_day() -> PureExecutionResult<()>
+    {
+        let latest = date("%latest")?;
+        let last_day = date("%9999-12-31")?;
+
+        assert_eq!(Collection::one(true)?, is_on_day(&latest, &latest)?);
+        assert_eq!(Collection::one(false)?, is_on_day(&latest, &last_day)?);
+        assert_eq!(Collection::one(true)?, is_after_day(&latest, &last_day)?);
+        assert_eq!(Collection::one(true)?, is_before_day(&last_day, &latest)?);
+        assert_eq!(Collection::one(true)?, is_on_or_before_day(&latest, &latest)?);
+        assert_eq!(Collection::one(false)?, is_on_or_before_day(&latest, &last_day)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+//! Collections and lambdas shared by the tests of the natives.
+
+use crate::data::lambda::Lambda;
+use crate::function::native::collection_of;
+use crate::*;
+
+pub fn integers(values: &[i64]) -> PureExecutionResult<Collection> { collection_of(Type::Integer, ZERO_MANY, values.iter().map(|v| Value::from(*v))) }
+
+pub fn floats(values: &[f64]) -> PureExecutionResult<Collection> { collection_of(Type::Float, ZERO_MANY, values.iter().map(|v| Value::from(*v))) }
+
+pub fn numbers(values: &[Value]) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_MANY, values.iter().cloned()) }
+
+pub fn strings(values: &[&str]) -> PureExecutionResult<Collection> { collection_of(Type::String, ZERO_MANY, values.iter().map(|v| Value::from(*v))) }
+
+pub fn decimals(values: &[&str]) -> PureExecutionResult<Collection>
+{
+    let values = values.iter().map(|v| Value::decimal(v)).collect::<PureExecutionResult<Vec<_>>>()?;
+    collection_of(Type::Decimal, ZERO_MANY, values)
+}
+
+pub fn decimal(text: &str) -> PureExecutionResult<Collection> { Ok(Value::decimal(text)?.to_collection()) }
+
+pub fn date(literal: &str) -> PureExecutionResult<Collection> { Ok(Value::date(literal)?.to_collection()) }
+
+pub fn zero_one(pure_type: Type, value: Option<Value>) -> PureExecutionResult<Collection> { collection_of(pure_type, ZERO_ONE, value) }
+
+/// A lambda of one `[1]` parameter whose body is given the parameter's value.
+pub fn unary<F>(from: Type, to: Type, to_mult: Multiplicity, body: F) -> PureExecutionResult<Collection>
+where
+    F: Fn(&Value) -> PureExecutionResult<Collection> + Send + Sync + 'static,
+{
+    let of = GenericType::from;
+    let lambda = Lambda::new(vec![(of(from), PURE_ONE)], (of(to), to_mult), move |args| body(args[0].into_iter().next().unwrap()));
+    Collection::one(lambda)
+}
@@ -0,0 +1,650 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, Pow, Signed, Zero};
+use pure_macros::pure_native;
+
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+pub use self::rounding::*;
+pub use self::scientific::*;
+pub use self::statistics::*;
+
+pub mod rounding;
+pub mod scientific;
+pub mod statistics;
+
+#[pure_native("meta::pure::functions::math::plus(Integer[*]):Integer[1]")]
+fn iplus(col: Vec<i64>) -> PureExecutionResult<Collection>
+{
+    let sum = col.into_iter().try_fold(0_i64, |a, i| a.checked_add(i).ok_or_else(|| overflow(FUNC)))?;
+    Collection::one(sum)
+}
+
+#[pure_native("meta::pure::functions::math::plus(Float[*]):Float[1]")]
+fn fplus(col: Vec<f64>) -> PureExecutionResult<Collection> { Collection::one(col.into_iter().sum::<f64>()) }
+
+#[pure_native("meta::pure::functions::math::plus(Number[*]):Number[1]")]
+fn nplus(col: Vec<&Value>) -> PureExecutionResult<Collection>
+{
+    let sum = col.into_iter().try_fold(Value::from(0), |a, n| promote(FUNC, 0, a, n, i64::checked_add, |l, r| l + r, |l, r| l + r))?;
+    Ok(sum.to_collection())
+}
+
+/// Subtracts the remaining integers from the first.  A single integer is negated and an empty collection gives zero.
+#[pure_native("meta::pure::functions::math::minus(Integer[*]):Integer[1]")]
+fn iminus(col: Vec<i64>) -> PureExecutionResult<Collection>
+{
+    let result = match col.split_first()
+    {
+        None => 0,
+        Some((only, [])) => only.checked_neg().ok_or_else(|| overflow(FUNC))?,
+        Some((first, rest)) => rest.iter().try_fold(*first, |a, i| a.checked_sub(*i).ok_or_else(|| overflow(FUNC)))?,
+    };
+    Collection::one(result)
+}
+
+/// Subtracts the remaining floats from the first.  A single float is negated and an empty collection gives zero.
+#[pure_native("meta::pure::functions::math::minus(Float[*]):Float[1]")]
+fn fminus(col: Vec<f64>) -> PureExecutionResult<Collection>
+{
+    let result = match col.split_first()
+    {
+        None => 0.0,
+        Some((only, [])) => -only,
+        Some((first, rest)) => rest.iter().fold(*first, |a, f| a - f),
+    };
+    Collection::one(result)
+}
+
+/// Subtracts the remaining numbers from the first, promoting as `plus` does.  A single number is negated and an empty collection
+/// gives zero.
+#[pure_native("meta::pure::functions::math::minus(Number[*]):Number[1]")]
+fn nminus(col: Vec<&Value>) -> PureExecutionResult<Collection>
+{
+    let result = match col.split_first()
+    {
+        None => Value::from(0),
+        Some((only, [])) => negate(FUNC, 0, only)?,
+        Some((first, rest)) => rest.iter().try_fold((*first).clone(), |a, n| promote(FUNC, 0, a, n, i64::checked_sub, |l, r| l - r, |l, r| l - r))?,
+    };
+    Ok(result.to_collection())
+}
+
+#[pure_native("meta::pure::functions::math::times(Integer[*]):Integer[1]")]
+fn itimes(col: Vec<i64>) -> PureExecutionResult<Collection>
+{
+    let product = col.into_iter().try_fold(1_i64, |a, i| a.checked_mul(i).ok_or_else(|| overflow(FUNC)))?;
+    Collection::one(product)
+}
+
+#[pure_native("meta::pure::functions::math::times(Float[*]):Float[1]")]
+fn ftimes(col: Vec<f64>) -> PureExecutionResult<Collection> { Collection::one(col.into_iter().product::<f64>()) }
+
+#[pure_native("meta::pure::functions::math::times(Number[*]):Number[1]")]
+fn ntimes(col: Vec<&Value>) -> PureExecutionResult<Collection>
+{
+    let product = col.into_iter().try_fold(Value::from(1), |a, n| promote(FUNC, 0, a, n, i64::checked_mul, |l, r| l * r, |l, r| l * r))?;
+    Ok(product.to_collection())
+}
+
+/// Divides one number by another always giving a Float.
+#[pure_native("meta::pure::functions::math::divide(Number[1], Number[1]):Float[1]")]
+fn ndivide(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let left: f64 = left.clone().try_into()?;
+    let right: f64 = right.clone().try_into()?;
+    if right == 0.0
+    {
+        return Err(division_by_zero(FUNC));
+    }
+    Collection::one(left / right)
+}
+
+/// The remainder of dividing one number by another, which has the sign of the dividend as Java's `%` does.  As in Legend Pure, `rem`
+/// takes numbers of any type whereas `mod` only takes Integers.
+#[pure_native("meta::pure::functions::math::rem(Number[1], Number[1]):Number[1]")]
+fn nrem(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    if right.compare_numbers(&Value::from(0)) == Some(Ordering::Equal)
+    {
+        return Err(division_by_zero(FUNC));
+    }
+    Ok(promote(FUNC, 1, left.clone(), right, |l, r| Some(l.wrapping_rem(r)), |l, r| l % r, |l, r| l % r)?.to_collection())
+}
+
+/// The modulus of one integer by another, which has the sign of the divisor as Java's `Math.floorMod` does.  Legend Pure has no `mod`
+/// of other numbers, for which `rem` can be used instead.
+#[pure_native("meta::pure::functions::math::mod(Integer[1], Integer[1]):Integer[1]")]
+fn imod(left: i64, right: i64) -> PureExecutionResult<Collection>
+{
+    if right == 0
+    {
+        return Err(division_by_zero(FUNC));
+    }
+
+    let rem = left.wrapping_rem(right);
+    Collection::one(if rem != 0 && (rem < 0) != (right < 0) { rem + right } else { rem })
+}
+
+#[pure_native("meta::pure::functions::math::abs(Integer[1]):Integer[1]")]
+fn iabs(col: i64) -> PureExecutionResult<Collection> { Collection::one(col.checked_abs().ok_or_else(|| overflow(FUNC))?) }
+
+#[pure_native("meta::pure::functions::math::abs(Float[1]):Float[1]")]
+fn fabs(col: f64) -> PureExecutionResult<Collection> { Collection::one(col.abs()) }
+
+#[pure_native("meta::pure::functions::math::abs(Number[1]):Number[1]")]
+fn nabs(col: &Value) -> PureExecutionResult<Collection>
+{
+    let abs = match col
+    {
+        Value::Integer(i) => Value::Integer(i.checked_abs().ok_or_else(|| overflow(FUNC))?),
+        Value::Float(f) => Value::Float(f.abs()),
+        Value::Decimal(d) => Value::Decimal(d.abs()),
+        other => return Err(not_a_number(FUNC, 0, other)),
+    };
+    Ok(abs.to_collection())
+}
+
+#[pure_native("meta::pure::functions::math::plus(Decimal[*]):Decimal[1]")]
+fn dplus(col: Vec<BigDecimal>) -> PureExecutionResult<Collection> { Collection::one(col.into_iter().fold(BigDecimal::zero(), |a, d| a + d)) }
+
+/// Subtracts the remaining decimals from the first.  A single decimal is negated and an empty collection gives zero.
+#[pure_native("meta::pure::functions::math::minus(Decimal[*]):Decimal[1]")]
+fn dminus(col: Vec<BigDecimal>) -> PureExecutionResult<Collection>
+{
+    let mut iter = col.into_iter();
+    let result = match (iter.next(), iter.len())
+    {
+        (None, _) => BigDecimal::zero(),
+        (Some(only), 0) => -only,
+        (Some(first), _) => iter.fold(first, |a, d| a - d),
+    };
+    Collection::one(result)
+}
+
+#[pure_native("meta::pure::functions::math::times(Decimal[*]):Decimal[1]")]
+fn dtimes(col: Vec<BigDecimal>) -> PureExecutionResult<Collection> { Collection::one(col.into_iter().fold(BigDecimal::from(1), |a, d| a * d)) }
+
+/// Divides one decimal by another giving a result with the requested scale, rounding half up as Legend Pure does.  The quotient is
+/// computed exactly to that scale, which is limited to `MAX_SCALE` in either direction.
+#[pure_native("meta::pure::functions::math::divide(Decimal[1], Decimal[1], Integer[1]):Decimal[1]")]
+fn ddivide(left: BigDecimal, right: BigDecimal, scale: i64) -> PureExecutionResult<Collection>
+{
+    let scale = check_scale(FUNC, 2, scale)?;
+    if right.is_zero()
+    {
+        return Err(division_by_zero(FUNC));
+    }
+
+    // left / right is l / r * 10^(right_scale - left_scale), so its digits at the scale are l * 10^shift / r
+    let (l, left_scale) = left.into_bigint_and_scale();
+    let (r, right_scale) = right.into_bigint_and_scale();
+    let shift = scale + right_scale - left_scale;
+    let power = BigInt::from(10).pow(shift.unsigned_abs());
+    let (numerator, denominator) = if shift >= 0 { (l * power, r) } else { (l, r * power) };
+
+    // Half up rounds away from zero when the remainder is at least half of the divisor
+    let quotient = &numerator / &denominator;
+    let remainder = &numerator % &denominator;
+    let quotient = if remainder.abs() * 2 >= denominator.abs() { quotient + numerator.signum() * denominator.signum() } else { quotient };
+    Collection::one(BigDecimal::new(quotient, scale))
+}
+
+/// Adds the arithmetic natives, then those of the other math modules, to a registry.  The Decimal overloads of the operators are
+/// registered after the others, which are preferred for arguments that suit either.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_iplus(registry)?;
+    register_fplus(registry)?;
+    register_nplus(registry)?;
+    register_iminus(registry)?;
+    register_fminus(registry)?;
+    register_nminus(registry)?;
+    register_itimes(registry)?;
+    register_ftimes(registry)?;
+    register_ntimes(registry)?;
+    register_ndivide(registry)?;
+    register_nrem(registry)?;
+    register_imod(registry)?;
+    register_iabs(registry)?;
+    register_fabs(registry)?;
+    register_nabs(registry)?;
+    register_dplus(registry)?;
+    register_dminus(registry)?;
+    register_dtimes(registry)?;
+    register_ddivide(registry)?;
+    rounding::register(registry)?;
+    scientific::register(registry)?;
+    statistics::register(registry)?;
+    Ok(())
+}
+
+/// Applies an arithmetic operator to two numbers promoting them as Legend Pure does: a Decimal operand makes the result a Decimal,
+/// otherwise a Float operand makes it a Float.  The integer operator returns `None` on overflow.  The left number is taken from the
+/// native's first argument and the right from argument `arg`, to which an operand that is not a number is attributed.
+pub(crate) fn promote<I, F, D>(
+    func: &str,
+    arg: usize,
+    left: Value,
+    right: &Value,
+    int_op: I,
+    float_op: F,
+    decimal_op: D,
+) -> PureExecutionResult<Value>
+where
+    I: Fn(i64, i64) -> Option<i64>,
+    F: Fn(f64, f64) -> f64,
+    D: Fn(BigDecimal, BigDecimal) -> BigDecimal,
+{
+    let to_decimal = |v: &Value| v.to_decimal().ok_or_else(|| PureExecutionError::IllegalCast { from: v.to_string(), to: "Decimal".into() });
+
+    match (&left, right)
+    {
+        (Value::Integer(l), Value::Integer(r)) => int_op(*l, *r).map(Value::Integer).ok_or_else(|| overflow(func)),
+        (Value::Decimal(_), Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) | (Value::Integer(_) | Value::Float(_), Value::Decimal(_)) =>
+        {
+            Ok(Value::Decimal(decimal_op(to_decimal(&left)?, to_decimal(right)?)))
+        }
+        (Value::Integer(_) | Value::Float(_), Value::Integer(_) | Value::Float(_)) =>
+        {
+            Ok(Value::Float(float_op(left.try_into()?, right.clone().try_into()?)))
+        }
+        (Value::Integer(_) | Value::Float(_) | Value::Decimal(_), _) => Err(not_a_number(func, arg, right)),
+        _ => Err(not_a_number(func, 0, &left)),
+    }
+}
+
+fn negate(func: &str, arg: usize, value: &Value) -> PureExecutionResult<Value>
+{
+    match value
+    {
+        Value::Integer(i) => i.checked_neg().map(Value::Integer).ok_or_else(|| overflow(func)),
+        Value::Float(f) => Ok(Value::Float(-f)),
+        Value::Decimal(d) => Ok(Value::Decimal(-d)),
+        other => Err(not_a_number(func, arg, other)),
+    }
+}
+
+/// The largest scale, in either direction, that a Decimal can be divided or rounded to.  Legend Pure has no such limit, but the scale
+/// is the number of digits to be computed so a larger one could take indefinitely long.
+pub(super) const MAX_SCALE: i64 = 10_000;
+
+/// Checks that a scale is within `MAX_SCALE`.
+pub(super) fn check_scale(func: &str, arg: usize, scale: i64) -> PureExecutionResult<i64>
+{
+    if !(-MAX_SCALE..=MAX_SCALE).contains(&scale)
+    {
+        let cause = format!("scale {scale} is not between -{MAX_SCALE} and {MAX_SCALE}");
+        return Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause });
+    }
+    Ok(scale)
+}
+
+pub(super) fn not_a_number(func: &str, arg: usize, value: &Value) -> PureExecutionError
+{
+    let unexpected = PureExecutionError::UnexpectedValue { expected: "Number".into(), got: value.pure_type().to_string() };
+    PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: unexpected.to_string() }
+}
+
+fn overflow(func: &str) -> PureExecutionError { PureExecutionError::IntegerOverflow { func: func.to_string() } }
+
+pub(super) fn division_by_zero(func: &str) -> PureExecutionError
+{
+    PureExecutionError::IllegalArgument { func: func.to_string(), arg: 1, cause: "division by zero".into() }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::{decimal, decimals};
+
+    #[test]
+    fn integer_plus() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?)?);
+        assert_eq!(Collection::one(1)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.build()?)?);
+        assert_eq!(Collection::one(6)?, iplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.push(2)?.push(3)?.build()?)?);
+
+        let bad = iplus(&ZERO_NIL);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Integer_MANY__Integer_1_: Unexpected value: expected Integer[*] but got Nil[0]",
+            format!("{}", bad.err().unwrap())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn integer_overflow_is_detected() -> PureExecutionResult<()>
+    {
+        let integers = |values: &[i64]| values.iter().try_fold(CollectionBuilder::new(Type::Integer, ZERO_MANY), |b, i| b.push(*i))?.build();
+
+        let bad = iplus(&integers(&[i64::MAX, 1])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::plus_Integer_MANY__Integer_1_", bad.err().unwrap().to_string());
+
+        let bad = iminus(&integers(&[i64::MIN, 1])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::minus_Integer_MANY__Integer_1_", bad.err().unwrap().to_string());
+
+        let bad = iminus(&integers(&[i64::MIN])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::minus_Integer_MANY__Integer_1_", bad.err().unwrap().to_string());
+
+        let bad = itimes(&integers(&[i64::MAX / 2, 3])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::times_Integer_MANY__Integer_1_", bad.err().unwrap().to_string());
+
+        let bad = nplus(&integers(&[i64::MAX, 1])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::plus_Number_MANY__Number_1_", bad.err().unwrap().to_string());
+
+        let bad = ntimes(&integers(&[i64::MIN, -1])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::times_Number_MANY__Number_1_", bad.err().unwrap().to_string());
+
+        let bad = iabs(&Collection::one(i64::MIN)?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::abs_Integer_1__Integer_1_", bad.err().unwrap().to_string());
+
+        assert_eq!(Collection::one(0)?, nrem(&Collection::one(i64::MIN)?, &Collection::one(-1)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn float_plus() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0.0)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).build()?)?);
+        assert_eq!(Collection::one(1.1)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.build()?)?);
+        assert_eq!(Collection::one(6.6)?, fplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.push(2.2)?.push(3.3)?.build()?)?);
+
+        let bad = fplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Float_MANY__Float_1_: Unexpected value: expected Float[*] but got Integer[*]",
+            format!("{}", bad.err().unwrap())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_plus() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?)?);
+        assert_eq!(Collection::one(1)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.build()?)?);
+        assert_eq!(Collection::one(6)?, nplus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).push(1)?.push(2)?.push(3)?.build()?)?);
+
+        assert_eq!(Collection::one(0)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).build()?)?);
+        assert_eq!(Collection::one(1.1)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.build()?)?);
+        assert_eq!(Collection::one(6.6)?, nplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).push(1.1)?.push(2.2)?.push(3.3)?.build()?)?);
+
+        assert_eq!(Collection::one(6.4)?, nplus(&CollectionBuilder::new(Type::Number, ZERO_MANY).push(1.1)?.push(2)?.push(3.3)?.build()?)?);
+
+        let bad = nplus(&CollectionBuilder::new(Type::Boolean, ZERO_MANY).build()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Number_MANY__Number_1_: Unexpected value: expected Number[*] but got Boolean[*]",
+            format!("{}", bad.err().unwrap())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_plus_promotes_to_decimal() -> PureExecutionResult<()>
+    {
+        let numbers = CollectionBuilder::new(Type::Number, ZERO_MANY).push(1)?.push(Value::decimal("0.1")?)?.push(0.2)?.build()?;
+        assert_eq!(decimal("1.3")?, nplus(&numbers)?);
+
+        let numbers = CollectionBuilder::new(Type::Number, ZERO_MANY).push(Value::decimal("0.1")?)?.push(Value::decimal("0.2")?)?.build()?;
+        assert_eq!(decimal("0.3")?, nplus(&numbers)?);
+
+        let numbers = CollectionBuilder::new(Type::Number, ZERO_MANY).push(Value::decimal("0.1")?)?.push(f64::NAN)?.build()?;
+        let bad = nplus(&numbers);
+        assert!(bad.is_err());
+        assert_eq!("Cast exception: NaN cannot be cast to Decimal", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn integer_minus_and_times() -> PureExecutionResult<()>
+    {
+        let integers = |values: &[i64]| values.iter().try_fold(CollectionBuilder::new(Type::Integer, ZERO_MANY), |b, i| b.push(*i))?.build();
+
+        assert_eq!(Collection::one(0)?, iminus(&integers(&[])?)?);
+        assert_eq!(Collection::one(-5)?, iminus(&integers(&[5])?)?);
+        assert_eq!(Collection::one(4)?, iminus(&integers(&[10, 4, 2])?)?);
+        assert_eq!(Collection::one(1)?, itimes(&integers(&[])?)?);
+        assert_eq!(Collection::one(-24)?, itimes(&integers(&[2, -3, 4])?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn float_minus_and_times() -> PureExecutionResult<()>
+    {
+        let floats = |values: &[f64]| values.iter().try_fold(CollectionBuilder::new(Type::Float, ZERO_MANY), |b, f| b.push(*f))?.build();
+
+        assert_eq!(Collection::one(0.0)?, fminus(&floats(&[])?)?);
+        assert_eq!(Collection::one(-1.5)?, fminus(&floats(&[1.5])?)?);
+        assert_eq!(Collection::one(1.0)?, fminus(&floats(&[3.5, 2.0, 0.5])?)?);
+        assert_eq!(Collection::one(1.0)?, ftimes(&floats(&[])?)?);
+        assert_eq!(Collection::one(-7.5)?, ftimes(&floats(&[2.5, -3.0])?)?);
+
+        let bad = fminus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::minus_Float_MANY__Float_1_: Unexpected value: expected Float[*] but got Integer[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_minus_and_times() -> PureExecutionResult<()>
+    {
+        let numbers = |values: &[Value]| values.iter().try_fold(CollectionBuilder::new(Type::Number, ZERO_MANY), |b, n| b.push(n.clone()))?.build();
+
+        assert_eq!(Collection::one(0)?, nminus(&numbers(&[])?)?);
+        assert_eq!(Collection::one(-2)?, nminus(&numbers(&[Value::from(2)])?)?);
+        assert_eq!(Collection::one(-2.5)?, nminus(&numbers(&[Value::from(2.5)])?)?);
+        assert_eq!(decimal("-2.5")?, nminus(&numbers(&[Value::decimal("2.5")?])?)?);
+        assert_eq!(Collection::one(7)?, nminus(&numbers(&[Value::from(10), Value::from(3)])?)?);
+        assert_eq!(Collection::one(7.5)?, nminus(&numbers(&[Value::from(10), Value::from(2.5)])?)?);
+        assert_eq!(decimal("9.9")?, nminus(&numbers(&[Value::from(10), Value::decimal("0.1")?])?)?);
+
+        assert_eq!(Collection::one(1)?, ntimes(&numbers(&[])?)?);
+        assert_eq!(Collection::one(6)?, ntimes(&numbers(&[Value::from(2), Value::from(3)])?)?);
+        assert_eq!(Collection::one(5.0)?, ntimes(&numbers(&[Value::from(2), Value::from(2.5)])?)?);
+        assert_eq!(decimal("0.6")?, ntimes(&numbers(&[Value::from(2), Value::decimal("0.3")?])?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_divide() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(2.5)?, ndivide(&Collection::one(5)?, &Collection::one(2)?)?);
+        assert_eq!(Collection::one(0.5)?, ndivide(&Collection::one(1.0)?, &Collection::one(2)?)?);
+        assert_eq!(Collection::one(0.25)?, ndivide(&decimal("0.5")?, &Collection::one(2)?)?);
+
+        let bad = ndivide(&Collection::one(1)?, &Collection::one(0)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::divide_Number_1__Number_1__Float_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = ndivide(&Collection::one(1)?, &Collection::one("2")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::divide_Number_1__Number_1__Float_1_: Unexpected value: expected Number[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_rem_and_integer_mod() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(1)?, nrem(&Collection::one(7)?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(-1)?, nrem(&Collection::one(-7)?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(1)?, nrem(&Collection::one(7)?, &Collection::one(-3)?)?);
+        assert_eq!(Collection::one(1.5)?, nrem(&Collection::one(7.5)?, &Collection::one(3)?)?);
+        assert_eq!(decimal("0.5")?, nrem(&decimal("3.5")?, &Collection::one(1)?)?);
+
+        assert_eq!(Collection::one(1)?, imod(&Collection::one(7)?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(2)?, imod(&Collection::one(-7)?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(-2)?, imod(&Collection::one(7)?, &Collection::one(-3)?)?);
+        assert_eq!(Collection::one(-1)?, imod(&Collection::one(-7)?, &Collection::one(-3)?)?);
+        assert_eq!(Collection::one(0)?, imod(&Collection::one(6)?, &Collection::one(-3)?)?);
+
+        let bad = nrem(&Collection::one(1.5)?, &Collection::one(0.0)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::rem_Number_1__Number_1__Number_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = imod(&Collection::one(1)?, &Collection::one(0)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::mod_Integer_1__Integer_1__Integer_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_abs() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(3)?, iabs(&Collection::one(-3)?)?);
+        assert_eq!(Collection::one(3)?, iabs(&Collection::one(3)?)?);
+        assert_eq!(Collection::one(2.5)?, fabs(&Collection::one(-2.5)?)?);
+        assert_eq!(Collection::one(3)?, nabs(&Collection::one(-3)?)?);
+        assert_eq!(Collection::one(2.5)?, nabs(&Collection::one(-2.5)?)?);
+        assert_eq!(decimal("2.5")?, nabs(&decimal("-2.5")?)?);
+
+        let bad = fabs(&Collection::one("1.5")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::abs_Float_1__Float_1_: Unexpected value: expected Float[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_plus() -> PureExecutionResult<()>
+    {
+        assert_eq!(decimal("0")?, dplus(&decimals(&[])?)?);
+        assert_eq!(decimal("0.3")?, dplus(&decimals(&["0.1", "0.2"])?)?);
+        assert_eq!(decimal("100000000000000000000.01")?, dplus(&decimals(&["99999999999999999999.99", "0.02"])?)?);
+
+        let bad = dplus(&CollectionBuilder::new(Type::Float, ZERO_MANY).build()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::plus_Decimal_MANY__Decimal_1_: Unexpected value: expected Decimal[*] but got Float[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_minus() -> PureExecutionResult<()>
+    {
+        assert_eq!(decimal("0")?, dminus(&decimals(&[])?)?);
+        assert_eq!(decimal("-1.5")?, dminus(&decimals(&["1.5"])?)?);
+        assert_eq!(decimal("0.7")?, dminus(&decimals(&["1.0", "0.2", "0.1"])?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_times() -> PureExecutionResult<()>
+    {
+        assert_eq!(decimal("1")?, dtimes(&decimals(&[])?)?);
+        assert_eq!(decimal("0.02")?, dtimes(&decimals(&["0.1", "0.2"])?)?);
+
+        let product: BigDecimal = match dtimes(&decimals(&["1.10", "2.5"])?)?.contents
+        {
+            CollectionContents::One(v) => v.try_into()?,
+            _ => unreachable!(),
+        };
+        assert_eq!(3, product.fractional_digit_count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_divide() -> PureExecutionResult<()>
+    {
+        assert_eq!(decimal("0.33")?, ddivide(&decimal("1")?, &decimal("3")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("0.67")?, ddivide(&decimal("2")?, &decimal("3")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("0.13")?, ddivide(&decimal("0.25")?, &decimal("2")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("-0.13")?, ddivide(&decimal("-0.25")?, &decimal("2")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("3333333333333333333333.3333333333")?, ddivide(&decimal("1E22")?, &decimal("3")?, &Collection::one(10)?)?);
+        assert_eq!(decimal("1.23E+4")?, ddivide(&decimal("12345")?, &decimal("1")?, &Collection::one(-2)?)?);
+        assert_eq!(decimal("-0.3")?, ddivide(&decimal("0.5")?, &decimal("-2")?, &Collection::one(1)?)?);
+        assert_eq!(decimal(&format!("0.{}7", "6".repeat(149)))?, ddivide(&decimal("2")?, &decimal("3")?, &Collection::one(150)?)?);
+
+        let bad = ddivide(&decimal("1")?, &decimal("3")?, &Collection::one(100_000_000)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 2 of meta::pure::functions::math::divide_Decimal_1__Decimal_1__Integer_1__Decimal_1_: scale 100000000 is not between -10000 and 10000",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = ddivide(&decimal("1")?, &decimal("0.00")?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::divide_Decimal_1__Decimal_1__Integer_1__Decimal_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn number_errors_name_their_argument()
+    {
+        let func = "meta::pure::functions::math::rem_Number_1__Number_1__Number_1_";
+        let rem = |left: Value, right: Value| promote(func, 1, left, &right, |l, r| Some(l % r), |l, r| l % r, |l, r| l % r);
+
+        let bad = rem(Value::from(1), Value::from("a"));
+        assert!(bad.is_err());
+        assert_eq!(
+            format!("Invalid argument passed to arg 1 of {func}: Unexpected value: expected Number but got String"),
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = rem(Value::from(true), Value::from(1));
+        assert!(bad.is_err());
+        assert_eq!(
+            format!("Invalid argument passed to arg 0 of {func}: Unexpected value: expected Number but got Boolean"),
+            bad.err().unwrap().to_string()
+        );
+
+        let func = "meta::pure::functions::math::minus_Number_MANY__Number_1_";
+        let bad = negate(func, 0, &Value::from("a"));
+        assert!(bad.is_err());
+        assert_eq!(
+            format!("Invalid argument passed to arg 0 of {func}: Unexpected value: expected Number but got String"),
+            bad.err().unwrap().to_string()
+        );
+    }
+}
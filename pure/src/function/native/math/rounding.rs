@@ -0,0 +1,216 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+use pure_macros::pure_native;
+
+use crate::function::native::math::{check_scale, not_a_number};
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+// Floats are converted to Integers as Java's `(long)` cast does: NaN becomes zero and values beyond the range of an Integer become
+// the nearest extreme.  Decimals are converted exactly so a Decimal beyond that range is an overflow.
+
+/// Rounds a number to the nearest Integer.  A Float half way between two Integers is rounded up, towards positive infinity, as
+/// Java's `Math.round` does, whereas a Decimal is rounded half away from zero.
+#[pure_native("meta::pure::functions::math::round(Number[1]):Integer[1]")]
+fn round(col: &Value) -> PureExecutionResult<Collection> { integer(FUNC, col, java_round, RoundingMode::HalfUp) }
+
+/// Rounds a Decimal to the given number of decimal places, rounding half away from zero.  The number of places is limited to
+/// `MAX_SCALE` in either direction.
+#[pure_native("meta::pure::functions::math::round(Decimal[1], Integer[1]):Decimal[1]")]
+fn dround(col: BigDecimal, scale: i64) -> PureExecutionResult<Collection>
+{
+    let scale = check_scale(FUNC, 1, scale)?;
+    Collection::one(col.with_scale_round(scale, RoundingMode::HalfUp))
+}
+
+/// Rounds a Float to the given number of decimal places, rounding half away from zero.  As in Java the Float is rounded as it
+/// is written, so `1.005` rounds to `1.01` even though its binary value is slightly less than `1.005`.  The number of places is
+/// limited as it is for a Decimal.
+#[pure_native("meta::pure::functions::math::round(Float[1], Integer[1]):Float[1]")]
+fn fround(float: f64, scale: i64) -> PureExecutionResult<Collection>
+{
+    let scale = check_scale(FUNC, 1, scale)?;
+    let decimal = BigDecimal::from_str(&format!("{float:?}")).map_err(|_| PureExecutionError::IllegalArgument {
+        func: FUNC.to_string(),
+        arg: 0,
+        cause: format!("{float} cannot be rounded"),
+    })?;
+    Collection::one(decimal.with_scale_round(scale, RoundingMode::HalfUp).to_f64().unwrap_or(f64::NAN))
+}
+
+/// The smallest Integer that is not less than the number.
+#[pure_native("meta::pure::functions::math::ceiling(Number[1]):Integer[1]")]
+fn ceiling(col: &Value) -> PureExecutionResult<Collection> { integer(FUNC, col, f64::ceil, RoundingMode::Ceiling) }
+
+/// The largest Integer that is not greater than the number.
+#[pure_native("meta::pure::functions::math::floor(Number[1]):Integer[1]")]
+fn floor(col: &Value) -> PureExecutionResult<Collection> { integer(FUNC, col, f64::floor, RoundingMode::Floor) }
+
+/// -1, 0 or 1 according to whether the number is negative, zero or positive.  The sign of NaN is 0.
+#[pure_native("meta::pure::functions::math::sign(Number[1]):Integer[1]")]
+fn sign(col: &Value) -> PureExecutionResult<Collection>
+{
+    let sign = match col
+    {
+        Value::Integer(i) => i.signum(),
+        Value::Float(f) if f.is_nan() || *f == 0.0 => 0,
+        Value::Float(f) => f.signum() as i64,
+        Value::Decimal(d) => d.cmp(&BigDecimal::from(0)) as i64,
+        other => return Err(not_a_number(FUNC, 0, other)),
+    };
+    Collection::one(sign)
+}
+
+/// Adds the rounding natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_round(registry)?;
+    register_dround(registry)?;
+    register_fround(registry)?;
+    register_ceiling(registry)?;
+    register_floor(registry)?;
+    register_sign(registry)?;
+    Ok(())
+}
+
+fn integer(func: &str, col: &Value, float_op: fn(f64) -> f64, mode: RoundingMode) -> PureExecutionResult<Collection>
+{
+    let integer = match col
+    {
+        Value::Integer(i) => *i,
+        Value::Float(f) => float_op(*f) as i64,
+        Value::Decimal(d) => d.with_scale_round(0, mode).to_i64().ok_or_else(|| PureExecutionError::IntegerOverflow { func: func.to_string() })?,
+        other => return Err(not_a_number(func, 0, other)),
+    };
+    Collection::one(integer)
+}
+
+/// Java's `Math.round` which rounds halves towards positive infinity.  Subtracting the floor is exact so, unlike adding 0.5 and
+/// taking the floor, this does not round up the largest Float below 0.5.
+fn java_round(float: f64) -> f64
+{
+    let floor = float.floor();
+    if float - floor >= 0.5
+    {
+        floor + 1.0
+    }
+    else
+    {
+        floor
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::decimal;
+
+    #[test]
+    fn rounding_round() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(3)?, round(&Collection::one(3)?)?);
+        assert_eq!(Collection::one(3)?, round(&Collection::one(2.5)?)?);
+        assert_eq!(Collection::one(-2)?, round(&Collection::one(-2.5)?)?);
+        assert_eq!(Collection::one(-3)?, round(&Collection::one(-2.51)?)?);
+        assert_eq!(Collection::one(0)?, round(&Collection::one(0.49999999999999994)?)?);
+        assert_eq!(Collection::one(0)?, round(&Collection::one(f64::NAN)?)?);
+        assert_eq!(Collection::one(i64::MAX)?, round(&Collection::one(f64::INFINITY)?)?);
+        assert_eq!(Collection::one(3)?, round(&decimal("2.5")?)?);
+        assert_eq!(Collection::one(-3)?, round(&decimal("-2.5")?)?);
+        assert_eq!(Collection::one(-2)?, round(&decimal("-2.49")?)?);
+
+        let bad = round(&decimal("1E19")?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::round_Number_1__Integer_1_", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rounding_round_with_scale() -> PureExecutionResult<()>
+    {
+        assert_eq!(decimal("1.24")?, dround(&decimal("1.235")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("-1.24")?, dround(&decimal("-1.235")?, &Collection::one(2)?)?);
+        assert_eq!(decimal("1.2000")?, dround(&decimal("1.2")?, &Collection::one(4)?)?);
+        assert_eq!(decimal("1.2E+2")?, dround(&decimal("123")?, &Collection::one(-1)?)?);
+
+        assert_eq!(Collection::one(1.01)?, fround(&Collection::one(1.005)?, &Collection::one(2)?)?);
+        assert_eq!(Collection::one(-1.01)?, fround(&Collection::one(-1.005)?, &Collection::one(2)?)?);
+        assert_eq!(Collection::one(3.0)?, fround(&Collection::one(2.5)?, &Collection::one(0)?)?);
+        assert_eq!(Collection::one(1200.0)?, fround(&Collection::one(1234.5)?, &Collection::one(-2)?)?);
+        assert_eq!(Collection::one(0.0)?, fround(&Collection::one(-0.001)?, &Collection::one(2)?)?);
+
+        let bad = fround(&Collection::one(f64::NAN)?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::round_Float_1__Integer_1__Float_1_: NaN cannot be rounded",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = dround(&Collection::one(1.5)?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::round_Decimal_1__Integer_1__Decimal_1_: Unexpected value: expected Decimal[1] but got Float[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = dround(&decimal("1.5")?, &Collection::one(100_000_000)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::round_Decimal_1__Integer_1__Decimal_1_: scale 100000000 is not between -10000 and 10000",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = fround(&Collection::one(1.5)?, &Collection::one(i64::MIN)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::round_Float_1__Integer_1__Float_1_: scale -9223372036854775808 is not between -10000 and 10000",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rounding_ceiling_and_floor() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(2)?, ceiling(&Collection::one(2)?)?);
+        assert_eq!(Collection::one(2)?, ceiling(&Collection::one(1.1)?)?);
+        assert_eq!(Collection::one(-1)?, ceiling(&Collection::one(-1.9)?)?);
+        assert_eq!(Collection::one(2)?, ceiling(&decimal("1.0001")?)?);
+        assert_eq!(Collection::one(-1)?, ceiling(&decimal("-1.9")?)?);
+
+        assert_eq!(Collection::one(1)?, floor(&Collection::one(1.9)?)?);
+        assert_eq!(Collection::one(-2)?, floor(&Collection::one(-1.1)?)?);
+        assert_eq!(Collection::one(-2)?, floor(&decimal("-1.0001")?)?);
+        assert_eq!(Collection::one(i64::MIN)?, floor(&Collection::one(-1e300)?)?);
+
+        let bad = floor(&Collection::one("1.5")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::floor_Number_1__Integer_1_: Unexpected value: expected Number[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rounding_sign() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(-1)?, sign(&Collection::one(-5)?)?);
+        assert_eq!(Collection::one(0)?, sign(&Collection::one(0)?)?);
+        assert_eq!(Collection::one(1)?, sign(&Collection::one(0.5)?)?);
+        assert_eq!(Collection::one(0)?, sign(&Collection::one(-0.0)?)?);
+        assert_eq!(Collection::one(0)?, sign(&Collection::one(f64::NAN)?)?);
+        assert_eq!(Collection::one(-1)?, sign(&Collection::one(f64::NEG_INFINITY)?)?);
+        assert_eq!(Collection::one(-1)?, sign(&decimal("-0.001")?)?);
+        assert_eq!(Collection::one(0)?, sign(&decimal("0.00")?)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::f64::consts::LOG10_2;
+use std::num::NonZeroU64;
+
+use bigdecimal::{BigDecimal, Context, One, RoundingMode, Zero};
+use pure_macros::pure_native;
+
+use crate::function::native::math::division_by_zero;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+// These functions are computed on Floats and, as in Java, an input outside the function's domain gives NaN (or an infinity)
+// rather than an error.
+
+/// The square root of a number, which is NaN for negative numbers.
+#[pure_native("meta::pure::functions::math::sqrt(Number[1]):Float[1]")]
+fn sqrt(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.sqrt()) }
+
+/// Raises a number to a power.  A Decimal raised to an Integer power gives a Decimal, otherwise the result is a Float.  A
+/// non-negative power of a Decimal is computed exactly, so it is limited to `MAX_POWER_DIGITS` digits, and a negative one is the
+/// reciprocal of that exact power rounded half up to `RECIPROCAL_PRECISION` significant digits.
+#[pure_native("meta::pure::functions::math::pow(Number[1], Number[1]):Number[1]")]
+fn pow(base: &Value, exponent: &Value) -> PureExecutionResult<Collection>
+{
+    let power = match (base, exponent)
+    {
+        (Value::Decimal(base), Value::Integer(exponent)) => Value::Decimal(decimal_power(FUNC, base, *exponent)?),
+        (base, exponent) => Value::Float(f64::try_from(base.clone())?.powf(f64::try_from(exponent.clone())?)),
+    };
+    Ok(power.to_collection())
+}
+
+/// Euler's number raised to a power.
+#[pure_native("meta::pure::functions::math::exp(Number[1]):Float[1]")]
+fn exp(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.exp()) }
+
+/// The natural logarithm of a number, which is negative infinity for zero and NaN for negative numbers.
+#[pure_native("meta::pure::functions::math::log(Number[1]):Float[1]")]
+fn log(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.ln()) }
+
+/// The base 10 logarithm of a number, which is negative infinity for zero and NaN for negative numbers.
+#[pure_native("meta::pure::functions::math::log10(Number[1]):Float[1]")]
+fn log10(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.log10()) }
+
+/// The sine of an angle in radians.
+#[pure_native("meta::pure::functions::math::sin(Number[1]):Float[1]")]
+fn sin(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.sin()) }
+
+/// The cosine of an angle in radians.
+#[pure_native("meta::pure::functions::math::cos(Number[1]):Float[1]")]
+fn cos(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.cos()) }
+
+/// The tangent of an angle in radians.
+#[pure_native("meta::pure::functions::math::tan(Number[1]):Float[1]")]
+fn tan(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.tan()) }
+
+/// The arc sine, in radians, of a number, which is NaN outside -1..1.
+#[pure_native("meta::pure::functions::math::asin(Number[1]):Float[1]")]
+fn asin(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.asin()) }
+
+/// The arc cosine, in radians, of a number, which is NaN outside -1..1.
+#[pure_native("meta::pure::functions::math::acos(Number[1]):Float[1]")]
+fn acos(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.acos()) }
+
+/// The arc tangent, in radians, of a number.
+#[pure_native("meta::pure::functions::math::atan(Number[1]):Float[1]")]
+fn atan(col: &Value) -> PureExecutionResult<Collection> { Collection::one(float(col)?.atan()) }
+
+/// The angle, in radians, of the point (x, y) from the positive x axis.  Note that, as in Java, y is the first argument.
+#[pure_native("meta::pure::functions::math::atan2(Number[1], Number[1]):Float[1]")]
+fn atan2(y: &Value, x: &Value) -> PureExecutionResult<Collection> { Collection::one(float(y)?.atan2(float(x)?)) }
+
+/// Adds the scientific natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_sqrt(registry)?;
+    register_pow(registry)?;
+    register_exp(registry)?;
+    register_log(registry)?;
+    register_log10(registry)?;
+    register_sin(registry)?;
+    register_cos(registry)?;
+    register_tan(registry)?;
+    register_asin(registry)?;
+    register_acos(registry)?;
+    register_atan(registry)?;
+    register_atan2(registry)?;
+    Ok(())
+}
+
+fn float(value: &Value) -> PureExecutionResult<f64> { f64::try_from(value.clone()) }
+
+/// The most digits the exact power of a Decimal may have.  Each squaring doubles the digits to be multiplied so the time taken
+/// grows faster than the size of the result.
+const MAX_POWER_DIGITS: u64 = 1_000_000;
+
+/// The number of significant digits to which the reciprocal giving a negative power of a Decimal is rounded.
+const RECIPROCAL_PRECISION: u64 = 100;
+
+fn decimal_power(func: &str, base: &BigDecimal, exponent: i64) -> PureExecutionResult<BigDecimal>
+{
+    if exponent < 0 && base.is_zero()
+    {
+        return Err(division_by_zero(func));
+    }
+    // The digits of the unscaled base's power are at least the exponent times the whole powers of two in the base, so an
+    // unscaled base of one or minus one, such as 0.01, has a power of one digit whatever the exponent
+    let (unscaled, scale) = base.as_bigint_and_scale();
+    let digits = unscaled.bits().saturating_sub(1) as f64 * exponent.unsigned_abs() as f64 * LOG10_2;
+    let cause = if digits > MAX_POWER_DIGITS as f64
+    {
+        Some(format!("{base} to the power {exponent} would have more than {MAX_POWER_DIGITS} digits"))
+    }
+    else if scale.checked_mul(exponent).is_none()
+    {
+        Some(format!("{base} to the power {exponent} would have a scale beyond the range of an Integer"))
+    }
+    else
+    {
+        None
+    };
+    if let Some(cause) = cause
+    {
+        return Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg: 1, cause });
+    }
+
+    // Exact, unlike powi which rounds to a default precision
+    let mut power = BigDecimal::from(1);
+    let mut square = base.clone();
+    let mut remaining = exponent.unsigned_abs();
+    while remaining > 0
+    {
+        if remaining & 1 == 1
+        {
+            power *= &square;
+        }
+        remaining >>= 1;
+        if remaining > 0
+        {
+            square = &square * &square;
+        }
+    }
+    if exponent >= 0
+    {
+        return Ok(power);
+    }
+
+    let precision = NonZeroU64::new(RECIPROCAL_PRECISION).expect("precision is not zero");
+    let reciprocal = Context::new(precision, RoundingMode::HalfUp).invert(&power);
+    // As in Java, an exact quotient has no more trailing zeros than its operands' scales require
+    if (&reciprocal * &power).is_one()
+    {
+        Ok(reciprocal.normalized())
+    }
+    else
+    {
+        Ok(reciprocal)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use super::*;
+    use crate::function::native::fixtures::decimal;
+
+    fn is_nan(col: PureExecutionResult<Collection>) -> bool
+    {
+        matches!(col.map(|c| c.contents), Ok(CollectionContents::One(Value::Float(f))) if f.is_nan())
+    }
+
+    fn close(expected: f64, col: PureExecutionResult<Collection>) -> bool
+    {
+        matches!(col.map(|c| c.contents), Ok(CollectionContents::One(Value::Float(f))) if (f - expected).abs() < 1e-12)
+    }
+
+    #[test]
+    fn scientific_sqrt_and_pow() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(3.0)?, sqrt(&Collection::one(9)?)?);
+        assert_eq!(Collection::one(1.5)?, sqrt(&decimal("2.25")?)?);
+        assert!(is_nan(sqrt(&Collection::one(-1)?)));
+
+        assert_eq!(Collection::one(8.0)?, pow(&Collection::one(2)?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(0.25)?, pow(&Collection::one(2)?, &Collection::one(-2)?)?);
+        assert_eq!(Collection::one(3.0)?, pow(&Collection::one(9.0)?, &Collection::one(0.5)?)?);
+        assert_eq!(Collection::one(1.0)?, pow(&Collection::one(0)?, &Collection::one(0)?)?);
+        assert!(is_nan(pow(&Collection::one(-8)?, &Collection::one(1.0 / 3.0)?)));
+        assert_eq!(decimal("1.331")?, pow(&decimal("1.1")?, &Collection::one(3)?)?);
+        assert_eq!(decimal("0.25")?, pow(&decimal("2")?, &Collection::one(-2)?)?);
+        assert_eq!(decimal(&format!("0.{}", "3".repeat(100)))?, pow(&decimal("3")?, &Collection::one(-1)?)?);
+        assert_eq!(decimal(&format!("-0.1{}7", "6".repeat(98)))?, pow(&decimal("-6")?, &Collection::one(-1)?)?);
+        assert_eq!(decimal("1E+2")?, pow(&decimal("0.1")?, &Collection::one(-2)?)?);
+        assert_eq!(decimal("1E-1000000001")?, pow(&decimal("0.1")?, &Collection::one(1_000_000_001)?)?);
+        assert_eq!(Collection::one(2.0)?, pow(&decimal("4")?, &Collection::one(0.5)?)?);
+
+        let bad = pow(&Collection::one("2")?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::pow_Number_1__Number_1__Number_1_: Unexpected value: expected Number[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = pow(&decimal("0")?, &Collection::one(-1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::pow_Number_1__Number_1__Number_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = pow(&decimal("1.1")?, &Collection::one(30_000_000)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::pow_Number_1__Number_1__Number_1_: 1.1 to the power 30000000 would have more than 1000000 digits",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = pow(&decimal("0.01")?, &Collection::one(i64::MIN)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::pow_Number_1__Number_1__Number_1_: 0.01 to the power -9223372036854775808 would have a scale beyond the range of an Integer",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn scientific_exp_and_log() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(1.0)?, exp(&Collection::one(0)?)?);
+        assert!(close(std::f64::consts::E, exp(&Collection::one(1)?)));
+        assert_eq!(Collection::one(0.0)?, log(&Collection::one(1)?)?);
+        assert!(close(1.0, log(&Collection::one(std::f64::consts::E)?)));
+        assert_eq!(Collection::one(f64::NEG_INFINITY)?, log(&Collection::one(0)?)?);
+        assert!(is_nan(log(&Collection::one(-1)?)));
+        assert_eq!(Collection::one(3.0)?, log10(&decimal("1000")?)?);
+        assert_eq!(Collection::one(f64::NEG_INFINITY)?, log10(&Collection::one(0.0)?)?);
+        assert!(is_nan(log10(&Collection::one(-10)?)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn scientific_trigonometry() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0.0)?, sin(&Collection::one(0)?)?);
+        assert!(close(1.0, sin(&Collection::one(FRAC_PI_2)?)));
+        assert!(close(-1.0, cos(&Collection::one(PI)?)));
+        assert!(close(1.0, tan(&Collection::one(FRAC_PI_4)?)));
+        assert!(close(FRAC_PI_2, asin(&Collection::one(1)?)));
+        assert!(close(PI, acos(&Collection::one(-1)?)));
+        assert!(is_nan(asin(&Collection::one(2)?)));
+        assert!(is_nan(acos(&decimal("-1.5")?)));
+        assert!(close(FRAC_PI_4, atan(&Collection::one(1)?)));
+        assert!(close(FRAC_PI_2, atan2(&Collection::one(1)?, &Collection::one(0)?)));
+        assert!(close(-3.0 * FRAC_PI_4, atan2(&Collection::one(-1)?, &Collection::one(-1.0)?)));
+
+        let bad = atan2(&Collection::one(1)?, &Collection::zero(Type::Integer));
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::atan2_Number_1__Number_1__Float_1_: Unexpected value: expected Number[1] but got Integer[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,488 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+
+use bigdecimal::{BigDecimal, Zero};
+use pure_macros::pure_native;
+
+use crate::function::native::collection_of;
+use crate::function::native::math::promote;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+#[pure_native("meta::pure::functions::math::sum(Integer[*]):Integer[1]")]
+fn isum(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(sum(FUNC, &numbers(col))?.to_collection()) }
+
+#[pure_native("meta::pure::functions::math::sum(Float[*]):Float[1]")]
+fn fsum(col: Vec<f64>) -> PureExecutionResult<Collection> { Collection::one(col.into_iter().sum::<f64>()) }
+
+/// Sums numbers of any type, promoting as `plus` does so that the sum of Decimals is exact.
+#[pure_native("meta::pure::functions::math::sum(Number[*]):Number[1]")]
+fn nsum(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(sum(FUNC, &numbers(col))?.to_collection()) }
+
+/// The mean of the integers, which are summed exactly so that the sum cannot overflow.
+#[pure_native("meta::pure::functions::math::average(Integer[*]):Float[1]")]
+fn iaverage(col: Vec<&Value>) -> PureExecutionResult<Collection> { average(FUNC, &numbers(col)) }
+
+#[pure_native("meta::pure::functions::math::average(Float[*]):Float[1]")]
+fn faverage(col: Vec<&Value>) -> PureExecutionResult<Collection> { average(FUNC, &numbers(col)) }
+
+/// The mean of numbers of any type.  The numbers are summed exactly before dividing so that Decimals lose no precision until the
+/// result is made a Float.
+#[pure_native("meta::pure::functions::math::average(Number[*]):Float[1]")]
+fn naverage(col: Vec<&Value>) -> PureExecutionResult<Collection> { average(FUNC, &numbers(col)) }
+
+#[pure_native("meta::pure::functions::math::max(Integer[*]):Integer[0..1]")]
+fn imax(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Integer, ZERO_ONE, extreme(numbers(col), Ordering::Greater)) }
+
+/// The largest float ignoring any NaN.
+#[pure_native("meta::pure::functions::math::max(Float[*]):Float[0..1]")]
+fn fmax(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Float, ZERO_ONE, extreme(numbers(col), Ordering::Greater)) }
+
+/// The largest number ignoring any NaN.  Numbers of different types are compared by value and the largest is returned unchanged.
+#[pure_native("meta::pure::functions::math::max(Number[*]):Number[0..1]")]
+fn nmax(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_ONE, extreme(numbers(col), Ordering::Greater)) }
+
+#[pure_native("meta::pure::functions::math::min(Integer[*]):Integer[0..1]")]
+fn imin(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Integer, ZERO_ONE, extreme(numbers(col), Ordering::Less)) }
+
+/// The smallest float ignoring any NaN.
+#[pure_native("meta::pure::functions::math::min(Float[*]):Float[0..1]")]
+fn fmin(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Float, ZERO_ONE, extreme(numbers(col), Ordering::Less)) }
+
+/// The smallest number ignoring any NaN.  Numbers of different types are compared by value and the smallest is returned unchanged.
+#[pure_native("meta::pure::functions::math::min(Number[*]):Number[0..1]")]
+fn nmin(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_ONE, extreme(numbers(col), Ordering::Less)) }
+
+/// The variance of the numbers, which is the sample variance if bias corrected and otherwise the population variance.
+#[pure_native("meta::pure::functions::math::variance(Number[*], Boolean[1]):Number[1]")]
+fn variance(col: Vec<&Value>, bias_corrected: bool) -> PureExecutionResult<Collection>
+{
+    Ok(variance_of(FUNC, &numbers(col), bias_corrected)?.to_collection())
+}
+
+#[pure_native("meta::pure::functions::math::varianceSample(Number[*]):Number[1]")]
+fn variance_sample(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(variance_of(FUNC, &numbers(col), true)?.to_collection()) }
+
+#[pure_native("meta::pure::functions::math::variancePopulation(Number[*]):Number[1]")]
+fn variance_population(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(variance_of(FUNC, &numbers(col), false)?.to_collection()) }
+
+/// The standard deviation of the numbers, which is the sample standard deviation if bias corrected and otherwise the population
+/// standard deviation.
+#[pure_native("meta::pure::functions::math::stdDev(Number[*], Boolean[1]):Number[1]")]
+fn std_dev(col: Vec<&Value>, bias_corrected: bool) -> PureExecutionResult<Collection>
+{
+    Ok(std_dev_of(FUNC, &numbers(col), bias_corrected)?.to_collection())
+}
+
+#[pure_native("meta::pure::functions::math::stdDevSample(Number[*]):Number[1]")]
+fn std_dev_sample(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(std_dev_of(FUNC, &numbers(col), true)?.to_collection()) }
+
+#[pure_native("meta::pure::functions::math::stdDevPopulation(Number[*]):Number[1]")]
+fn std_dev_population(col: Vec<&Value>) -> PureExecutionResult<Collection> { Ok(std_dev_of(FUNC, &numbers(col), false)?.to_collection()) }
+
+/// The middle integer, or the Float mean of the two middle integers when there is an even number of them.
+#[pure_native("meta::pure::functions::math::median(Integer[*]):Number[0..1]")]
+fn imedian(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_ONE, median(FUNC, numbers(col))?) }
+
+#[pure_native("meta::pure::functions::math::median(Float[*]):Float[0..1]")]
+fn fmedian(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Float, ZERO_ONE, median(FUNC, numbers(col))?) }
+
+/// The middle number, or the mean of the two middle numbers when there is an even number of them, which is an exact Decimal if
+/// either is a Decimal.
+#[pure_native("meta::pure::functions::math::median(Number[*]):Number[0..1]")]
+fn nmedian(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_ONE, median(FUNC, numbers(col))?) }
+
+#[pure_native("meta::pure::functions::math::mode(Integer[*]):Integer[0..1]")]
+fn imode(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Integer, ZERO_ONE, mode(numbers(col))) }
+
+#[pure_native("meta::pure::functions::math::mode(Float[*]):Float[0..1]")]
+fn fmode(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Float, ZERO_ONE, mode(numbers(col))) }
+
+/// The most frequent number, where numbers of different types are the same if they are equal in value.  If several numbers are
+/// equally frequent the smallest is chosen.
+#[pure_native("meta::pure::functions::math::mode(Number[*]):Number[0..1]")]
+fn nmode(col: Vec<&Value>) -> PureExecutionResult<Collection> { collection_of(Type::Number, ZERO_ONE, mode(numbers(col))) }
+
+/// The value at a percentile, between 0 and 1, of the numbers sorted in ascending or descending order.  A continuous percentile
+/// interpolates between the two nearest numbers, as SQL's `PERCENTILE_CONT` does, while a discrete one is the first number at or
+/// beyond the percentile, as `PERCENTILE_DISC` is.
+#[pure_native("meta::pure::functions::math::percentile(Number[*], Float[1], Boolean[1], Boolean[1]):Number[0..1]")]
+fn percentile(col: Vec<&Value>, p: f64, ascending: bool, continuous: bool) -> PureExecutionResult<Collection>
+{
+    if !(0.0..=1.0).contains(&p)
+    {
+        return Err(PureExecutionError::IllegalArgument { func: FUNC.to_string(), arg: 1, cause: format!("percentile {p} is not between 0 and 1") });
+    }
+
+    let mut values = sorted(numbers(col));
+    if !ascending
+    {
+        values.reverse();
+    }
+    if values.is_empty()
+    {
+        return collection_of(Type::Number, ZERO_ONE, None);
+    }
+
+    let result = if continuous
+    {
+        let rank = p * (values.len() - 1) as f64;
+        let (lower, upper) = (&values[rank.floor() as usize], &values[rank.ceil() as usize]);
+        let fraction = rank - rank.floor();
+        if has_decimal(&[lower, upper])
+        {
+            let fraction = decimal(FUNC, &Value::from(fraction))?;
+            let (lower, upper) = (decimal(FUNC, lower)?, decimal(FUNC, upper)?);
+            Value::Decimal((&lower + (upper - &lower) * fraction).normalized())
+        }
+        else
+        {
+            let (lower, upper) = (float(FUNC, lower)?, float(FUNC, upper)?);
+            Value::Float(lower + (upper - lower) * fraction)
+        }
+    }
+    else
+    {
+        let index = ((p * values.len() as f64).ceil() as usize).max(1) - 1;
+        values.swap_remove(index)
+    };
+    collection_of(Type::Number, ZERO_ONE, Some(result))
+}
+
+/// Adds the statistics natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_isum(registry)?;
+    register_fsum(registry)?;
+    register_nsum(registry)?;
+    register_iaverage(registry)?;
+    register_faverage(registry)?;
+    register_naverage(registry)?;
+    register_imax(registry)?;
+    register_fmax(registry)?;
+    register_nmax(registry)?;
+    register_imin(registry)?;
+    register_fmin(registry)?;
+    register_nmin(registry)?;
+    register_variance(registry)?;
+    register_variance_sample(registry)?;
+    register_variance_population(registry)?;
+    register_std_dev(registry)?;
+    register_std_dev_sample(registry)?;
+    register_std_dev_population(registry)?;
+    register_imedian(registry)?;
+    register_fmedian(registry)?;
+    register_nmedian(registry)?;
+    register_imode(registry)?;
+    register_fmode(registry)?;
+    register_nmode(registry)?;
+    register_percentile(registry)?;
+    Ok(())
+}
+
+fn numbers(col: Vec<&Value>) -> Vec<Value> { col.into_iter().cloned().collect() }
+
+fn sum(func: &str, values: &[Value]) -> PureExecutionResult<Value>
+{
+    values.iter().try_fold(Value::from(0), |a, n| promote(func, 0, a, n, i64::checked_add, |l, r| l + r, |l, r| l + r))
+}
+
+fn average(func: &str, values: &[Value]) -> PureExecutionResult<Collection>
+{
+    if values.is_empty()
+    {
+        return Err(too_few(func, 1, values));
+    }
+    // Integers are summed as Decimals, as Decimals are, so that their sum cannot overflow
+    if has_decimal(values) || values.iter().all(|v| matches!(v, Value::Integer(_)))
+    {
+        let total = values.iter().map(|v| decimal(func, v)).sum::<PureExecutionResult<BigDecimal>>()?;
+        Collection::one(float(func, &Value::Decimal(total / BigDecimal::from(values.len() as u64)))?)
+    }
+    else
+    {
+        let total = values.iter().map(|v| float(func, v)).sum::<PureExecutionResult<f64>>()?;
+        Collection::one(total / values.len() as f64)
+    }
+}
+
+fn variance_of(func: &str, values: &[Value], bias_corrected: bool) -> PureExecutionResult<Value>
+{
+    let required = if bias_corrected { 2 } else { 1 };
+    if values.len() < required
+    {
+        return Err(too_few(func, required, values));
+    }
+
+    let divisor = values.len() - if bias_corrected { 1 } else { 0 };
+    if has_decimal(values)
+    {
+        let count = BigDecimal::from(values.len() as u64);
+        let values = values.iter().map(|v| decimal(func, v)).collect::<PureExecutionResult<Vec<_>>>()?;
+        let mean = values.iter().sum::<BigDecimal>() / count;
+        let squares = values.iter().map(|v| (v - &mean).square()).sum::<BigDecimal>();
+        Ok(Value::Decimal((squares / BigDecimal::from(divisor as u64)).normalized()))
+    }
+    else
+    {
+        let values = values.iter().map(|v| float(func, v)).collect::<PureExecutionResult<Vec<_>>>()?;
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let squares = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+        Ok(Value::Float(squares / divisor as f64))
+    }
+}
+
+fn std_dev_of(func: &str, values: &[Value], bias_corrected: bool) -> PureExecutionResult<Value>
+{
+    match variance_of(func, values, bias_corrected)?
+    {
+        Value::Decimal(variance) => Ok(Value::Decimal(variance.sqrt().unwrap_or_else(BigDecimal::zero).normalized())),
+        variance => Ok(Value::Float(float(func, &variance)?.sqrt())),
+    }
+}
+
+fn median(func: &str, values: Vec<Value>) -> PureExecutionResult<Option<Value>>
+{
+    let mut values = sorted(values);
+    let len = values.len();
+    if len % 2 == 1
+    {
+        return Ok(Some(values.swap_remove(len / 2)));
+    }
+    if len == 0
+    {
+        return Ok(None);
+    }
+
+    let (lower, upper) = (&values[len / 2 - 1], &values[len / 2]);
+    let median = if has_decimal(&[lower, upper])
+    {
+        Value::Decimal(((decimal(func, lower)? + decimal(func, upper)?) / BigDecimal::from(2)).normalized())
+    }
+    else
+    {
+        Value::Float(float(func, &sum(func, &[lower.clone(), upper.clone()])?)? / 2.0)
+    };
+    Ok(Some(median))
+}
+
+fn mode(values: Vec<Value>) -> Option<Value>
+{
+    let values = sorted(values);
+    let mut best: Option<(&Value, usize)> = None;
+    let mut idx = 0;
+    while idx < values.len()
+    {
+        let run = values[idx..].iter().take_while(|v| v.equals(&values[idx])).count().max(1);
+        if best.is_none_or(|(_, count)| run > count)
+        {
+            best = Some((&values[idx], run));
+        }
+        idx += run;
+    }
+    best.map(|(value, _)| value.clone())
+}
+
+/// The largest or smallest number, ignoring any NaN.
+fn extreme(values: Vec<Value>, wanted: Ordering) -> Option<Value>
+{
+    values.into_iter().filter(|v| !matches!(v, Value::Float(f) if f.is_nan())).reduce(|best, v| {
+        if v.compare_numbers(&best) == Some(wanted)
+        {
+            v
+        }
+        else
+        {
+            best
+        }
+    })
+}
+
+/// Sorts numbers by value, placing any NaN after all other numbers.
+fn sorted(mut values: Vec<Value>) -> Vec<Value>
+{
+    let is_nan = |v: &Value| matches!(v, Value::Float(f) if f.is_nan());
+    values.sort_by(|l, r| l.compare_numbers(r).unwrap_or_else(|| is_nan(l).cmp(&is_nan(r))));
+    values
+}
+
+fn has_decimal<V: std::borrow::Borrow<Value>>(values: &[V]) -> bool { values.iter().any(|v| matches!(v.borrow(), Value::Decimal(_))) }
+
+fn float(func: &str, value: &Value) -> PureExecutionResult<f64>
+{
+    f64::try_from(value.clone()).map_err(|e| PureExecutionError::IllegalArgument { func: func.to_string(), arg: 0, cause: e.to_string() })
+}
+
+/// A number as a Decimal, which a NaN or infinite Float cannot be.
+fn decimal(func: &str, value: &Value) -> PureExecutionResult<BigDecimal>
+{
+    let cause = || format!("{} {} cannot be used as a Decimal", value.pure_type(), value.to_pure_string());
+    value.to_decimal().ok_or_else(|| PureExecutionError::IllegalArgument { func: func.to_string(), arg: 0, cause: cause() })
+}
+
+fn too_few(func: &str, required: usize, values: &[Value]) -> PureExecutionError
+{
+    let cause = format!("at least {required} values are required but got {}", values.len());
+    PureExecutionError::IllegalArgument { func: func.to_string(), arg: 0, cause }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::{floats, integers, numbers, zero_one};
+
+    #[test]
+    fn statistics_sum() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(6)?, isum(&integers(&[1, 2, 3])?)?);
+        assert_eq!(Collection::one(0)?, isum(&integers(&[])?)?);
+        assert_eq!(Collection::one(4.0)?, fsum(&floats(&[1.5, 2.5])?)?);
+        assert_eq!(Collection::one(Value::decimal("0.3")?)?, nsum(&numbers(&[Value::decimal("0.1")?, Value::decimal("0.2")?])?)?);
+        assert_eq!(Collection::one(3.5)?, nsum(&numbers(&[Value::from(1), Value::from(2.5)])?)?);
+
+        let bad = isum(&integers(&[i64::MAX, 1])?);
+        assert!(bad.is_err());
+        assert_eq!("Integer overflow in meta::pure::functions::math::sum_Integer_MANY__Integer_1_", bad.err().unwrap().to_string());
+
+        let bad = isum(&floats(&[1.0])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::sum_Integer_MANY__Integer_1_: Unexpected value: expected Integer[*] but got Float[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_average() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(2.5)?, iaverage(&integers(&[1, 2, 3, 4])?)?);
+        assert_eq!(Collection::one(2.0)?, faverage(&floats(&[1.5, 2.5])?)?);
+        assert_eq!(Collection::one(0.2)?, naverage(&numbers(&[Value::decimal("0.1")?, Value::decimal("0.2")?, Value::from(0.3)])?)?);
+        assert_eq!(Collection::one(2f64.powi(62))?, iaverage(&integers(&[i64::MAX, 1])?)?);
+        assert_eq!(Collection::one(-(2f64.powi(63)))?, iaverage(&integers(&[i64::MIN, i64::MIN])?)?);
+        assert_eq!(Collection::one(2f64.powi(63) / 3.0)?, naverage(&numbers(&[Value::from(i64::MAX), Value::from(1), Value::from(0.0)])?)?);
+
+        let bad = iaverage(&integers(&[])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::average_Integer_MANY__Float_1_: at least 1 values are required but got 0",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_max_and_min() -> PureExecutionResult<()>
+    {
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(3)))?, imax(&integers(&[1, 3, 2])?)?);
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(1)))?, imin(&integers(&[1, 3, 2])?)?);
+        assert_eq!(zero_one(Type::Integer, None)?, imax(&integers(&[])?)?);
+        assert_eq!(zero_one(Type::Float, None)?, fmin(&floats(&[])?)?);
+
+        assert_eq!(zero_one(Type::Float, Some(Value::from(2.5)))?, fmax(&floats(&[1.5, f64::NAN, 2.5])?)?);
+        assert_eq!(zero_one(Type::Float, Some(Value::from(1.5)))?, fmin(&floats(&[f64::NAN, 1.5, 2.5])?)?);
+
+        let mixed = numbers(&[Value::from(2), Value::decimal("2.5")?, Value::from(-1.5)])?;
+        assert_eq!(zero_one(Type::Number, Some(Value::decimal("2.5")?))?, nmax(&mixed)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(-1.5)))?, nmin(&mixed)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_variance_and_std_dev() -> PureExecutionResult<()>
+    {
+        let values = integers(&[2, 4, 4, 4, 5, 5, 7, 9])?;
+
+        assert_eq!(Collection::one(4.0)?, variance_population(&values)?);
+        assert_eq!(Collection::one(32.0 / 7.0)?, variance_sample(&values)?);
+        assert_eq!(Collection::one(32.0 / 7.0)?, variance(&values, &Collection::one(true)?)?);
+        assert_eq!(Collection::one(2.0)?, std_dev_population(&values)?);
+        assert_eq!(Collection::one(2.0)?, std_dev(&values, &Collection::one(false)?)?);
+        assert_eq!(Collection::one((32.0_f64 / 7.0).sqrt())?, std_dev_sample(&values)?);
+
+        let decimals = numbers(&[Value::decimal("0.1")?, Value::decimal("0.2")?, Value::decimal("0.3")?])?;
+        assert_eq!(Collection::one(Value::decimal("0.01")?)?, variance_sample(&decimals)?);
+        assert_eq!(Collection::one(Value::decimal("0.1")?)?, std_dev_sample(&decimals)?);
+
+        let bad = variance_sample(&integers(&[1])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::varianceSample_Number_MANY__Number_1_: at least 2 values are required but got 1",
+            bad.err().unwrap().to_string()
+        );
+        assert_eq!(Collection::one(0.0)?, std_dev_population(&integers(&[1])?)?);
+
+        let bad = variance_sample(&numbers(&[Value::decimal("0.1")?, Value::from(f64::NAN)])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::varianceSample_Number_MANY__Number_1_: Float NaN cannot be used as a Decimal",
+            bad.err().unwrap().to_string()
+        );
+        let bad = nmedian(&numbers(&[Value::decimal("0.1")?, Value::from(f64::INFINITY)])?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::math::median_Number_MANY__Number_$0_1$_: Float Infinity cannot be used as a Decimal",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_median() -> PureExecutionResult<()>
+    {
+        assert_eq!(zero_one(Type::Number, Some(Value::from(3)))?, imedian(&integers(&[5, 1, 3])?)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(2.5)))?, imedian(&integers(&[4, 1, 3, 2])?)?);
+        assert_eq!(zero_one(Type::Number, None)?, imedian(&integers(&[])?)?);
+        assert_eq!(zero_one(Type::Float, Some(Value::from(2.0)))?, fmedian(&floats(&[1.5, 2.5])?)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::decimal("0.15")?))?, nmedian(&numbers(&[Value::decimal("0.2")?, Value::from(0.1)])?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_mode() -> PureExecutionResult<()>
+    {
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(2)))?, imode(&integers(&[3, 2, 1, 2, 3, 2])?)?);
+        assert_eq!(zero_one(Type::Integer, Some(Value::from(1)))?, imode(&integers(&[3, 1, 2])?)?);
+        assert_eq!(zero_one(Type::Integer, None)?, imode(&integers(&[])?)?);
+        assert_eq!(zero_one(Type::Float, Some(Value::from(1.5)))?, fmode(&floats(&[2.5, 1.5, 1.5])?)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(1)))?, nmode(&numbers(&[Value::from(2), Value::from(1), Value::decimal("1.0")?])?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_percentile() -> PureExecutionResult<()>
+    {
+        let values = integers(&[1, 2, 3, 4, 5])?;
+        let yes = Collection::one(true)?;
+        let no = Collection::one(false)?;
+
+        assert_eq!(zero_one(Type::Number, Some(Value::from(3.0)))?, percentile(&values, &Collection::one(0.5)?, &yes, &yes)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(2.2)))?, percentile(&values, &Collection::one(0.3)?, &yes, &yes)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(3.8)))?, percentile(&values, &Collection::one(0.3)?, &no, &yes)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(2)))?, percentile(&values, &Collection::one(0.3)?, &yes, &no)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(1)))?, percentile(&values, &Collection::one(0.0)?, &yes, &no)?);
+        assert_eq!(zero_one(Type::Number, Some(Value::from(5)))?, percentile(&values, &Collection::one(1.0)?, &yes, &no)?);
+        assert_eq!(zero_one(Type::Number, None)?, percentile(&integers(&[])?, &Collection::one(0.5)?, &yes, &yes)?);
+
+        let decimals = numbers(&[Value::decimal("0.1")?, Value::decimal("0.2")?])?;
+        assert_eq!(zero_one(Type::Number, Some(Value::decimal("0.125")?))?, percentile(&decimals, &Collection::one(0.25)?, &yes, &yes)?);
+
+        let bad = percentile(&values, &Collection::one(1.5)?, &yes, &yes);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::math::percentile_Number_MANY__Float_1__Boolean_1__Boolean_1__Number_$0_1$_: percentile 1.5 is not between 0 and 1",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
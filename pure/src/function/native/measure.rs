@@ -0,0 +1,233 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use pure_macros::pure_native;
+
+use crate::data::measure::{Quantity, Unit};
+use crate::function::native::math::promote;
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+pub use meta_pure_functions_measure_convert_Any_1__Unit_1__Any_1_ as convert;
+pub use meta_pure_functions_measure_newUnit_Unit_1__Number_1__Any_1_ as new_unit;
+
+/// Creates a quantity of the unit.  Units are types rather than values so the unit is passed directly.
+#[allow(non_snake_case)]
+pub fn meta_pure_functions_measure_newUnit_Unit_1__Number_1__Any_1_<C>(unit: &Arc<Unit>, amount: C) -> PureExecutionResult<Collection>
+where
+    C: FunctionArgument,
+{
+    const FUNC: &str = "meta::pure::functions::measure::newUnit_Unit_1__Number_1__Any_1_";
+
+    Collection::one(Quantity::new(unit, amount.one_raw(FUNC, 1, Type::Number)?.clone())?)
+}
+
+#[pure_native("meta::pure::functions::measure::getUnitValue(Any[1]):Number[1]")]
+fn get_unit_value(col: &Value) -> PureExecutionResult<Collection> { Ok(quantity(FUNC, 0, col)?.amount().clone().to_collection()) }
+
+/// Converts a quantity to another unit of the same measure.
+#[allow(non_snake_case)]
+pub fn meta_pure_functions_measure_convert_Any_1__Unit_1__Any_1_<C>(col: C, unit: &Arc<Unit>) -> PureExecutionResult<Collection>
+where
+    C: FunctionArgument,
+{
+    const FUNC: &str = "meta::pure::functions::measure::convert_Any_1__Unit_1__Any_1_";
+
+    Collection::one(quantity(FUNC, 0, col.one_raw(FUNC, 0, Type::Any)?)?.convert(unit).map_err(|e| illegal_argument(FUNC, 1, e))?)
+}
+
+/// Adds two quantities of the same measure giving a result in the unit of the first.
+#[pure_native("meta::pure::functions::measure::plus(Any[1], Any[1]):Any[1]")]
+fn mplus(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let left = quantity(FUNC, 0, left)?;
+    let right = quantity(FUNC, 1, right)?.convert(left.unit()).map_err(|e| illegal_argument(FUNC, 1, e))?;
+    let amount = promote(FUNC, 1, left.amount().clone(), right.amount(), i64::checked_add, |l, r| l + r, |l, r| l + r)?;
+    Collection::one(Quantity::new(left.unit(), amount)?)
+}
+
+/// Subtracts a quantity from another of the same measure giving a result in the unit of the first.
+#[pure_native("meta::pure::functions::measure::minus(Any[1], Any[1]):Any[1]")]
+fn mminus(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let left = quantity(FUNC, 0, left)?;
+    let right = quantity(FUNC, 1, right)?.convert(left.unit()).map_err(|e| illegal_argument(FUNC, 1, e))?;
+    let amount = promote(FUNC, 1, left.amount().clone(), right.amount(), i64::checked_sub, |l, r| l - r, |l, r| l - r)?;
+    Collection::one(Quantity::new(left.unit(), amount)?)
+}
+
+#[pure_native("meta::pure::functions::measure::times(Any[1], Number[1]):Any[1]")]
+fn mtimes(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let left = quantity(FUNC, 0, left)?;
+    let amount = promote(FUNC, 1, left.amount().clone(), right, i64::checked_mul, |l, r| l * r, |l, r| l * r)?;
+    Collection::one(Quantity::new(left.unit(), amount)?)
+}
+
+/// Divides a quantity by a number.  As with Pure's divide an Integer amount divided by an Integer gives a Float.
+#[pure_native("meta::pure::functions::measure::divide(Any[1], Number[1]):Any[1]")]
+fn mdivide(left: &Value, right: &Value) -> PureExecutionResult<Collection>
+{
+    let left = quantity(FUNC, 0, left)?;
+    if right.compare_numbers(&Value::from(0)) == Some(Ordering::Equal)
+    {
+        return Err(PureExecutionError::IllegalArgument { func: FUNC.to_string(), arg: 1, cause: "division by zero".into() });
+    }
+
+    let dividend = match (left.amount(), right)
+    {
+        (Value::Integer(l), Value::Integer(_)) => Value::Float(*l as f64),
+        (amount, _) => amount.clone(),
+    };
+    let amount = promote(FUNC, 1, dividend, right, i64::checked_div, |l, r| l / r, |l, r| l / r)?;
+    Collection::one(Quantity::new(left.unit(), amount)?)
+}
+
+/// Adds the measure natives that do not take a unit to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_get_unit_value(registry)?;
+    register_mplus(registry)?;
+    register_mminus(registry)?;
+    register_mtimes(registry)?;
+    register_mdivide(registry)?;
+    Ok(())
+}
+
+fn quantity<'a>(func: &str, arg: usize, value: &'a Value) -> PureExecutionResult<&'a Quantity>
+{
+    match value
+    {
+        Value::Quantity(quantity) => Ok(quantity),
+        other => Err(illegal_argument(func, arg, format!("{} {other} is not a quantity", other.pure_type()))),
+    }
+}
+
+fn illegal_argument<E: ToString>(func: &str, arg: usize, cause: E) -> PureExecutionError
+{
+    PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause: cause.to_string() }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+    use crate::data::model::Model;
+
+    struct Units
+    {
+        share: Arc<Unit>,
+        lot: Arc<Unit>,
+        usd: Arc<Unit>,
+        gbp: Arc<Unit>,
+    }
+
+    fn units() -> PureExecutionResult<Units>
+    {
+        let mut model = Model::new();
+        model.add_measure("trade::Quantity", "Share", &[("Lot", BigDecimal::from(100))])?;
+        model.add_non_convertible_measure("trade::Currency", &["USD", "GBP"])?;
+        Ok(Units {
+            share: model.unit("trade::Quantity~Share")?,
+            lot: model.unit("trade::Quantity~Lot")?,
+            usd: model.unit("trade::Currency~USD")?,
+            gbp: model.unit("trade::Currency~GBP")?,
+        })
+    }
+
+    fn quantity_of(unit: &Arc<Unit>, amount: Value) -> PureExecutionResult<Collection> { Collection::one(Quantity::new(unit, amount)?) }
+
+    #[test]
+    fn measure_new_unit_and_value() -> PureExecutionResult<()>
+    {
+        let units = units()?;
+
+        let lots = new_unit(&units.lot, &Collection::one(3)?)?;
+        assert_eq!(quantity_of(&units.lot, Value::from(3))?, lots);
+        assert_eq!(Type::Unit { measure: units.lot.measure(), unit: units.lot.id() }, lots.pure_type());
+        assert_eq!(Collection::one(3)?, get_unit_value(&lots)?);
+
+        let bad = new_unit(&units.lot, &Collection::one("3")?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::newUnit_Unit_1__Number_1__Any_1_: Unexpected value: expected Number[1] but got String[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = get_unit_value(&Collection::one(3)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::measure::getUnitValue_Any_1__Number_1_: Integer 3 is not a quantity",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn measure_convert() -> PureExecutionResult<()>
+    {
+        let units = units()?;
+
+        assert_eq!(quantity_of(&units.share, Value::from(300))?, convert(&quantity_of(&units.lot, Value::from(3))?, &units.share)?);
+        assert_eq!(quantity_of(&units.lot, Value::decimal("2.5")?)?, convert(&quantity_of(&units.share, Value::from(250))?, &units.lot)?);
+
+        let bad = convert(&quantity_of(&units.usd, Value::from(1))?, &units.gbp);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::convert_Any_1__Unit_1__Any_1_: Incompatible units: Currency~USD cannot be converted to Currency~GBP",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn measure_arithmetic() -> PureExecutionResult<()>
+    {
+        let units = units()?;
+        let lots = quantity_of(&units.lot, Value::from(3))?;
+        let shares = quantity_of(&units.share, Value::from(50))?;
+
+        assert_eq!(quantity_of(&units.share, Value::from(350))?, mplus(&shares, &lots)?);
+        assert_eq!(quantity_of(&units.lot, Value::decimal("3.5")?)?, mplus(&lots, &shares)?);
+        assert_eq!(quantity_of(&units.share, Value::from(-250))?, mminus(&shares, &lots)?);
+        assert_eq!(quantity_of(&units.lot, Value::from(6))?, mtimes(&lots, &Collection::one(2)?)?);
+        assert_eq!(quantity_of(&units.lot, Value::from(1.5))?, mdivide(&lots, &Collection::one(2)?)?);
+        assert_eq!(quantity_of(&units.lot, Value::decimal("1.5")?)?, mdivide(&lots, &Collection::one(Value::decimal("2")?)?)?);
+
+        let bad = mplus(&lots, &quantity_of(&units.usd, Value::from(1))?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::plus_Any_1__Any_1__Any_1_: Incompatible units: Currency~USD cannot be converted to Quantity~Lot",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = mminus(&quantity_of(&units.usd, Value::from(1))?, &quantity_of(&units.gbp, Value::from(1))?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::minus_Any_1__Any_1__Any_1_: Incompatible units: Currency~GBP cannot be converted to Currency~USD",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = mplus(&lots, &Collection::one(1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::plus_Any_1__Any_1__Any_1_: Integer 1 is not a quantity",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = mdivide(&lots, &Collection::one(0.0)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::measure::divide_Any_1__Number_1__Any_1_: division by zero",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
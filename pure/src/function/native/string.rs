@@ -0,0 +1,419 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use pure_macros::pure_native;
+
+use crate::function::registry::NativeRegistry;
+use crate::function::*;
+
+#[pure_native("meta::pure::functions::string::length(String[1]):Integer[1]")]
+fn length(string: String) -> PureExecutionResult<Collection> { Collection::one(string.chars().count()) }
+
+#[pure_native("meta::pure::functions::string::toUpper(String[1]):String[1]")]
+fn to_upper(string: String) -> PureExecutionResult<Collection> { Collection::one(string.to_uppercase()) }
+
+#[pure_native("meta::pure::functions::string::toLower(String[1]):String[1]")]
+fn to_lower(string: String) -> PureExecutionResult<Collection> { Collection::one(string.to_lowercase()) }
+
+#[pure_native("meta::pure::functions::string::trim(String[1]):String[1]")]
+fn trim(string: String) -> PureExecutionResult<Collection> { Collection::one(string.trim()) }
+
+#[pure_native("meta::pure::functions::string::substring(String[1], Integer[1]):String[1]")]
+fn substring_from(string: String, begin: i64) -> PureExecutionResult<Collection>
+{
+    let begin = char_index(FUNC, 1, begin, &string)?;
+    Collection::one(string.chars().skip(begin).collect::<String>())
+}
+
+#[pure_native("meta::pure::functions::string::substring(String[1], Integer[1], Integer[1]):String[1]")]
+fn substring(string: String, begin: i64, end: i64) -> PureExecutionResult<Collection>
+{
+    let begin = char_index(FUNC, 1, begin, &string)?;
+    let end = char_index(FUNC, 2, end, &string)?;
+    if end < begin
+    {
+        let cause = format!("end index {end} is before begin index {begin}");
+        return Err(PureExecutionError::IllegalArgument { func: FUNC.to_string(), arg: 2, cause });
+    }
+    Collection::one(string.chars().skip(begin).take(end - begin).collect::<String>())
+}
+
+#[pure_native("meta::pure::functions::string::indexOf(String[1], String[1]):Integer[1]")]
+fn index_of(string: String, to_find: String) -> PureExecutionResult<Collection> { Collection::one(find_from(&string, &to_find, 0)) }
+
+#[pure_native("meta::pure::functions::string::indexOf(String[1], String[1], Integer[1]):Integer[1]")]
+fn index_of_from(string: String, to_find: String, from: i64) -> PureExecutionResult<Collection>
+{
+    let from = char_index(FUNC, 2, from, &string)?;
+    Collection::one(find_from(&string, &to_find, from))
+}
+
+#[pure_native("meta::pure::functions::string::contains(String[1], String[1]):Boolean[1]")]
+fn contains(string: String, to_find: String) -> PureExecutionResult<Collection> { Collection::one(string.contains(&to_find)) }
+
+#[pure_native("meta::pure::functions::string::startsWith(String[1], String[1]):Boolean[1]")]
+fn starts_with(string: String, prefix: String) -> PureExecutionResult<Collection> { Collection::one(string.starts_with(&prefix)) }
+
+#[pure_native("meta::pure::functions::string::endsWith(String[1], String[1]):Boolean[1]")]
+fn ends_with(string: String, suffix: String) -> PureExecutionResult<Collection> { Collection::one(string.ends_with(&suffix)) }
+
+#[pure_native("meta::pure::functions::string::joinStrings(String[*]):String[1]")]
+fn join_strings(strings: Vec<String>) -> PureExecutionResult<Collection> { Collection::one(strings.concat()) }
+
+#[pure_native("meta::pure::functions::string::joinStrings(String[*], String[1]):String[1]")]
+fn join_strings_with(strings: Vec<String>, separator: String) -> PureExecutionResult<Collection> { Collection::one(strings.join(&separator)) }
+
+#[pure_native("meta::pure::functions::string::plus(String[*]):String[1]")]
+fn splus(strings: Vec<String>) -> PureExecutionResult<Collection> { Collection::one(strings.concat()) }
+
+/// Splits a string into tokens.  As in Legend Pure every character of the delimiter is a separator in its own right and empty tokens
+/// are dropped.
+#[pure_native("meta::pure::functions::string::split(String[1], String[1]):String[*]")]
+fn split(string: String, delimiter: String) -> PureExecutionResult<Collection>
+{
+    string
+        .split(|c| delimiter.contains(c))
+        .filter(|token| !token.is_empty())
+        .try_fold(CollectionBuilder::new(Type::String, ZERO_MANY), |builder, token| builder.push(token))?
+        .build()
+}
+
+/// Writes any value as a string, as Pure does, so a Float always has a decimal point or exponent.
+#[pure_native("meta::pure::functions::string::toString(Any[1]):String[1]")]
+fn to_string(value: &Value) -> PureExecutionResult<Collection> { Collection::one(value.to_pure_string()) }
+
+/// Parses an optionally signed decimal integer, as Java's `Long.parseLong` does.
+#[pure_native("meta::pure::functions::string::parseInteger(String[1]):Integer[1]")]
+fn parse_integer(text: String) -> PureExecutionResult<Collection>
+{
+    match text.parse::<i64>()
+    {
+        Ok(integer) => Collection::one(integer),
+        Err(_) => Err(PureExecutionError::IllegalValue { pure_type: Type::Integer, value: text }),
+    }
+}
+
+/// Parses a float written as Java's `Double.parseDouble` accepts it, including `NaN` and `Infinity`, ignoring surrounding
+/// whitespace and an `f` or `d` suffix.
+#[pure_native("meta::pure::functions::string::parseFloat(String[1]):Float[1]")]
+fn parse_float(text: String) -> PureExecutionResult<Collection>
+{
+    let trimmed = text.trim();
+    let unsigned = trimmed.strip_prefix(['+', '-']).unwrap_or(trimmed);
+    let float = match unsigned
+    {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(if trimmed.starts_with('-') { f64::NEG_INFINITY } else { f64::INFINITY }),
+        _ =>
+        {
+            let digits = trimmed.strip_suffix(['f', 'F', 'd', 'D']).unwrap_or(trimmed);
+            let valid = digits.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+            digits.parse::<f64>().ok().filter(|_| valid)
+        }
+    };
+    match float
+    {
+        Some(float) => Collection::one(float),
+        None => Err(PureExecutionError::IllegalValue { pure_type: Type::Float, value: text }),
+    }
+}
+
+/// Parses a decimal preserving its scale, optionally written as a Pure literal with a `D` suffix.
+#[pure_native("meta::pure::functions::string::parseDecimal(String[1]):Decimal[1]")]
+fn parse_decimal(text: String) -> PureExecutionResult<Collection> { Ok(Value::decimal(&text)?.to_collection()) }
+
+/// Parses `true` or `false` ignoring case.
+#[pure_native("meta::pure::functions::string::parseBoolean(String[1]):Boolean[1]")]
+fn parse_boolean(text: String) -> PureExecutionResult<Collection>
+{
+    match text.to_lowercase().as_str()
+    {
+        "true" => Collection::one(true),
+        "false" => Collection::one(false),
+        _ => Err(PureExecutionError::IllegalValue { pure_type: Type::Boolean, value: text }),
+    }
+}
+
+/// Parses a date written as a Pure date literal, with or without the leading `%`.
+#[pure_native("meta::pure::functions::string::parseDate(String[1]):Date[1]")]
+fn parse_date(text: String) -> PureExecutionResult<Collection> { Ok(Value::date(&text)?.to_collection()) }
+
+/// Adds the string natives to a registry.
+pub(crate) fn register(registry: &mut NativeRegistry) -> PureExecutionResult<()>
+{
+    register_length(registry)?;
+    register_to_upper(registry)?;
+    register_to_lower(registry)?;
+    register_trim(registry)?;
+    register_substring_from(registry)?;
+    register_substring(registry)?;
+    register_index_of(registry)?;
+    register_index_of_from(registry)?;
+    register_contains(registry)?;
+    register_starts_with(registry)?;
+    register_ends_with(registry)?;
+    register_join_strings(registry)?;
+    register_join_strings_with(registry)?;
+    register_splus(registry)?;
+    register_split(registry)?;
+    register_to_string(registry)?;
+    register_parse_integer(registry)?;
+    register_parse_float(registry)?;
+    register_parse_decimal(registry)?;
+    register_parse_boolean(registry)?;
+    register_parse_date(registry)?;
+    Ok(())
+}
+
+/// Converts a Pure (character based) index into a string to a `usize`, validating that it lies within the string.
+fn char_index(func: &str, arg: usize, index: i64, string: &str) -> PureExecutionResult<usize>
+{
+    let length = string.chars().count();
+    match usize::try_from(index)
+    {
+        Ok(idx) if idx <= length => Ok(idx),
+        _ =>
+        {
+            let cause = format!("index {index} is out of range for a String of length {length}");
+            Err(PureExecutionError::IllegalArgument { func: func.to_string(), arg, cause })
+        }
+    }
+}
+
+/// Returns the character index of the first occurrence of `to_find` at or after the character index `from`, or -1 if there is none.
+fn find_from(string: &str, to_find: &str, from: usize) -> i64
+{
+    let offset = string.char_indices().nth(from).map_or(string.len(), |(offset, _)| offset);
+    string[offset..].find(to_find).map_or(-1, |found| (from + string[offset..offset + found].chars().count()) as i64)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::fixtures::strings;
+
+    #[test]
+    fn string_length() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(0)?, length(&Collection::one("")?)?);
+        assert_eq!(Collection::one(5)?, length(&Collection::one("hello")?)?);
+        assert_eq!(Collection::one(4)?, length(&Collection::one("£€$¢")?)?);
+
+        let bad = length(&Collection::one(1)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::string::length_String_1__Integer_1_: Unexpected value: expected String[1] but got Integer[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_case_and_trim() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one("HELLO WORLD")?, to_upper(&Collection::one("Hello World")?)?);
+        assert_eq!(Collection::one("hello world")?, to_lower(&Collection::one("Hello World")?)?);
+        assert_eq!(Collection::one("Hello World")?, trim(&Collection::one(" \t Hello World \n")?)?);
+        assert_eq!(Collection::one("")?, trim(&Collection::one("   ")?)?);
+
+        let bad = trim(&Collection::zero(Type::String));
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::string::trim_String_1__String_1_: Unexpected value: expected String[1] but got String[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_substring() -> PureExecutionResult<()>
+    {
+        let hello = Collection::one("hello")?;
+        assert_eq!(Collection::one("llo")?, substring_from(&hello, &Collection::one(2)?)?);
+        assert_eq!(Collection::one("")?, substring_from(&hello, &Collection::one(5)?)?);
+        assert_eq!(Collection::one("ell")?, substring(&hello, &Collection::one(1)?, &Collection::one(4)?)?);
+        assert_eq!(Collection::one("")?, substring(&hello, &Collection::one(2)?, &Collection::one(2)?)?);
+        assert_eq!(Collection::one("€$")?, substring(&Collection::one("£€$¢")?, &Collection::one(1)?, &Collection::one(3)?)?);
+
+        let bad = substring_from(&hello, &Collection::one(6)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::string::substring_String_1__Integer_1__String_1_: index 6 is out of range for a String of length 5",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = substring(&hello, &Collection::one(-1)?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::string::substring_String_1__Integer_1__Integer_1__String_1_: index -1 is out of range for a String of length 5",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = substring(&hello, &Collection::one(3)?, &Collection::one(2)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 2 of meta::pure::functions::string::substring_String_1__Integer_1__Integer_1__String_1_: end index 2 is before begin index 3",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_index_of() -> PureExecutionResult<()>
+    {
+        let hello = Collection::one("hello")?;
+        assert_eq!(Collection::one(2)?, index_of(&hello, &Collection::one("l")?)?);
+        assert_eq!(Collection::one(-1)?, index_of(&hello, &Collection::one("z")?)?);
+        assert_eq!(Collection::one(0)?, index_of(&hello, &Collection::one("")?)?);
+        assert_eq!(Collection::one(2)?, index_of(&Collection::one("£€$¢")?, &Collection::one("$")?)?);
+
+        assert_eq!(Collection::one(3)?, index_of_from(&hello, &Collection::one("l")?, &Collection::one(3)?)?);
+        assert_eq!(Collection::one(-1)?, index_of_from(&hello, &Collection::one("h")?, &Collection::one(1)?)?);
+        assert_eq!(Collection::one(3)?, index_of_from(&Collection::one("£€$¢")?, &Collection::one("¢")?, &Collection::one(1)?)?);
+
+        let bad = index_of_from(&hello, &Collection::one("l")?, &Collection::one(9)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 2 of meta::pure::functions::string::indexOf_String_1__String_1__Integer_1__Integer_1_: index 9 is out of range for a String of length 5",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_matching() -> PureExecutionResult<()>
+    {
+        let hello = Collection::one("hello")?;
+        assert_eq!(Collection::one(true)?, contains(&hello, &Collection::one("ell")?)?);
+        assert_eq!(Collection::one(false)?, contains(&hello, &Collection::one("elk")?)?);
+        assert_eq!(Collection::one(true)?, starts_with(&hello, &Collection::one("he")?)?);
+        assert_eq!(Collection::one(false)?, starts_with(&hello, &Collection::one("lo")?)?);
+        assert_eq!(Collection::one(true)?, ends_with(&hello, &Collection::one("lo")?)?);
+        assert_eq!(Collection::one(false)?, ends_with(&hello, &Collection::one("he")?)?);
+
+        let bad = contains(&hello, &Collection::one(true)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 1 of meta::pure::functions::string::contains_String_1__String_1__Boolean_1_: Unexpected value: expected String[1] but got Boolean[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_join_and_plus() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one("")?, join_strings(&strings(&[])?)?);
+        assert_eq!(Collection::one("abc")?, join_strings(&strings(&["a", "b", "c"])?)?);
+        assert_eq!(Collection::one("")?, join_strings_with(&strings(&[])?, &Collection::one(", ")?)?);
+        assert_eq!(Collection::one("a")?, join_strings_with(&strings(&["a"])?, &Collection::one(", ")?)?);
+        assert_eq!(Collection::one("a, b, c")?, join_strings_with(&strings(&["a", "b", "c"])?, &Collection::one(", ")?)?);
+
+        assert_eq!(Collection::one("")?, splus(&strings(&[])?)?);
+        assert_eq!(Collection::one("hello world")?, splus(&strings(&["hello", " ", "world"])?)?);
+
+        let bad = splus(&CollectionBuilder::new(Type::Integer, ZERO_MANY).build()?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::string::plus_String_MANY__String_1_: Unexpected value: expected String[*] but got Integer[*]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_split() -> PureExecutionResult<()>
+    {
+        assert_eq!(strings(&["a", "b", "c"])?, split(&Collection::one("a,b,c")?, &Collection::one(",")?)?);
+        assert_eq!(strings(&["a", "b", "c"])?, split(&Collection::one(",a,,b;c;")?, &Collection::one(",;")?)?);
+        assert_eq!(strings(&["abc"])?, split(&Collection::one("abc")?, &Collection::one(",")?)?);
+        assert_eq!(strings(&[])?, split(&Collection::one("")?, &Collection::one(",")?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_to_string() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one("42")?, to_string(&Collection::one(42)?)?);
+        assert_eq!(Collection::one("42.0")?, to_string(&Collection::one(42.0)?)?);
+        assert_eq!(Collection::one("1.50")?, to_string(&Collection::one(Value::decimal("1.50")?)?)?);
+        assert_eq!(Collection::one("false")?, to_string(&Collection::one(false)?)?);
+        assert_eq!(Collection::one("abc")?, to_string(&Collection::one("abc")?)?);
+        assert_eq!(Collection::one("2022-03-01T10:15:00+0000")?, to_string(&Value::date("%2022-03-01T10:15:00+0000")?.to_collection())?);
+
+        let bad = to_string(&ZERO_NIL);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::string::toString_Any_1__String_1_: Unexpected value: expected Any[1] but got Nil[0]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_parse_numbers() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(42)?, parse_integer(&Collection::one("42")?)?);
+        assert_eq!(Collection::one(-7)?, parse_integer(&Collection::one("-7")?)?);
+        assert_eq!(Collection::one(7)?, parse_integer(&Collection::one("+7")?)?);
+        for bad_text in ["", " 1", "1.0", "9223372036854775808", "abc"]
+        {
+            let bad = parse_integer(&Collection::one(bad_text)?);
+            assert!(bad.is_err());
+            assert_eq!(format!("Illegal value for type Integer: {bad_text}"), bad.err().unwrap().to_string());
+        }
+
+        assert_eq!(Collection::one(1.5)?, parse_float(&Collection::one("1.5")?)?);
+        assert_eq!(Collection::one(-1500.0)?, parse_float(&Collection::one(" -1.5e3 ")?)?);
+        assert_eq!(Collection::one(2.0)?, parse_float(&Collection::one("2d")?)?);
+        assert_eq!(Collection::one(f64::NEG_INFINITY)?, parse_float(&Collection::one("-Infinity")?)?);
+        assert!(matches!(parse_float(&Collection::one("NaN")?)?.contents, CollectionContents::One(Value::Float(f)) if f.is_nan()));
+        for bad_text in ["", "inf", "nan", "1.5.2", "abc"]
+        {
+            let bad = parse_float(&Collection::one(bad_text)?);
+            assert!(bad.is_err());
+            assert_eq!(format!("Illegal value for type Float: {bad_text}"), bad.err().unwrap().to_string());
+        }
+
+        assert_eq!(Collection::one(Value::decimal("1.50")?)?, parse_decimal(&Collection::one("1.50")?)?);
+        assert_eq!(Collection::one(Value::decimal("-3")?)?, parse_decimal(&Collection::one("-3D")?)?);
+        let bad = parse_decimal(&Collection::one("1,5")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Decimal: 1,5", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn string_parse_boolean_and_date() -> PureExecutionResult<()>
+    {
+        assert_eq!(Collection::one(true)?, parse_boolean(&Collection::one("true")?)?);
+        assert_eq!(Collection::one(false)?, parse_boolean(&Collection::one("FALSE")?)?);
+        let bad = parse_boolean(&Collection::one("yes")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Boolean: yes", bad.err().unwrap().to_string());
+
+        assert_eq!(Value::date("%2022-03-01")?.to_collection(), parse_date(&Collection::one("2022-03-01")?)?);
+        assert_eq!(Value::date("%2022-03-01T10:15:00+0000")?.to_collection(), parse_date(&Collection::one("%2022-03-01T11:15:00+0100")?)?);
+        let bad = parse_date(&Collection::one("2022-13-01")?);
+        assert!(bad.is_err());
+        assert_eq!("Illegal value for type Date: 2022-13-01", bad.err().unwrap().to_string());
+
+        let bad = parse_date(&Collection::one(20220301)?);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Invalid argument passed to arg 0 of meta::pure::functions::string::parseDate_String_1__Date_1_: Unexpected value: expected String[1] but got Integer[1]",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::sync::Arc;
+
+use crate::generic_type::{check_multiplicity, FunctionType, TypeBindings, Variance};
+use crate::*;
+
+/// A function that may be one of several with the same name, such as a native or a user defined function.
+pub trait Overload
+{
+    fn id(&self) -> &str;
+
+    fn signature(&self) -> &FunctionType;
+}
+
+impl<O: Overload> Overload for Arc<O>
+{
+    fn id(&self) -> &str { (**self).id() }
+
+    fn signature(&self) -> &FunctionType { (**self).signature() }
+}
+
+/// Chooses the overload of the named function to call with arguments of the given types and multiplicities.  An overload matches
+/// when each argument type can be assigned to its parameter type and each argument multiplicity is subsumed by its parameter
+/// multiplicity, binding any type and multiplicity parameters as it does so.  Of the matching overloads the one chosen is that
+/// whose parameters could all be passed to every other match, so that `plus(Integer[*])` is chosen over `plus(Number[*])` for
+/// Integers.  It is an error if no overload matches or if there is no single most specific match.
+pub fn resolve<'a, O>(name: &str, candidates: &'a [O], arguments: &[(Type, Multiplicity)]) -> PureExecutionResult<&'a O>
+where
+    O: Overload,
+{
+    if candidates.is_empty()
+    {
+        return Err(PureExecutionError::UnknownElement { name: name.to_string() });
+    }
+
+    let arguments_signature: Vec<(GenericType, GenericMultiplicity)> =
+        arguments.iter().map(|(ty, mult)| (GenericType::from(*ty), (*mult).into())).collect();
+    let matches: Vec<&O> = candidates.iter().filter(|candidate| accepts(candidate.signature(), &arguments_signature)).collect();
+
+    let most_specific: Vec<&O> = matches
+        .iter()
+        .filter(|candidate| {
+            matches.iter().all(|other| {
+                std::ptr::eq(*candidate, other)
+                    || (accepts(other.signature(), &candidate.signature().parameters)
+                        && !accepts(candidate.signature(), &other.signature().parameters))
+            })
+        })
+        .copied()
+        .collect();
+
+    let described = |overloads: &mut dyn Iterator<Item = &O>| overloads.map(|o| o.id()).collect::<Vec<_>>().join(", ");
+    let arguments = arguments.iter().map(|(ty, mult)| format!("{ty}{mult}")).collect::<Vec<_>>().join(", ");
+    match (matches.len(), most_specific.as_slice())
+    {
+        (_, [chosen]) => Ok(chosen),
+        (0, _) => Err(PureExecutionError::NoMatchingFunction { name: name.to_string(), arguments, candidates: described(&mut candidates.iter()) }),
+        _ => Err(PureExecutionError::AmbiguousFunction { name: name.to_string(), arguments, candidates: described(&mut matches.into_iter()) }),
+    }
+}
+
+/// Whether a function can be called with arguments of the given types and multiplicities.
+fn accepts(signature: &FunctionType, arguments: &[(GenericType, GenericMultiplicity)]) -> bool
+{
+    let mut bindings = TypeBindings::default();
+    signature.parameters.len() == arguments.len()
+        && signature.parameters.iter().zip(arguments).all(|((ty, mult), (arg_ty, arg_mult))| {
+            ty.is_assignable_from_binding(arg_ty, &mut bindings) && check_multiplicity(mult, arg_mult, Variance::Covariant, &mut bindings)
+        })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::registry::NativeRegistry;
+
+    struct UserFunction
+    {
+        id: &'static str,
+        signature: FunctionType,
+    }
+
+    impl Overload for UserFunction
+    {
+        fn id(&self) -> &str { self.id }
+
+        fn signature(&self) -> &FunctionType { &self.signature }
+    }
+
+    fn user_function(id: &'static str, signature: &str) -> UserFunction { UserFunction { id, signature: signature.parse().unwrap() } }
+
+    #[test]
+    fn overload_most_specific_is_chosen() -> PureExecutionResult<()>
+    {
+        let registry = NativeRegistry::with_natives()?;
+        let plus = |arguments: &[(Type, Multiplicity)]| {
+            registry.resolve("meta::pure::functions::math::plus", arguments).map(|native| native.id().to_string())
+        };
+
+        assert_eq!("meta::pure::functions::math::plus_Integer_MANY__Integer_1_", plus(&[(Type::Integer, Multiplicity::from(2))])?);
+        assert_eq!("meta::pure::functions::math::plus_Float_MANY__Float_1_", plus(&[(Type::Float, PURE_ONE)])?);
+        assert_eq!("meta::pure::functions::math::plus_Decimal_MANY__Decimal_1_", plus(&[(Type::Decimal, ZERO_MANY)])?);
+        assert_eq!("meta::pure::functions::math::plus_Number_MANY__Number_1_", plus(&[(Type::Number, ZERO_MANY)])?);
+
+        let less_than = registry.resolve("meta::pure::functions::boolean::lessThan", &[(Type::Integer, PURE_ONE), (Type::Float, ZERO_ONE)])?;
+        assert_eq!("meta::pure::functions::boolean::lessThan_Number_$0_1$__Number_$0_1$__Boolean_1_", less_than.id());
+
+        // A generic overload matches anything of the right multiplicity
+        let first = registry.resolve("meta::pure::functions::collection::first", &[(Type::String, ZERO_MANY)])?;
+        assert_eq!("meta::pure::functions::collection::first_T_MANY__T_$0_1$_", first.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn overload_concrete_is_preferred_to_generic() -> PureExecutionResult<()>
+    {
+        let candidates = [
+            user_function("test::describe_T_m__String_1_", "{T[m]->String[1]}"),
+            user_function("test::describe_Number_1__String_1_", "{Number[1]->String[1]}"),
+            user_function("test::describe_Integer_$0_1$__String_1_", "{Integer[0..1]->String[1]}"),
+        ];
+
+        assert_eq!("test::describe_Number_1__String_1_", resolve("test::describe", &candidates, &[(Type::Float, PURE_ONE)])?.id);
+        assert_eq!("test::describe_Integer_$0_1$__String_1_", resolve("test::describe", &candidates, &[(Type::Integer, PURE_ZERO)])?.id);
+        assert_eq!("test::describe_T_m__String_1_", resolve("test::describe", &candidates, &[(Type::String, ZERO_MANY)])?.id);
+
+        // Integer[1] matches both Number[1] and Integer[0..1] but neither is more specific than the other
+        let bad = resolve("test::describe", &candidates, &[(Type::Integer, PURE_ONE)]);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Ambiguous function: test::describe(Integer[1]) matches all of test::describe_T_m__String_1_, test::describe_Number_1__String_1_, test::describe_Integer_$0_1$__String_1_",
+            bad.err().unwrap().to_string()
+        );
+
+        // T[m] and Any[*] accept exactly the same arguments
+        let equivalent = [
+            user_function("test::describe_T_m__String_1_", "{T[m]->String[1]}"),
+            user_function("test::describe_Any_MANY__String_1_", "{Any[*]->String[1]}"),
+        ];
+        let bad = resolve("test::describe", &equivalent, &[(Type::String, ZERO_MANY)]);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Ambiguous function: test::describe(String[*]) matches all of test::describe_T_m__String_1_, test::describe_Any_MANY__String_1_",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn overload_errors_list_candidates() -> PureExecutionResult<()>
+    {
+        let registry = NativeRegistry::with_natives()?;
+
+        let bad = registry.resolve("meta::pure::functions::math::abs", &[(Type::String, PURE_ONE)]);
+        assert!(bad.is_err());
+        assert_eq!(
+            "No matching function: meta::pure::functions::math::abs(String[1]) does not match any of meta::pure::functions::math::abs_Integer_1__Integer_1_, meta::pure::functions::math::abs_Float_1__Float_1_, meta::pure::functions::math::abs_Number_1__Number_1_",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = registry.resolve("meta::pure::functions::math::abs", &[(Type::Integer, ZERO_MANY)]);
+        assert!(bad.is_err());
+
+        let bad = registry.resolve("meta::pure::functions::math::abs", &[(Type::Integer, PURE_ONE), (Type::Integer, PURE_ONE)]);
+        assert!(bad.is_err());
+
+        let bad = registry.resolve("meta::pure::functions::math::plus", &[(Type::Nil, PURE_ZERO)]);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Ambiguous function: meta::pure::functions::math::plus(Nil[0]) matches all of meta::pure::functions::math::plus_Integer_MANY__Integer_1_, meta::pure::functions::math::plus_Float_MANY__Float_1_, meta::pure::functions::math::plus_Number_MANY__Number_1_, meta::pure::functions::math::plus_Decimal_MANY__Decimal_1_",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = registry.resolve("meta::pure::functions::math::unknown", &[]);
+        assert!(bad.is_err());
+        assert_eq!("UnknownElement: meta::pure::functions::math::unknown", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+}
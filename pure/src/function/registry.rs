@@ -0,0 +1,226 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::function::native;
+use crate::function::overload::{resolve, Overload};
+use crate::generic_type::FunctionType;
+use crate::*;
+
+type NativeBody = dyn Fn(&[&Collection]) -> PureExecutionResult<Collection> + Send + Sync;
+
+/// A native function that can be found by its id at runtime.  The id is the function's package path followed by its Pure
+/// signature, such as `meta::pure::functions::boolean::and_Boolean_1__Boolean_1__Boolean_1_`, and the name is the id without the
+/// signature, such as `meta::pure::functions::boolean::and`, which is shared by the function's overloads.
+pub struct NativeFunction
+{
+    id: String,
+    name: String,
+    signature: FunctionType,
+    body: Arc<NativeBody>,
+}
+
+impl NativeFunction
+{
+    pub fn id(&self) -> &str { &self.id }
+
+    pub fn name(&self) -> &str { &self.name }
+
+    pub fn signature(&self) -> &FunctionType { &self.signature }
+
+    pub fn parameters(&self) -> &[(GenericType, GenericMultiplicity)] { &self.signature.parameters }
+
+    pub fn returns(&self) -> &(GenericType, GenericMultiplicity) { &self.signature.returns }
+
+    /// Calls the native.  The number of arguments is checked here, whereas their types and multiplicities are checked by the
+    /// native itself.  The arguments are already evaluated so, called this way, `and` and `or` do not short-circuit.
+    pub fn invoke(&self, arguments: &[&Collection]) -> PureExecutionResult<Collection>
+    {
+        if arguments.len() != self.signature.parameters.len()
+        {
+            return Err(PureExecutionError::WrongArgumentCount {
+                func: self.id.clone(),
+                expected: self.signature.parameters.len(),
+                got: arguments.len(),
+            });
+        }
+        (self.body)(arguments)
+    }
+}
+
+impl Overload for NativeFunction
+{
+    fn id(&self) -> &str { &self.id }
+
+    fn signature(&self) -> &FunctionType { &self.signature }
+}
+
+impl fmt::Debug for NativeFunction
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "NativeFunction({}: {})", self.id, GenericType::Function(Arc::new(self.signature.clone())))
+    }
+}
+
+/// The native functions available to an interpreter, found either by id or, for overload resolution, by name.
+#[derive(Default)]
+pub struct NativeRegistry
+{
+    functions: HashMap<String, Arc<NativeFunction>>,
+    overloads: HashMap<String, Vec<Arc<NativeFunction>>>,
+}
+
+impl NativeRegistry
+{
+    pub fn new() -> NativeRegistry { NativeRegistry::default() }
+
+    /// Creates a registry holding all the natives of `function::native` that take only collections as arguments.
+    pub fn with_natives() -> PureExecutionResult<NativeRegistry>
+    {
+        let mut registry = NativeRegistry::new();
+        native::register(&mut registry)?;
+        Ok(registry)
+    }
+
+    /// Registers a native given its id and its signature, written as a function type such as `{Boolean[1], Boolean[1]->Boolean[1]}`.
+    pub fn register<F>(&mut self, id: &str, signature: &str, body: F) -> PureExecutionResult<()>
+    where
+        F: Fn(&[&Collection]) -> PureExecutionResult<Collection> + Send + Sync + 'static,
+    {
+        if self.functions.contains_key(id)
+        {
+            return Err(PureExecutionError::DuplicateElementName { name: id.to_string() });
+        }
+
+        let native =
+            Arc::new(NativeFunction { id: id.to_string(), name: name_of(id).to_string(), signature: signature.parse()?, body: Arc::new(body) });
+        self.overloads.entry(native.name.clone()).or_default().push(native.clone());
+        self.functions.insert(native.id.clone(), native);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Arc<NativeFunction>> { self.functions.get(id) }
+
+    /// The natives with the given name in the order they were registered.
+    pub fn overloads(&self, name: &str) -> &[Arc<NativeFunction>] { self.overloads.get(name).map_or(&[], |natives| natives) }
+
+    /// Chooses the overload of the named native to call with arguments of the given types and multiplicities.
+    pub fn resolve(&self, name: &str, arguments: &[(Type, Multiplicity)]) -> PureExecutionResult<&Arc<NativeFunction>>
+    {
+        resolve(name, self.overloads(name), arguments)
+    }
+
+    pub fn len(&self) -> usize { self.functions.len() }
+
+    pub fn is_empty(&self) -> bool { self.functions.is_empty() }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<NativeFunction>> { self.functions.values() }
+}
+
+/// The name of a function is its id up to the first underscore of its last segment, as Pure function names do not contain
+/// underscores.
+fn name_of(id: &str) -> &str
+{
+    let start = id.rfind("::").map_or(0, |i| i + 2);
+    id[start..].find('_').map_or(id, |i| &id[..start + i])
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::function::native::not;
+
+    #[test]
+    fn registry_finds_natives_by_id() -> PureExecutionResult<()>
+    {
+        let registry = NativeRegistry::with_natives()?;
+
+        let and = registry.get("meta::pure::functions::boolean::and_Boolean_1__Boolean_1__Boolean_1_").unwrap();
+        assert_eq!("meta::pure::functions::boolean::and", and.name());
+        assert_eq!(&[(GenericType::from(Type::Boolean), PURE_ONE.into()), (GenericType::from(Type::Boolean), PURE_ONE.into())], and.parameters());
+        assert_eq!(&(GenericType::from(Type::Boolean), PURE_ONE.into()), and.returns());
+        assert_eq!(Collection::one(false)?, and.invoke(&[&Collection::one(true)?, &Collection::one(false)?])?);
+
+        let find = registry.get("meta::pure::functions::collection::find_T_MANY__Function_1__T_$0_1$_").unwrap();
+        assert_eq!("Function<{T[1]->Boolean[1]}>", find.parameters()[1].0.to_string());
+        assert_eq!(GenericMultiplicity::from(ZERO_ONE), find.returns().1);
+
+        let now = registry.get("meta::pure::functions::date::now__DateTime_1_").unwrap();
+        assert_eq!("meta::pure::functions::date::now", now.name());
+        assert!(now.parameters().is_empty());
+
+        assert!(registry.get("meta::pure::functions::boolean::and").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn registry_finds_overloads_by_name() -> PureExecutionResult<()>
+    {
+        let registry = NativeRegistry::with_natives()?;
+
+        let plus = registry.overloads("meta::pure::functions::math::plus").iter().map(|native| native.id()).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "meta::pure::functions::math::plus_Integer_MANY__Integer_1_",
+                "meta::pure::functions::math::plus_Float_MANY__Float_1_",
+                "meta::pure::functions::math::plus_Number_MANY__Number_1_",
+                "meta::pure::functions::math::plus_Decimal_MANY__Decimal_1_",
+            ],
+            plus
+        );
+        assert!(registry.overloads("meta::pure::functions::math::unknown").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn registry_ids_match_natives() -> PureExecutionResult<()>
+    {
+        let registry = NativeRegistry::with_natives()?;
+        assert!(!registry.is_empty());
+        assert_eq!(registry.len(), registry.iter().count());
+
+        // Natives name themselves in their errors so an empty argument shows whether a native is registered under its own id
+        for native in registry.iter()
+        {
+            let arguments = vec![&ZERO_NIL; native.parameters().len()];
+            if let Err(e) = native.invoke(&arguments)
+            {
+                let message = e.to_string();
+                assert!(!message.contains("meta::pure::functions::") || message.contains(native.id()), "{}: {message}", native.id());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn registry_checks_registrations_and_arguments() -> PureExecutionResult<()>
+    {
+        let mut registry = NativeRegistry::new();
+        registry.register("meta::pure::functions::boolean::not_Boolean_1__Boolean_1_", "{Boolean[1]->Boolean[1]}", |args| not(args[0]))?;
+
+        let bad = registry.register("meta::pure::functions::boolean::not_Boolean_1__Boolean_1_", "{Boolean[1]->Boolean[1]}", |args| not(args[0]));
+        assert!(bad.is_err());
+        assert_eq!("DuplicateElementName: meta::pure::functions::boolean::not_Boolean_1__Boolean_1_", bad.err().unwrap().to_string());
+
+        let bad = registry.register("meta::pure::functions::boolean::not_Boolean_0_1__Boolean_1_", "{Boolean[0..1]->Boolean}", |args| not(args[0]));
+        assert!(bad.is_err());
+        assert_eq!("Unparseable type {Boolean[0..1]->Boolean}: expected '[' at position 23", bad.err().unwrap().to_string());
+        assert_eq!(1, registry.len());
+
+        let bad = registry.get("meta::pure::functions::boolean::not_Boolean_1__Boolean_1_").unwrap().invoke(&[]);
+        assert!(bad.is_err());
+        assert_eq!(
+            "Wrong number of arguments: meta::pure::functions::boolean::not_Boolean_1__Boolean_1_ expects 1 arguments but got 0",
+            bad.err().unwrap().to_string()
+        );
+
+        Ok(())
+    }
+}
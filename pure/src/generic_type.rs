@@ -0,0 +1,775 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::multiplicity::GenericMultiplicity;
+use crate::pure_type::PRIMITIVES;
+use crate::*;
+
+/// How a type argument affects the assignability of a generic type.  A covariant argument allows a more specific type to be
+/// assigned (`List<Integer>` to `List<Number>`), a contravariant one a more general type and an invariant one only the same type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variance
+{
+    Covariant,
+    Contravariant,
+    Invariant,
+}
+
+impl Variance
+{
+    /// The variance of a position nested inside a position of this variance.
+    fn then(self, inner: Variance) -> Variance
+    {
+        match (self, inner)
+        {
+            (Variance::Invariant, _) | (_, Variance::Invariant) => Variance::Invariant,
+            (Variance::Covariant, v) => v,
+            (Variance::Contravariant, Variance::Covariant) => Variance::Contravariant,
+            (Variance::Contravariant, Variance::Contravariant) => Variance::Covariant,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TypeParameter
+{
+    pub name: &'static str,
+    pub variance: Variance,
+}
+
+/// A type as it is written in a Pure signature: a raw `Type` with its type arguments, a type parameter such as `T`, or a function
+/// type such as `{String[1]->Boolean[1]}`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GenericType
+{
+    Concrete
+    {
+        raw: Type,
+        type_arguments: Arc<[GenericType]>,
+    },
+    Parameter(Arc<str>),
+    Function(Arc<FunctionType>),
+}
+
+/// The parameters and return of a function, each with its type and multiplicity.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FunctionType
+{
+    pub parameters: Vec<(GenericType, GenericMultiplicity)>,
+    pub returns: (GenericType, GenericMultiplicity),
+}
+
+impl GenericType
+{
+    /// Creates a generic type checking that the number of type arguments matches the type parameters of the raw type.  A raw type
+    /// may also be given without any arguments.
+    pub fn new(raw: Type, type_arguments: Vec<GenericType>) -> PureExecutionResult<GenericType>
+    {
+        let expected = raw.type_parameters().len();
+        if !type_arguments.is_empty() && type_arguments.len() != expected
+        {
+            return Err(PureExecutionError::WrongTypeArguments { pure_type: raw, expected, got: type_arguments.len() });
+        }
+        Ok(GenericType::Concrete { raw, type_arguments: type_arguments.into() })
+    }
+
+    pub fn parameter(name: &str) -> GenericType { GenericType::Parameter(Arc::from(name)) }
+
+    /// Creates the type `Function<{...}>` of a function with the given parameters and return.
+    pub fn function<P, R>(parameters: Vec<(GenericType, P)>, returns: (GenericType, R)) -> GenericType
+    where
+        P: Into<GenericMultiplicity>,
+        R: Into<GenericMultiplicity>,
+    {
+        let parameters = parameters.into_iter().map(|(ty, mult)| (ty, mult.into())).collect();
+        let returns = (returns.0, returns.1.into());
+        let function_type = GenericType::Function(Arc::new(FunctionType { parameters, returns }));
+        GenericType::Concrete { raw: Type::Function, type_arguments: Arc::from([function_type]) }
+    }
+
+    /// The raw type, or `None` for a type parameter or a bare function type.
+    pub fn raw_type(&self) -> Option<Type>
+    {
+        match self
+        {
+            GenericType::Concrete { raw, .. } => Some(*raw),
+            _ => None,
+        }
+    }
+
+    pub fn type_arguments(&self) -> &[GenericType]
+    {
+        match self
+        {
+            GenericType::Concrete { type_arguments, .. } => type_arguments,
+            _ => &[],
+        }
+    }
+
+    pub fn is_assignable_from(&self, other: &GenericType) -> bool { self.is_assignable_from_binding(other, &mut TypeBindings::default()) }
+
+    /// Checks assignability binding the type parameters of this type to the types they must take for the assignment to succeed.
+    /// Parameters that are already bound are widened to a common type where necessary, so that `T` is bound to `Number` when it
+    /// must accept both an `Integer` and a `Float`.
+    pub fn is_assignable_from_binding(&self, other: &GenericType, bindings: &mut TypeBindings) -> bool
+    {
+        check(self, other, Variance::Covariant, bindings)
+    }
+
+    /// The most specific type that both types can be assigned to: one of the types if the other can be assigned to it, otherwise the
+    /// common supertype of their raw types.
+    pub fn common_supertype(&self, other: &GenericType) -> GenericType
+    {
+        if self.is_assignable_from(other)
+        {
+            self.clone()
+        }
+        else if other.is_assignable_from(self)
+        {
+            other.clone()
+        }
+        else
+        {
+            common_supertype(self, other).unwrap_or_else(|| GenericType::from(Type::Any))
+        }
+    }
+
+    /// Replaces the bound type parameters within this type with their bindings.
+    pub fn resolve(&self, bindings: &TypeBindings) -> GenericType
+    {
+        match self
+        {
+            GenericType::Parameter(name) => bindings.get(name).cloned().unwrap_or_else(|| self.clone()),
+            GenericType::Concrete { raw, type_arguments } =>
+            {
+                GenericType::Concrete { raw: *raw, type_arguments: type_arguments.iter().map(|arg| arg.resolve(bindings)).collect() }
+            }
+            GenericType::Function(function) => GenericType::Function(Arc::new(FunctionType {
+                parameters: function.parameters.iter().map(|(ty, mult)| (ty.resolve(bindings), mult.resolve(bindings))).collect(),
+                returns: (function.returns.0.resolve(bindings), function.returns.1.resolve(bindings)),
+            })),
+        }
+    }
+
+    /// Writes this type using the given function to name its raw types.
+    pub fn to_string_with(&self, name: &dyn Fn(&Type) -> String) -> String
+    {
+        let list = |types: &mut dyn Iterator<Item = String>| types.collect::<Vec<_>>().join(", ");
+
+        match self
+        {
+            GenericType::Concrete { raw, type_arguments } if type_arguments.is_empty() => name(raw),
+            GenericType::Concrete { raw, type_arguments } =>
+            {
+                format!("{}<{}>", name(raw), list(&mut type_arguments.iter().map(|arg| arg.to_string_with(name))))
+            }
+            GenericType::Parameter(param) => param.to_string(),
+            GenericType::Function(function) =>
+            {
+                let parameters = list(&mut function.parameters.iter().map(|(ty, mult)| format!("{}{mult}", ty.to_string_with(name))));
+                format!("{{{parameters}->{}{}}}", function.returns.0.to_string_with(name), function.returns.1)
+            }
+        }
+    }
+}
+
+impl From<Type> for GenericType
+{
+    fn from(raw: Type) -> Self { GenericType::Concrete { raw, type_arguments: Arc::from([]) } }
+}
+
+/// Parses a type as it is displayed, such as `Map<String, List<T>>` or `{T[1], Integer[*]->Boolean[0..1]}`.  A single capital
+/// letter names a type parameter and any other name must be a built-in type.  A bare function type is parsed as a
+/// `GenericType::Function`.
+impl FromStr for GenericType
+{
+    type Err = PureExecutionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err>
+    {
+        let mut parser = TypeParser { text, position: 0 };
+        let generic_type = parser.generic_type()?;
+        parser.end()?;
+        Ok(generic_type)
+    }
+}
+
+/// Parses a function type written as `{T[1], Integer[*]->Boolean[0..1]}`.
+impl FromStr for FunctionType
+{
+    type Err = PureExecutionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err>
+    {
+        let mut parser = TypeParser { text, position: 0 };
+        let function_type = parser.function_type()?;
+        parser.end()?;
+        Ok(function_type)
+    }
+}
+
+struct TypeParser<'a>
+{
+    text: &'a str,
+    position: usize,
+}
+
+impl TypeParser<'_>
+{
+    fn generic_type(&mut self) -> PureExecutionResult<GenericType>
+    {
+        if self.peek() == Some('{')
+        {
+            return Ok(GenericType::Function(Arc::new(self.function_type()?)));
+        }
+
+        self.skip_whitespace();
+        let start = self.position;
+        let name = self.identifier()?;
+        let Some(raw) = built_in_type(name)
+        else
+        {
+            if is_type_parameter(name)
+            {
+                return Ok(GenericType::parameter(name));
+            }
+            let problem = format!("unknown type {name}");
+            self.position = start;
+            return Err(self.error(&problem));
+        };
+
+        let mut type_arguments = vec![];
+        if self.accept("<")
+        {
+            type_arguments.push(self.generic_type()?);
+            while self.accept(",")
+            {
+                type_arguments.push(self.generic_type()?);
+            }
+            self.expect(">")?;
+        }
+        GenericType::new(raw, type_arguments)
+    }
+
+    fn function_type(&mut self) -> PureExecutionResult<FunctionType>
+    {
+        self.expect("{")?;
+        let mut parameters = vec![];
+        if !self.accept("->")
+        {
+            parameters.push((self.generic_type()?, self.multiplicity()?));
+            while self.accept(",")
+            {
+                parameters.push((self.generic_type()?, self.multiplicity()?));
+            }
+            self.expect("->")?;
+        }
+        let returns = (self.generic_type()?, self.multiplicity()?);
+        self.expect("}")?;
+        Ok(FunctionType { parameters, returns })
+    }
+
+    fn multiplicity(&mut self) -> PureExecutionResult<GenericMultiplicity>
+    {
+        self.expect("[")?;
+        let multiplicity = if self.accept("*")
+        {
+            GenericMultiplicity::from(ZERO_MANY)
+        }
+        else if self.peek().is_some_and(|c| c.is_ascii_digit())
+        {
+            let lower_bound = self.bound()?;
+            let upper_bound = if !self.accept("..")
+            {
+                Some(lower_bound)
+            }
+            else if self.accept("*")
+            {
+                None
+            }
+            else
+            {
+                Some(self.bound()?)
+            };
+            if upper_bound.is_some_and(|upper| upper < lower_bound)
+            {
+                return Err(self.error("upper bound is less than lower bound"));
+            }
+            GenericMultiplicity::from(Multiplicity { lower_bound, upper_bound })
+        }
+        else
+        {
+            GenericMultiplicity::parameter(self.identifier()?)
+        };
+        self.expect("]")?;
+        Ok(multiplicity)
+    }
+
+    fn bound(&mut self) -> PureExecutionResult<i64>
+    {
+        self.skip_whitespace();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        digits.parse().map_err(|_| self.error("expected a number"))
+    }
+
+    fn identifier(&mut self) -> PureExecutionResult<&str>
+    {
+        self.skip_whitespace();
+        let start = self.position;
+        if !self.rest().starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        {
+            return Err(self.error("expected a name"));
+        }
+        self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        Ok(&self.text[start..self.position])
+    }
+
+    fn take_while(&mut self, predicate: fn(char) -> bool) -> &str
+    {
+        let start = self.position;
+        let length = self.rest().find(|c| !predicate(c)).unwrap_or(self.rest().len());
+        self.position += length;
+        &self.text[start..self.position]
+    }
+
+    fn accept(&mut self, token: &str) -> bool
+    {
+        self.skip_whitespace();
+        let accepted = self.rest().starts_with(token);
+        if accepted
+        {
+            self.position += token.len();
+        }
+        accepted
+    }
+
+    fn expect(&mut self, token: &str) -> PureExecutionResult<()>
+    {
+        if self.accept(token)
+        {
+            Ok(())
+        }
+        else
+        {
+            Err(self.error(&format!("expected '{token}'")))
+        }
+    }
+
+    fn end(&mut self) -> PureExecutionResult<()>
+    {
+        self.skip_whitespace();
+        if self.rest().is_empty()
+        {
+            Ok(())
+        }
+        else
+        {
+            Err(self.error("unexpected text"))
+        }
+    }
+
+    fn peek(&mut self) -> Option<char>
+    {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) { self.position = self.text.len() - self.rest().trim_start().len(); }
+
+    fn rest(&self) -> &str { &self.text[self.position..] }
+
+    fn error(&self, problem: &str) -> PureExecutionError
+    {
+        PureExecutionError::UnparseableType { text: self.text.to_string(), position: self.position, problem: problem.to_string() }
+    }
+}
+
+fn built_in_type(name: &str) -> Option<Type>
+{
+    [Type::Nil, Type::Any, Type::List, Type::Pair, Type::Map, Type::Function].into_iter().chain(PRIMITIVES).find(|t| t.to_string() == name)
+}
+
+/// Type parameters are named, as in Pure's own signatures, by a single capital letter.
+fn is_type_parameter(name: &str) -> bool { name.len() == 1 && name.starts_with(|c: char| c.is_ascii_uppercase()) }
+
+impl fmt::Display for GenericType
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.to_string_with(&|t| t.to_string())) }
+}
+
+/// The types and multiplicities bound to type and multiplicity parameters while checking assignability.  A binding made only in
+/// covariant positions may later be widened, but one made in a contravariant or invariant position is fixed.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct TypeBindings
+{
+    bindings: Vec<(Arc<str>, GenericType, bool)>,
+    multiplicities: Vec<(Arc<str>, Multiplicity, bool)>,
+}
+
+impl TypeBindings
+{
+    pub fn get(&self, name: &str) -> Option<&GenericType> { self.bindings.iter().find(|(n, _, _)| &**n == name).map(|(_, ty, _)| ty) }
+
+    pub fn get_multiplicity(&self, name: &str) -> Option<Multiplicity>
+    {
+        self.multiplicities.iter().find(|(n, _, _)| &**n == name).map(|(_, mult, _)| *mult)
+    }
+
+    fn set_multiplicity(&mut self, name: &Arc<str>, mult: Multiplicity, widenable: bool)
+    {
+        match self.multiplicities.iter_mut().find(|(n, _, _)| n == name)
+        {
+            Some(binding) =>
+            {
+                binding.1 = mult;
+                binding.2 &= widenable;
+            }
+            None => self.multiplicities.push((name.clone(), mult, widenable)),
+        }
+    }
+
+    fn set(&mut self, name: &Arc<str>, ty: GenericType, widenable: bool)
+    {
+        match self.bindings.iter_mut().find(|(n, _, _)| n == name)
+        {
+            Some(binding) =>
+            {
+                binding.1 = ty;
+                binding.2 &= widenable;
+            }
+            None => self.bindings.push((name.clone(), ty, widenable)),
+        }
+    }
+
+    fn is_widenable(&self, name: &str) -> bool { self.bindings.iter().any(|(n, _, widenable)| &**n == name && *widenable) }
+}
+
+fn check(target: &GenericType, source: &GenericType, variance: Variance, bindings: &mut TypeBindings) -> bool
+{
+    match (target, source)
+    {
+        (GenericType::Parameter(name), _) => bind(name, source, variance, bindings),
+        (_, GenericType::Parameter(_)) => target == source || (variance == Variance::Covariant && target.raw_type() == Some(Type::Any)),
+        (
+            GenericType::Concrete { raw: target_raw, type_arguments: target_args },
+            GenericType::Concrete { raw: source_raw, type_arguments: source_args },
+        ) =>
+        {
+            let raw_matches = match variance
+            {
+                Variance::Covariant => target_raw.is_assignable_from(source_raw),
+                Variance::Contravariant => source_raw.is_assignable_from(target_raw),
+                Variance::Invariant => target_raw == source_raw,
+            };
+
+            // Arguments only need checking when both sides are the same parameterized type; otherwise the raw types decide
+            if !raw_matches || target_raw != source_raw || target_args.is_empty() || source_args.is_empty()
+            {
+                return raw_matches;
+            }
+
+            let parameters = target_raw.type_parameters();
+            parameters
+                .iter()
+                .zip(target_args.iter().zip(source_args.iter()))
+                .all(|(param, (t, s))| check(t, s, variance.then(param.variance), bindings))
+        }
+        (GenericType::Function(target_function), GenericType::Function(source_function)) =>
+        {
+            let parameters_match = target_function.parameters.len() == source_function.parameters.len()
+                && target_function.parameters.iter().zip(source_function.parameters.iter()).all(|((t, t_mult), (s, s_mult))| {
+                    let param_variance = variance.then(Variance::Contravariant);
+                    check(t, s, param_variance, bindings) && check_multiplicity(t_mult, s_mult, param_variance, bindings)
+                });
+
+            let (target_returns, target_mult) = &target_function.returns;
+            let (source_returns, source_mult) = &source_function.returns;
+            parameters_match
+                && check(target_returns, source_returns, variance, bindings)
+                && check_multiplicity(target_mult, source_mult, variance, bindings)
+        }
+        _ => false,
+    }
+}
+
+fn bind(name: &Arc<str>, source: &GenericType, variance: Variance, bindings: &mut TypeBindings) -> bool
+{
+    let covariant = variance == Variance::Covariant;
+    let Some(bound) = bindings.get(name).cloned()
+    else
+    {
+        bindings.set(name, source.clone(), covariant);
+        return true;
+    };
+
+    if check(&bound, source, variance, &mut bindings.clone())
+    {
+        if !covariant
+        {
+            bindings.set(name, bound, false);
+        }
+        true
+    }
+    else if covariant && bindings.is_widenable(name)
+    {
+        match common_supertype(&bound, source)
+        {
+            Some(common) =>
+            {
+                bindings.set(name, common, true);
+                true
+            }
+            None => false,
+        }
+    }
+    else
+    {
+        false
+    }
+}
+
+/// The most specific raw type that both types can be assigned to.
+fn common_supertype(left: &GenericType, right: &GenericType) -> Option<GenericType>
+{
+    let (Some(left_raw), Some(right_raw)) = (left.raw_type(), right.raw_type())
+    else
+    {
+        return None;
+    };
+    Some(GenericType::from(left_raw.common_supertype(&right_raw)))
+}
+
+pub(crate) fn check_multiplicity(target: &GenericMultiplicity, source: &GenericMultiplicity, variance: Variance, bindings: &mut TypeBindings)
+    -> bool
+{
+    match (target, source)
+    {
+        (GenericMultiplicity::Parameter(name), GenericMultiplicity::Concrete(source)) => bind_multiplicity(name, source, variance, bindings),
+        (GenericMultiplicity::Parameter(_), GenericMultiplicity::Parameter(_)) => target == source,
+        (GenericMultiplicity::Concrete(target), GenericMultiplicity::Parameter(_)) => variance == Variance::Covariant && *target == ZERO_MANY,
+        (GenericMultiplicity::Concrete(target), GenericMultiplicity::Concrete(source)) => match variance
+        {
+            Variance::Covariant => source.is_subsumed_by(target),
+            Variance::Contravariant => target.is_subsumed_by(source),
+            Variance::Invariant => target == source,
+        },
+    }
+}
+
+fn bind_multiplicity(name: &Arc<str>, source: &Multiplicity, variance: Variance, bindings: &mut TypeBindings) -> bool
+{
+    let covariant = variance == Variance::Covariant;
+    match bindings.multiplicities.iter().find(|(n, _, _)| n == name).map(|(_, mult, widenable)| (*mult, *widenable))
+    {
+        None => bindings.set_multiplicity(name, *source, covariant),
+        Some((bound, _)) if bound == *source => bindings.set_multiplicity(name, bound, covariant),
+        Some((bound, true)) if covariant => bindings.set_multiplicity(name, bound.union(source), true),
+        Some(_) => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn list(arg: GenericType) -> GenericType { GenericType::new(Type::List, vec![arg]).unwrap() }
+    fn pair(left: GenericType, right: GenericType) -> GenericType { GenericType::new(Type::Pair, vec![left, right]).unwrap() }
+    fn map(key: GenericType, value: GenericType) -> GenericType { GenericType::new(Type::Map, vec![key, value]).unwrap() }
+    fn of(raw: Type) -> GenericType { GenericType::from(raw) }
+
+    #[test]
+    fn generic_type_infers_result_multiplicities()
+    {
+        let m = || GenericMultiplicity::parameter("m");
+        let argument = |mult: Multiplicity| {
+            let mut bindings = TypeBindings::default();
+            assert!(check_multiplicity(&m(), &mult.into(), Variance::Covariant, &mut bindings));
+            bindings
+        };
+
+        // map<T,V|m>(value:T[m], func:{T[1]->V[1]}):V[m]
+        assert_eq!(GenericMultiplicity::from(ZERO_ONE), m().resolve(&argument(ZERO_ONE)));
+        assert_eq!(GenericMultiplicity::from(Multiplicity::from(2..)), m().resolve(&argument(Multiplicity::from(2..))));
+
+        // A multiplicity parameter seen more than once widens as a type parameter does
+        let mut bindings = argument(PURE_ONE);
+        assert!(check_multiplicity(&m(), &PURE_ZERO.into(), Variance::Covariant, &mut bindings));
+        assert_eq!(Some(ZERO_ONE), bindings.get_multiplicity("m"));
+    }
+
+    #[test]
+    fn generic_type_display()
+    {
+        assert_eq!("String", of(Type::String).to_string());
+        assert_eq!("List<String>", list(of(Type::String)).to_string());
+        assert_eq!("Pair<Integer, Date>", pair(of(Type::Integer), of(Type::Date)).to_string());
+        assert_eq!("Map<T, List<V>>", map(GenericType::parameter("T"), list(GenericType::parameter("V"))).to_string());
+        assert_eq!(
+            "Function<{String[1], Integer[*]->Boolean[0..1]}>",
+            GenericType::function(vec![(of(Type::String), PURE_ONE), (of(Type::Integer), ZERO_MANY)], (of(Type::Boolean), ZERO_ONE)).to_string()
+        );
+        assert_eq!("Function<{->T[1]}>", GenericType::function::<Multiplicity, _>(vec![], (GenericType::parameter("T"), PURE_ONE)).to_string());
+    }
+
+    #[test]
+    fn generic_type_parse() -> PureExecutionResult<()>
+    {
+        assert_eq!(of(Type::String), "String".parse()?);
+        assert_eq!(GenericType::parameter("T"), " T ".parse()?);
+        assert_eq!(map(GenericType::parameter("T"), list(GenericType::parameter("V"))), "Map<T, List<V>>".parse()?);
+        for text in ["Pair<Integer, Date>", "Function<{String[1], Integer[*]->Boolean[0..1]}>", "Function<{->T[1]}>", "{T[m], V[1..*]->List<V>[m]}"]
+        {
+            assert_eq!(text, text.parse::<GenericType>()?.to_string());
+        }
+
+        let function: FunctionType = "{Number[*], Boolean[1]->Number[0..1]}".parse()?;
+        assert_eq!(vec![(of(Type::Number), ZERO_MANY.into()), (of(Type::Boolean), PURE_ONE.into())], function.parameters);
+        assert_eq!((of(Type::Number), ZERO_ONE.into()), function.returns);
+        assert_eq!(GenericMultiplicity::parameter("m"), "{T[m]->T[m]}".parse::<FunctionType>()?.returns.1);
+
+        let bad = "Pair<String>".parse::<GenericType>();
+        assert!(bad.is_err());
+        assert_eq!("Wrong type arguments: Pair expects 2 type arguments but got 1", bad.err().unwrap().to_string());
+
+        let bad = "{String[1]->Boolean}".parse::<FunctionType>();
+        assert!(bad.is_err());
+        assert_eq!("Unparseable type {String[1]->Boolean}: expected '[' at position 19", bad.err().unwrap().to_string());
+
+        let bad = "{Integer[2..1]->Integer[1]}".parse::<FunctionType>();
+        assert!(bad.is_err());
+        assert_eq!(
+            "Unparseable type {Integer[2..1]->Integer[1]}: upper bound is less than lower bound at position 13",
+            bad.err().unwrap().to_string()
+        );
+
+        let bad = "List<String> x".parse::<GenericType>();
+        assert!(bad.is_err());
+        assert_eq!("Unparseable type List<String> x: unexpected text at position 13", bad.err().unwrap().to_string());
+
+        let bad = "{Strng[1]->Integer[1]}".parse::<FunctionType>();
+        assert!(bad.is_err());
+        assert_eq!("Unparseable type {Strng[1]->Integer[1]}: unknown type Strng at position 1", bad.err().unwrap().to_string());
+
+        let bad = "List<TT>".parse::<GenericType>();
+        assert!(bad.is_err());
+        assert_eq!("Unparseable type List<TT>: unknown type TT at position 5", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn generic_type_arguments_are_counted() -> PureExecutionResult<()>
+    {
+        let pair = GenericType::new(Type::Pair, vec![of(Type::String), of(Type::Integer)])?;
+        assert_eq!(Some(Type::Pair), pair.raw_type());
+        assert_eq!(&[of(Type::String), of(Type::Integer)], pair.type_arguments());
+        assert_eq!(of(Type::List), GenericType::new(Type::List, vec![])?);
+
+        let bad = GenericType::new(Type::Pair, vec![of(Type::String)]);
+        assert!(bad.is_err());
+        assert_eq!("Wrong type arguments: Pair expects 2 type arguments but got 1", bad.err().unwrap().to_string());
+
+        let bad = GenericType::new(Type::String, vec![of(Type::String)]);
+        assert!(bad.is_err());
+        assert_eq!("Wrong type arguments: String expects 0 type arguments but got 1", bad.err().unwrap().to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn generic_type_covariance()
+    {
+        assert!(list(of(Type::Number)).is_assignable_from(&list(of(Type::Integer))));
+        assert!(!list(of(Type::Integer)).is_assignable_from(&list(of(Type::Number))));
+        assert!(list(of(Type::Any)).is_assignable_from(&list(list(of(Type::String)))));
+        assert!(of(Type::Any).is_assignable_from(&list(of(Type::String))));
+        assert!(of(Type::List).is_assignable_from(&list(of(Type::String))));
+        assert!(list(of(Type::String)).is_assignable_from(&of(Type::Nil)));
+        assert!(!list(of(Type::String)).is_assignable_from(&pair(of(Type::String), of(Type::String))));
+        assert!(pair(of(Type::Number), of(Type::Date)).is_assignable_from(&pair(of(Type::Float), of(Type::StrictDate))));
+        assert!(!pair(of(Type::Number), of(Type::Date)).is_assignable_from(&pair(of(Type::Float), of(Type::String))));
+    }
+
+    #[test]
+    fn generic_type_common_supertype()
+    {
+        assert_eq!(list(of(Type::Number)), list(of(Type::Number)).common_supertype(&list(of(Type::Integer))));
+        assert_eq!(list(of(Type::Number)), of(Type::Nil).common_supertype(&list(of(Type::Number))));
+        assert_eq!(of(Type::Number), of(Type::Integer).common_supertype(&of(Type::Float)));
+        assert_eq!(of(Type::List), list(of(Type::Integer)).common_supertype(&list(of(Type::String))));
+    }
+
+    #[test]
+    fn generic_type_invariance()
+    {
+        assert!(map(of(Type::String), of(Type::Number)).is_assignable_from(&map(of(Type::String), of(Type::Integer))));
+        assert!(!map(of(Type::Any), of(Type::Number)).is_assignable_from(&map(of(Type::String), of(Type::Number))));
+    }
+
+    #[test]
+    fn generic_type_function_variance()
+    {
+        let predicate = |param: Type| GenericType::function(vec![(of(param), PURE_ONE)], (of(Type::Boolean), PURE_ONE));
+        assert!(predicate(Type::Integer).is_assignable_from(&predicate(Type::Number)));
+        assert!(!predicate(Type::Number).is_assignable_from(&predicate(Type::Integer)));
+
+        let producer = |returns: Type, mult: Multiplicity| GenericType::function::<Multiplicity, _>(vec![], (of(returns), mult));
+        assert!(producer(Type::Number, ZERO_MANY).is_assignable_from(&producer(Type::Integer, PURE_ONE)));
+        assert!(!producer(Type::Integer, ZERO_MANY).is_assignable_from(&producer(Type::Number, PURE_ONE)));
+        assert!(!producer(Type::Number, PURE_ONE).is_assignable_from(&producer(Type::Integer, ZERO_MANY)));
+
+        let consumer = |mult: Multiplicity| GenericType::function(vec![(of(Type::String), mult)], (of(Type::Boolean), PURE_ONE));
+        assert!(consumer(PURE_ONE).is_assignable_from(&consumer(ZERO_MANY)));
+        assert!(!consumer(ZERO_MANY).is_assignable_from(&consumer(PURE_ONE)));
+
+        assert!(!predicate(Type::Integer).is_assignable_from(&producer(Type::Boolean, PURE_ONE)));
+    }
+
+    #[test]
+    fn generic_type_parameters_are_bound()
+    {
+        let t = || GenericType::parameter("T");
+
+        let mut bindings = TypeBindings::default();
+        assert!(list(t()).is_assignable_from_binding(&list(of(Type::String)), &mut bindings));
+        assert_eq!(Some(&of(Type::String)), bindings.get("T"));
+        assert_eq!(pair(of(Type::String), of(Type::Integer)), pair(t(), of(Type::Integer)).resolve(&bindings));
+
+        let mut bindings = TypeBindings::default();
+        assert!(t().is_assignable_from_binding(&of(Type::Integer), &mut bindings));
+        assert!(t().is_assignable_from_binding(&of(Type::Float), &mut bindings));
+        assert_eq!(Some(&of(Type::Number)), bindings.get("T"));
+        assert!(t().is_assignable_from_binding(&of(Type::String), &mut bindings));
+        assert_eq!(Some(&of(Type::Any)), bindings.get("T"));
+
+        let mut bindings = TypeBindings::default();
+        let filter = GenericType::function(vec![(t(), PURE_ONE)], (of(Type::Boolean), PURE_ONE));
+        let is_even = GenericType::function(vec![(of(Type::Integer), PURE_ONE)], (of(Type::Boolean), PURE_ONE));
+        assert!(filter.is_assignable_from_binding(&is_even, &mut bindings));
+        assert_eq!(Some(&of(Type::Integer)), bindings.get("T"));
+
+        let mut bindings = TypeBindings::default();
+        let mapping = GenericType::function(vec![(t(), PURE_ONE)], (t(), PURE_ONE));
+        let to_string = GenericType::function(vec![(of(Type::Integer), PURE_ONE)], (of(Type::String), PURE_ONE));
+        assert!(!mapping.is_assignable_from_binding(&to_string, &mut bindings));
+
+        let mut bindings = TypeBindings::default();
+        let m = GenericMultiplicity::parameter("m");
+        let producer = GenericType::function(vec![(t(), m.clone())], (t(), m.clone()));
+        let first = GenericType::function(vec![(of(Type::String), ZERO_MANY)], (of(Type::String), ZERO_MANY));
+        assert!(producer.is_assignable_from_binding(&first, &mut bindings));
+        assert_eq!(Some(ZERO_MANY), bindings.get_multiplicity("m"));
+        assert_eq!(GenericType::function(vec![(of(Type::String), ZERO_MANY)], (of(Type::String), ZERO_MANY)), producer.resolve(&bindings));
+        let first = GenericType::function(vec![(of(Type::String), ZERO_MANY)], (of(Type::String), PURE_ONE));
+        assert!(!producer.is_assignable_from_binding(&first, &mut TypeBindings::default()));
+
+        assert!(t().is_assignable_from(&t()));
+        assert!(of(Type::Any).is_assignable_from(&t()));
+        assert!(!of(Type::String).is_assignable_from(&t()));
+    }
+}
@@ -1,11 +1,13 @@
 // Copyright 2022 Dave Wathen. All rights reserved.
 
-#![feature(const_type_id)]
+pub use bigdecimal::BigDecimal;
 
 pub use crate::data::Collection;
 pub use crate::data::Value;
 pub use crate::data::ZERO_NIL;
 pub use crate::error::PureExecutionError;
+pub use crate::generic_type::GenericType;
+pub use crate::multiplicity::GenericMultiplicity;
 pub use crate::multiplicity::Multiplicitied;
 pub use crate::multiplicity::Multiplicity;
 pub use crate::multiplicity::PURE_ONE;
@@ -18,6 +20,7 @@ pub use crate::pure_type::Typed;
 pub mod data;
 pub mod error;
 pub mod function;
+pub mod generic_type;
 #[allow(non_camel_case_types)]
 pub mod multiplicity;
 pub mod pure_type;
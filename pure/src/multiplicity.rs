@@ -1,7 +1,9 @@
 // Copyright 2022 Dave Wathen. All rights reserved.
 
+use crate::generic_type::TypeBindings;
 use crate::*;
 use std::fmt;
+use std::sync::Arc;
 
 macro_rules! pure_multiplicity {
     ([ $l:literal .. $u:literal ]) => {
@@ -35,7 +37,111 @@ pub trait Multiplicitied: pure_type::Typed
     fn multiplicity(&self) -> Multiplicity;
 }
 
-impl Multiplicity {}
+impl Multiplicity
+{
+    /// Returns true if a collection of the given size has this multiplicity.
+    pub fn contains(&self, size: i64) -> bool { size >= self.lower_bound && self.upper_bound.is_none_or(|upper| size <= upper) }
+
+    /// Returns true if every size allowed by this multiplicity is allowed by the other, so that `[1]` is subsumed by `[0..1]` and
+    /// `[*]`.
+    pub fn is_subsumed_by(&self, other: &Multiplicity) -> bool
+    {
+        other.lower_bound <= self.lower_bound && other.upper_bound.is_none_or(|upper| self.upper_bound.is_some_and(|own| own <= upper))
+    }
+
+    /// The smallest multiplicity allowing every size allowed by either, as needed for the result of an `if`.
+    pub fn union(&self, other: &Multiplicity) -> Multiplicity
+    {
+        let upper_bound = self.upper_bound.zip(other.upper_bound).map(|(l, r)| l.max(r));
+        Multiplicity { lower_bound: self.lower_bound.min(other.lower_bound), upper_bound }
+    }
+
+    /// The multiplicity allowing only the sizes allowed by both, or `None` if there are no such sizes.
+    pub fn intersection(&self, other: &Multiplicity) -> Option<Multiplicity>
+    {
+        let lower_bound = self.lower_bound.max(other.lower_bound);
+        let upper_bound = match (self.upper_bound, other.upper_bound)
+        {
+            (Some(l), Some(r)) => Some(l.min(r)),
+            (bound, None) | (None, bound) => bound,
+        };
+        upper_bound.is_none_or(|upper| upper >= lower_bound).then_some(Multiplicity { lower_bound, upper_bound })
+    }
+
+    /// The multiplicity of two collections concatenated.  An upper bound too large to represent is unbounded.
+    pub fn plus(&self, other: &Multiplicity) -> Multiplicity
+    {
+        let upper_bound = self.upper_bound.zip(other.upper_bound).and_then(|(l, r)| l.checked_add(r));
+        Multiplicity { lower_bound: self.lower_bound.saturating_add(other.lower_bound), upper_bound }
+    }
+
+    /// The multiplicity of the result of mapping a collection of this multiplicity with a function returning the other.  An upper
+    /// bound too large to represent is unbounded.
+    pub fn times(&self, other: &Multiplicity) -> Multiplicity
+    {
+        let upper_bound = match (self.upper_bound, other.upper_bound)
+        {
+            (Some(0), _) | (_, Some(0)) => Some(0),
+            (l, r) => l.zip(r).and_then(|(l, r)| l.checked_mul(r)),
+        };
+        Multiplicity { lower_bound: self.lower_bound.saturating_mul(other.lower_bound), upper_bound }
+    }
+
+    pub fn is_to_one(&self) -> bool { *self == PURE_ONE }
+    pub fn is_zero_one(&self) -> bool { *self == ZERO_ONE }
+    pub fn has_to_one_upper_bound(&self) -> bool { self.upper_bound == Some(1) }
+    pub fn is_many(&self) -> bool { self.upper_bound.is_none_or(|upper| upper > 1) }
+}
+
+/// A multiplicity as it is written in a Pure signature: either a concrete multiplicity or a multiplicity parameter such as `[m]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GenericMultiplicity
+{
+    Concrete(Multiplicity),
+    Parameter(Arc<str>),
+}
+
+impl GenericMultiplicity
+{
+    pub fn parameter(name: &str) -> GenericMultiplicity { GenericMultiplicity::Parameter(Arc::from(name)) }
+
+    /// The concrete multiplicity, or `None` for a parameter.
+    pub fn concrete(&self) -> Option<Multiplicity>
+    {
+        match self
+        {
+            GenericMultiplicity::Concrete(mult) => Some(*mult),
+            GenericMultiplicity::Parameter(_) => None,
+        }
+    }
+
+    /// Replaces a bound multiplicity parameter with its binding.
+    pub fn resolve(&self, bindings: &TypeBindings) -> GenericMultiplicity
+    {
+        match self
+        {
+            GenericMultiplicity::Parameter(name) => bindings.get_multiplicity(name).map_or_else(|| self.clone(), GenericMultiplicity::Concrete),
+            concrete => concrete.clone(),
+        }
+    }
+}
+
+impl From<Multiplicity> for GenericMultiplicity
+{
+    fn from(mult: Multiplicity) -> Self { GenericMultiplicity::Concrete(mult) }
+}
+
+impl fmt::Display for GenericMultiplicity
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            GenericMultiplicity::Concrete(mult) => write!(f, "{mult}"),
+            GenericMultiplicity::Parameter(name) => write!(f, "[{name}]"),
+        }
+    }
+}
 
 impl From<i64> for Multiplicity
 {
@@ -183,6 +289,86 @@ mod tests
         assert_eq!("[1]", format!("{}", m));
     }
 
+    #[test]
+    fn multiplicity_subsumption()
+    {
+        assert!(PURE_ONE.is_subsumed_by(&PURE_ONE));
+        assert!(PURE_ONE.is_subsumed_by(&ZERO_ONE));
+        assert!(PURE_ONE.is_subsumed_by(&ZERO_MANY));
+        assert!(PURE_ZERO.is_subsumed_by(&ZERO_ONE));
+        assert!(Multiplicity::from(2..=3).is_subsumed_by(&Multiplicity::from(1..)));
+        assert!(!ZERO_ONE.is_subsumed_by(&PURE_ONE));
+        assert!(!ZERO_MANY.is_subsumed_by(&Multiplicity::from(0..=100)));
+        assert!(!Multiplicity::from(1..).is_subsumed_by(&Multiplicity::from(2..)));
+
+        assert!(ZERO_ONE.contains(0));
+        assert!(ZERO_ONE.contains(1));
+        assert!(!ZERO_ONE.contains(2));
+        assert!(Multiplicity::from(2..).contains(1000));
+        assert!(!Multiplicity::from(2..).contains(1));
+    }
+
+    #[test]
+    fn multiplicity_union_and_intersection()
+    {
+        assert_eq!(ZERO_ONE, PURE_ONE.union(&PURE_ZERO));
+        assert_eq!(Multiplicity::from(1..=3), PURE_ONE.union(&Multiplicity::from(3)));
+        assert_eq!(ZERO_MANY, ZERO_ONE.union(&Multiplicity::from(1..)));
+
+        assert_eq!(Some(PURE_ONE), ZERO_ONE.intersection(&Multiplicity::from(1..)));
+        assert_eq!(Some(Multiplicity::from(2..=3)), Multiplicity::from(1..=3).intersection(&Multiplicity::from(2..)));
+        assert_eq!(Some(ZERO_MANY), ZERO_MANY.intersection(&ZERO_MANY));
+        assert_eq!(None, PURE_ZERO.intersection(&PURE_ONE));
+    }
+
+    #[test]
+    fn multiplicity_arithmetic()
+    {
+        assert_eq!(Multiplicity::from(2), PURE_ONE.plus(&PURE_ONE));
+        assert_eq!(Multiplicity::from(1..=2), PURE_ONE.plus(&ZERO_ONE));
+        assert_eq!(Multiplicity::from(1..), PURE_ONE.plus(&ZERO_MANY));
+        assert_eq!(ZERO_ONE, PURE_ZERO.plus(&ZERO_ONE));
+
+        assert_eq!(PURE_ONE, PURE_ONE.times(&PURE_ONE));
+        assert_eq!(Multiplicity::from(2..=6), Multiplicity::from(1..=2).times(&Multiplicity::from(2..=3)));
+        assert_eq!(ZERO_MANY, ZERO_MANY.times(&PURE_ONE));
+        assert_eq!(Multiplicity::from(1..), Multiplicity::from(1..).times(&PURE_ONE));
+        assert_eq!(PURE_ZERO, PURE_ZERO.times(&ZERO_MANY));
+        assert_eq!(PURE_ZERO, ZERO_MANY.times(&PURE_ZERO));
+
+        // Bounds too large to represent are unbounded
+        assert_eq!(Multiplicity::from(1..), Multiplicity::from(0..=i64::MAX).plus(&PURE_ONE));
+        assert_eq!(Multiplicity::from(2..), Multiplicity::from(1..=i64::MAX).times(&Multiplicity::from(2)));
+        assert_eq!(Multiplicity::from(i64::MAX..), Multiplicity::from(i64::MAX).plus(&PURE_ONE));
+    }
+
+    #[test]
+    fn multiplicity_checks()
+    {
+        assert!(PURE_ONE.is_to_one());
+        assert!(!ZERO_ONE.is_to_one());
+        assert!(ZERO_ONE.is_zero_one());
+        assert!(ZERO_ONE.has_to_one_upper_bound());
+        assert!(PURE_ONE.has_to_one_upper_bound());
+        assert!(!ZERO_MANY.has_to_one_upper_bound());
+
+        assert!(ZERO_MANY.is_many());
+        assert!(Multiplicity::from(2).is_many());
+        assert!(!ZERO_ONE.is_many());
+        assert!(!PURE_ZERO.is_many());
+    }
+
+    #[test]
+    fn generic_multiplicities()
+    {
+        let m = GenericMultiplicity::parameter("m");
+        assert_eq!("[m]", m.to_string());
+        assert_eq!(None, m.concrete());
+        assert_eq!("[0..1]", GenericMultiplicity::from(ZERO_ONE).to_string());
+        assert_eq!(Some(ZERO_ONE), GenericMultiplicity::from(ZERO_ONE).concrete());
+        assert_eq!(m, m.resolve(&TypeBindings::default()));
+    }
+
     #[test]
     fn specific_macro_constructs()
     {
@@ -3,6 +3,9 @@
 use std::any::TypeId;
 use std::fmt;
 
+use crate::data::model::ElementId;
+use crate::generic_type::{GenericType, TypeParameter, Variance};
+
 pub const PRIMITIVES: [Type; 12] = [
     Type::String,
     Type::Binary,
@@ -41,27 +44,68 @@ pub enum Type
     StrictDate,
     DateTime,
     LatestDate,
-    // TODO Pure Enums
-    // TODO Pure Classes
-    // TODO Pure Measures
+
+    // Built-in generic classes
+    List,
+    Pair,
+    Map,
+    Function,
+
+    /// A user defined enumeration declared in a `Model`
+    Enum(ElementId),
+    /// A user defined class declared in a `Model`
+    Class(ElementId),
+    /// A user defined measure declared in a `Model`
+    Measure(ElementId),
+    /// A unit of a user defined measure, which specializes the measure
+    Unit
+    {
+        measure: ElementId,
+        unit: ElementId,
+    },
 }
 
 const REL_ANY: TypeRelation = TypeRelation::Type(Type::Any);
 
-pub const fn pure_type_of<T: 'static>() -> Type
+pub fn pure_type_of<T: 'static>() -> Type
 {
-    const ID_BOOL: TypeId = TypeId::of::<bool>();
-    const ID_I64: TypeId = TypeId::of::<i64>();
-    const ID_F64: TypeId = TypeId::of::<f64>();
-    const ID_STRING: TypeId = TypeId::of::<String>();
+    let id = TypeId::of::<T>();
 
-    match TypeId::of::<T>()
+    if id == TypeId::of::<bool>()
+    {
+        Type::Boolean
+    }
+    else if id == TypeId::of::<i64>()
+    {
+        Type::Integer
+    }
+    else if id == TypeId::of::<f64>()
+    {
+        Type::Float
+    }
+    else if id == TypeId::of::<bigdecimal::BigDecimal>()
+    {
+        Type::Decimal
+    }
+    else if id == TypeId::of::<crate::data::date::PureDate>()
+    {
+        Type::Date
+    }
+    else if id == TypeId::of::<crate::data::date::PureTime>()
+    {
+        Type::StrictTime
+    }
+    else if id == TypeId::of::<std::sync::Arc<[u8]>>()
     {
-        ID_BOOL => Type::Boolean,
-        ID_I64 => Type::Integer,
-        ID_F64 => Type::Float,
-        ID_STRING => Type::String,
-        _ => Type::Any,
+        Type::Binary
+    }
+    else if id == TypeId::of::<String>()
+    {
+        Type::String
+    }
+    else
+    {
+        Type::Any
     }
 }
 
@@ -70,9 +114,25 @@ impl Type
     pub fn is_primitive(&self) -> bool { PRIMITIVES.iter().any(|prim| prim == self) }
     pub fn is_number(&self) -> bool { NUMBERS.iter().any(|prim| prim == self) }
     pub fn is_date(&self) -> bool { DATES.iter().any(|prim| prim == self) }
-    pub fn is_class(&self) -> bool { false } // TODO
-    pub fn is_enum(&self) -> bool { false } // TODO
-    pub fn is_measure(&self) -> bool { false } // TODO
+    pub fn is_class(&self) -> bool { matches!(self, Type::Class(_) | Type::List | Type::Pair | Type::Map | Type::Function) }
+    pub fn is_enum(&self) -> bool { matches!(self, Type::Enum(_)) }
+    pub fn is_measure(&self) -> bool { matches!(self, Type::Measure(_) | Type::Unit { .. }) }
+
+    /// The type parameters of a generic type, which are empty for all other types.
+    pub fn type_parameters(&self) -> &'static [TypeParameter]
+    {
+        const T: TypeParameter = TypeParameter { name: "T", variance: Variance::Covariant };
+        const U: TypeParameter = TypeParameter { name: "U", variance: Variance::Covariant };
+        const V: TypeParameter = TypeParameter { name: "V", variance: Variance::Covariant };
+
+        match self
+        {
+            Type::List | Type::Function => &[T],
+            Type::Pair => &[U, V],
+            Type::Map => &[TypeParameter { name: "U", variance: Variance::Invariant }, V],
+            _ => &[],
+        }
+    }
 
     pub fn generalizations(&self) -> &[TypeRelation]
     {
@@ -80,9 +140,21 @@ impl Type
         {
             Type::Any => &[],
             Type::Nil => &[REL_ANY, TypeRelation::AllPrimitives, TypeRelation::AllClasses, TypeRelation::AllEnums, TypeRelation::AllMeasures],
-            Type::String | Type::Binary | Type::Boolean | Type::Number | Type::Date => &[REL_ANY],
+            Type::String
+            | Type::Binary
+            | Type::Boolean
+            | Type::Number
+            | Type::Date
+            | Type::List
+            | Type::Pair
+            | Type::Map
+            | Type::Function
+            | Type::Enum(_)
+            | Type::Class(_)
+            | Type::Measure(_) => &[REL_ANY],
             Type::Integer | Type::Float | Type::Decimal => &[REL_ANY, TypeRelation::Type(Type::Number)],
             Type::StrictTime | Type::StrictDate | Type::DateTime | Type::LatestDate => &[REL_ANY, TypeRelation::Type(Type::Date)],
+            Type::Unit { .. } => &[REL_ANY, TypeRelation::MeasureOfUnit],
         }
     }
 
@@ -154,6 +226,13 @@ impl Type
                         return true;
                     }
                 }
+                TypeRelation::MeasureOfUnit =>
+                {
+                    if matches!(other, Type::Unit { measure, .. } if *self == Type::Measure(*measure))
+                    {
+                        return true;
+                    }
+                }
                 TypeRelation::Type(t) =>
                 {
                     if t == self || self.is_generalization_of(t)
@@ -167,6 +246,55 @@ impl Type
     }
 
     pub fn is_specialization_of(&self, other: &Type) -> bool { other.is_generalization_of(self) }
+
+    /// The most specific type that values of both types can be assigned to, which is `Any` when they have nothing else in common.
+    pub fn common_supertype(&self, other: &Type) -> Type
+    {
+        if self.is_assignable_from(other)
+        {
+            return *self;
+        }
+        if other.is_assignable_from(self)
+        {
+            return *other;
+        }
+
+        let mut ancestors = vec![*self];
+        let mut idx = 0;
+        while idx < ancestors.len()
+        {
+            let ancestor = ancestors[idx];
+            for relation in ancestor.generalizations()
+            {
+                let general = match (relation, ancestor)
+                {
+                    (TypeRelation::Type(general), _) => *general,
+                    (TypeRelation::MeasureOfUnit, Type::Unit { measure, .. }) => Type::Measure(measure),
+                    _ => continue,
+                };
+                if !ancestors.contains(&general)
+                {
+                    ancestors.push(general);
+                }
+            }
+            idx += 1;
+        }
+
+        ancestors
+            .into_iter()
+            .filter(|ancestor| ancestor.is_assignable_from(other))
+            .reduce(|a, b| {
+                if a.is_assignable_from(&b)
+                {
+                    b
+                }
+                else
+                {
+                    a
+                }
+            })
+            .unwrap_or(Type::Any)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -176,17 +304,32 @@ pub enum TypeRelation
     AllClasses,
     AllEnums,
     AllMeasures,
+    /// The measure of a unit, which is the one recorded for the unit by the `Model` declaring it and carried by its `Type::Unit`
+    MeasureOfUnit,
     Type(Type),
 }
 
 impl fmt::Display for Type
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{self:?}") }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Type::Enum(id) => write!(f, "Enum{id}"),
+            Type::Class(id) => write!(f, "Class{id}"),
+            Type::Measure(id) => write!(f, "Measure{id}"),
+            Type::Unit { unit, .. } => write!(f, "Unit{unit}"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
 }
 
 pub trait Typed
 {
     fn pure_type(&self) -> Type;
+
+    /// The full type including any type arguments.
+    fn generic_type(&self) -> GenericType { GenericType::from(self.pure_type()) }
 }
 
 impl Typed for bool
@@ -210,8 +353,8 @@ mod tests
             {
                 if let TypeRelation::Type(gen_ty) = gen
                 {
-                    let reverse_exists = gen_ty.specializations().iter().any(|spc| *spc == TypeRelation::Type(*ty));
-                    let is_via_all_primitives = ty.is_primitive() && gen_ty.specializations().iter().any(|spc| *spc == TypeRelation::AllPrimitives);
+                    let reverse_exists = gen_ty.specializations().contains(&TypeRelation::Type(*ty));
+                    let is_via_all_primitives = ty.is_primitive() && gen_ty.specializations().contains(&TypeRelation::AllPrimitives);
                     assert!(
                         reverse_exists || is_via_all_primitives,
                         "For type {:?} the generalization {:?} missing in {:?} specializations: {:?}",
@@ -236,7 +379,7 @@ mod tests
             {
                 if let TypeRelation::Type(spc_ty) = spc
                 {
-                    let reverse_exists = spc_ty.generalizations().iter().any(|gen| *gen == TypeRelation::Type(*ty));
+                    let reverse_exists = spc_ty.generalizations().contains(&TypeRelation::Type(*ty));
                     assert!(
                         reverse_exists,
                         "For type {:?} the specialization {:?} is missing in {:?} generalizations: {:?}",
@@ -321,6 +464,62 @@ mod tests
         assert!(Type::LatestDate.is_specialization_of(&Type::Date));
     }
 
+    #[test]
+    fn enum_generalizations() -> crate::PureExecutionResult<()>
+    {
+        let mut model = crate::data::model::Model::new();
+        let colour = Type::Enum(model.add_enumeration("domain::Colour", &["RED", "GREEN"])?);
+        let size = Type::Enum(model.add_enumeration("domain::Size", &["SMALL", "LARGE"])?);
+
+        assert!(colour.is_enum());
+        assert!(!colour.is_primitive());
+        assert!(!Type::String.is_enum());
+
+        assert!(Type::Any.is_generalization_of(&colour));
+        assert!(colour.is_specialization_of(&Type::Any));
+        assert!(colour.is_generalization_of(&Type::Nil));
+        assert!(Type::Nil.is_specialization_of(&colour));
+
+        assert!(colour.is_assignable_from(&colour));
+        assert!(!colour.is_assignable_from(&size));
+        assert!(!colour.is_assignable_from(&Type::String));
+        assert!(!Type::String.is_assignable_from(&colour));
+
+        Ok(())
+    }
+
+    #[test]
+    fn class_generalizations() -> crate::PureExecutionResult<()>
+    {
+        let mut model = crate::data::model::Model::new();
+        let firm = Type::Class(model.add_class("domain::Firm", vec![])?);
+        let person = Type::Class(model.add_class("domain::Person", vec![])?);
+
+        assert!(firm.is_class());
+        assert!(!firm.is_enum());
+        assert!(!firm.is_primitive());
+
+        assert!(Type::Any.is_generalization_of(&firm));
+        assert!(firm.is_generalization_of(&Type::Nil));
+        assert!(Type::Nil.is_specialization_of(&firm));
+        assert!(!firm.is_assignable_from(&person));
+        assert!(!firm.is_assignable_from(&Type::Any));
+
+        Ok(())
+    }
+
+    #[test]
+    fn common_supertypes()
+    {
+        assert_eq!(Type::Integer, Type::Integer.common_supertype(&Type::Integer));
+        assert_eq!(Type::Number, Type::Integer.common_supertype(&Type::Float));
+        assert_eq!(Type::Number, Type::Decimal.common_supertype(&Type::Number));
+        assert_eq!(Type::Date, Type::StrictDate.common_supertype(&Type::LatestDate));
+        assert_eq!(Type::Any, Type::Integer.common_supertype(&Type::String));
+        assert_eq!(Type::String, Type::Nil.common_supertype(&Type::String));
+        assert_eq!(Type::Float, Type::Float.common_supertype(&Type::Nil));
+    }
+
     #[test]
     fn nil_specializations()
     {
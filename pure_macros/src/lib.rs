@@ -0,0 +1,204 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+//! Macros for declaring the natives of `legend_pure`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, FnArg, GenericArgument, Ident, ItemFn, LitStr, Pat, PathArguments, Type, TypeParamBound};
+
+use crate::signature::{Parameter, Signature};
+
+mod signature;
+
+/// Declares a native from its Pure signature, for example:
+///
+/// ```ignore
+/// #[pure_native("meta::pure::functions::binary::length(Binary[1]):Integer[1]")]
+/// fn binary_length(binary: Arc<[u8]>) -> PureExecutionResult<Collection> { Collection::one(binary.len()) }
+/// ```
+///
+/// The function is renamed to the native's mangled name, here `meta_pure_functions_binary_length_Binary_1__Integer_1_`, with a
+/// `FunctionArgument` for each parameter, and re-exported under its own name.  Each argument is extracted as the type of its
+/// parameter before the body runs: `&Value` for a `[1]` argument of any type, `Option<&Value>` for a `[0..1]` argument,
+/// `Vec<&Value>` for the values of any argument, `Vec<T>` for the values of an argument of the primitive type `T`, or otherwise
+/// the primitive value of a `[1]` argument.  A parameter of type `impl FunctionArgument` is passed to the body as it is, for natives
+/// such as `and` that only evaluate an argument when they need it.  The body may use `FUNC`, the native's id, in its errors.  A
+/// function named `register_` followed by the function's name is also declared to add the native to a `NativeRegistry`.
+///
+/// The signature's types must be built in or type parameters, which Pure names with single capital letters, so a misspelt type is
+/// an error rather than a type parameter.  The function itself cannot be generic since its arguments are extracted as Rust types
+/// chosen from the signature.
+#[proc_macro_attribute]
+pub fn pure_native(attr: TokenStream, item: TokenStream) -> TokenStream
+{
+    let signature = parse_macro_input!(attr as LitStr);
+    let function = parse_macro_input!(item as ItemFn);
+    expand(&signature, function).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn expand(text: &LitStr, function: ItemFn) -> syn::Result<TokenStream2>
+{
+    let signature = Signature::parse(&text.value()).map_err(|e| syn::Error::new(text.span(), e))?;
+
+    if !function.sig.generics.params.is_empty() || function.sig.generics.where_clause.is_some()
+    {
+        return Err(syn::Error::new_spanned(&function.sig.generics, "a native cannot be generic"));
+    }
+
+    let inputs = function
+        .sig
+        .inputs
+        .iter()
+        .map(|input| match input
+        {
+            FnArg::Typed(typed) => match &*typed.pat
+            {
+                Pat::Ident(pat) => Ok((pat.ident.clone(), (*typed.ty).clone())),
+                other => Err(syn::Error::new(other.span(), "a native's parameters must be named")),
+            },
+            FnArg::Receiver(receiver) => Err(syn::Error::new(receiver.span(), "a native cannot take self")),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    if inputs.len() != signature.parameters.len()
+    {
+        let message = format!("the Pure signature has {} parameters but the function has {}", signature.parameters.len(), inputs.len());
+        return Err(syn::Error::new(function.sig.inputs.span(), message));
+    }
+
+    let id = signature.id();
+    let function_type = signature.function_type();
+    let alias = &function.sig.ident;
+    let native = format_ident!("{}", signature.rust_name());
+    let register = format_ident!("register_{}", alias);
+    let attrs = &function.attrs;
+    let output = &function.sig.output;
+    let body = &function.block.stmts;
+
+    let names = inputs.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    let generics = (0..inputs.len()).map(|i| format_ident!("A{}", i)).collect::<Vec<_>>();
+    let extractions = inputs
+        .iter()
+        .zip(&signature.parameters)
+        .enumerate()
+        .map(|(arg, ((name, ty), parameter))| extraction(arg, name, ty, parameter))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let call = match inputs.len()
+    {
+        0 => quote! { |_| #native() },
+        n =>
+        {
+            let indices = 0..n;
+            quote! { |args| #native(#(args[#indices]),*) }
+        }
+    };
+
+    Ok(quote! {
+        pub use #native as #alias;
+
+        #(#attrs)*
+        #[allow(non_snake_case)]
+        pub fn #native<#(#generics),*>(#(#names: #generics),*) #output
+        where
+            #(#generics: crate::function::FunctionArgument),*
+        {
+            const FUNC: &str = #id;
+
+            #(#extractions)*
+            #(#body)*
+        }
+
+        pub fn #register(registry: &mut crate::function::registry::NativeRegistry) -> crate::PureExecutionResult<()>
+        {
+            registry.register(#id, #function_type, #call)
+        }
+    })
+}
+
+/// The statement extracting an argument as the type of the Rust parameter, checking that the type suits the Pure multiplicity, or
+/// nothing for an argument passed through as it is.
+fn extraction(arg: usize, name: &Ident, ty: &Type, parameter: &Parameter) -> syn::Result<TokenStream2>
+{
+    let pure_type = format_ident!("{}", parameter.raw_type());
+    let pure_type = quote! { crate::Type::#pure_type };
+    let multiplicity = parameter.multiplicity.as_str();
+    let written = ty.to_token_stream().to_string().replace(' ', "");
+    let wrong_multiplicity = || syn::Error::new(ty.span(), format!("{written} cannot hold an argument of multiplicity [{multiplicity}]"));
+
+    let extraction = match (outer_type(ty), inner_type(ty))
+    {
+        _ if is_function_argument(ty) => return Ok(quote! {}),
+        _ if matches!(ty, Type::ImplTrait(_)) =>
+        {
+            return Err(syn::Error::new(ty.span(), "a native's pass-through parameters must be impl FunctionArgument"))
+        }
+        (Some("Vec"), Some(inner)) if is_value_reference(inner) => quote! { #name.many_raw(FUNC, #arg, #pure_type)?.collect::<Vec<_>>() },
+        (Some("Vec"), Some(inner)) => quote! { #name.many::<#inner>(FUNC, #arg)?.collect::<crate::PureExecutionResult<Vec<#inner>>>()? },
+        (Some("Option"), Some(inner)) if is_value_reference(inner) && multiplicity == "0..1" =>
+        {
+            quote! { #name.zero_or_one_raw(FUNC, #arg, #pure_type)? }
+        }
+        (Some("Option"), _) => return Err(wrong_multiplicity()),
+        _ if multiplicity != "1" => return Err(wrong_multiplicity()),
+        _ if is_value_reference(ty) => quote! { #name.one_raw(FUNC, #arg, #pure_type)? },
+        _ => quote! { #name.one::<#ty>(FUNC, #arg)? },
+    };
+    Ok(quote! { let #name = #extraction; })
+}
+
+fn outer_type(ty: &Type) -> Option<&str>
+{
+    match ty
+    {
+        Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()).and_then(|name| match name.as_str()
+        {
+            "Vec" => Some("Vec"),
+            "Option" => Some("Option"),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn inner_type(ty: &Type) -> Option<&Type>
+{
+    let Type::Path(path) = ty
+    else
+    {
+        return None;
+    };
+    match &path.path.segments.last()?.arguments
+    {
+        PathArguments::AngleBracketed(arguments) => arguments.args.iter().find_map(|arg| match arg
+        {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn is_value_reference(ty: &Type) -> bool
+{
+    match ty
+    {
+        Type::Reference(reference) => matches!(&*reference.elem, Type::Path(path) if path.path.is_ident("Value")),
+        _ => false,
+    }
+}
+
+fn is_function_argument(ty: &Type) -> bool
+{
+    let Type::ImplTrait(impl_trait) = ty
+    else
+    {
+        return false;
+    };
+    let mut bounds = impl_trait.bounds.iter();
+    match (bounds.next(), bounds.next())
+    {
+        (Some(TypeParamBound::Trait(bound)), None) => bound.path.segments.last().is_some_and(|segment| segment.ident == "FunctionArgument"),
+        _ => false,
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+const BUILT_IN_TYPES: [&str; 18] = [
+    "Nil",
+    "Any",
+    "String",
+    "Binary",
+    "Boolean",
+    "Number",
+    "Integer",
+    "Float",
+    "Decimal",
+    "Date",
+    "StrictTime",
+    "StrictDate",
+    "DateTime",
+    "LatestDate",
+    "List",
+    "Pair",
+    "Map",
+    "Function",
+];
+
+/// A Pure function signature such as `meta::pure::functions::math::plus(Integer[*]):Integer[1]`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Signature
+{
+    pub package: String,
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub returns: Parameter,
+}
+
+/// A parameter or return of a signature as it is written, such as `Function<{T[1]->Boolean[1]}>` and `1`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Parameter
+{
+    pub generic_type: String,
+    pub multiplicity: String,
+}
+
+impl Signature
+{
+    pub fn parse(text: &str) -> Result<Signature, String>
+    {
+        let text = text.trim();
+        let open = text.find('(').ok_or("expected '(' after the function name")?;
+        let close = matching(text, open).ok_or("expected ')' after the parameters")?;
+        let returns = text[close + 1..].trim_start().strip_prefix(':').ok_or("expected ':' before the return type")?;
+
+        let (package, name) = text[..open].trim().rsplit_once("::").ok_or("expected a package before the function name")?;
+        if !is_identifier(name)
+        {
+            return Err(format!("'{name}' is not a function name"));
+        }
+
+        let parameters =
+            split_top_level(&text[open + 1..close]).into_iter().filter(|p| !p.is_empty()).map(Parameter::parse).collect::<Result<_, _>>()?;
+        Ok(Signature { package: package.to_string(), name: name.to_string(), parameters, returns: Parameter::parse(returns)? })
+    }
+
+    /// The id of the function as Legend writes it: `and_Boolean_1__Boolean_1__Boolean_1_` for `and(Boolean[1], Boolean[1]):Boolean[1]`.
+    pub fn id(&self) -> String
+    {
+        let parameters = self.parameters.iter().map(Parameter::mangled).collect::<Vec<_>>().join("__");
+        let separator = if parameters.is_empty() { "" } else { "_" };
+        format!("{}::{}{separator}{parameters}__{}_", self.package, self.name, self.returns.mangled())
+    }
+
+    /// The name of the Rust function implementing the native, which is its id with the package separators and `$`s removed.
+    pub fn rust_name(&self) -> String { self.id().replace("::", "_").replace('$', "") }
+
+    /// The signature as a function type such as `{Boolean[1], Boolean[1]->Boolean[1]}`.
+    pub fn function_type(&self) -> String
+    {
+        let parameters = self.parameters.iter().map(Parameter::to_string).collect::<Vec<_>>().join(", ");
+        format!("{{{parameters}->{}}}", self.returns)
+    }
+}
+
+impl Parameter
+{
+    fn parse(text: &str) -> Result<Parameter, String>
+    {
+        let text = text.trim();
+        let open = text.rfind('[').filter(|_| text.ends_with(']')).ok_or_else(|| format!("'{text}' has no multiplicity"))?;
+        let generic_type = text[..open].trim();
+        let multiplicity = text[open + 1..text.len() - 1].trim();
+        let raw = raw_name(generic_type);
+        if !is_identifier(raw)
+        {
+            return Err(format!("'{generic_type}' is not a type"));
+        }
+        if !BUILT_IN_TYPES.contains(&raw) && !is_type_parameter(raw)
+        {
+            return Err(format!("'{raw}' is neither a built-in type nor a type parameter"));
+        }
+        if !is_multiplicity(multiplicity)
+        {
+            return Err(format!("'{multiplicity}' is not a multiplicity"));
+        }
+        Ok(Parameter { generic_type: generic_type.to_string(), multiplicity: multiplicity.to_string() })
+    }
+
+    /// The built-in raw type of the parameter or `Any` for a type parameter.
+    pub fn raw_type(&self) -> &str
+    {
+        let raw = raw_name(&self.generic_type);
+        if BUILT_IN_TYPES.contains(&raw)
+        {
+            raw
+        }
+        else
+        {
+            "Any"
+        }
+    }
+
+    fn mangled(&self) -> String
+    {
+        let multiplicity = match self.multiplicity.split_once("..")
+        {
+            Some((lower, upper)) => format!("${lower}_{}$", if upper == "*" { "MANY" } else { upper }),
+            None if self.multiplicity == "*" => "MANY".to_string(),
+            None => self.multiplicity.clone(),
+        };
+        format!("{}_{multiplicity}", raw_name(&self.generic_type))
+    }
+}
+
+impl std::fmt::Display for Parameter
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}[{}]", self.generic_type, self.multiplicity) }
+}
+
+fn raw_name(generic_type: &str) -> &str { generic_type.split('<').next().unwrap_or_default().trim() }
+
+/// Pure names its type parameters with single capital letters, such as `T` and `V`.
+fn is_type_parameter(text: &str) -> bool { text.len() == 1 && text.starts_with(|c: char| c.is_ascii_uppercase()) }
+
+fn is_identifier(text: &str) -> bool
+{
+    text.starts_with(|c: char| c.is_ascii_alphabetic()) && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_multiplicity(text: &str) -> bool
+{
+    let is_bound = |bound: &str| !bound.is_empty() && bound.chars().all(|c| c.is_ascii_digit());
+    match text.split_once("..")
+    {
+        Some((lower, upper)) => is_bound(lower) && (upper == "*" || is_bound(upper)),
+        None => text == "*" || is_bound(text) || is_identifier(text),
+    }
+}
+
+/// The position of the bracket closing the one at `open`.
+fn matching(text: &str, open: usize) -> Option<usize>
+{
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip_while(|(i, _)| *i < open)
+    {
+        match c
+        {
+            '(' | '<' | '{' | '[' => depth += 1,
+            ')' | '>' | '}' | ']' if !text[..i].ends_with('-') => depth -= 1,
+            _ => (),
+        }
+        if depth == 0
+        {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Splits on the commas that are not nested within brackets.
+fn split_top_level(text: &str) -> Vec<&str>
+{
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices()
+    {
+        match c
+        {
+            '(' | '<' | '{' | '[' => depth += 1,
+            ')' | '>' | '}' | ']' if !text[..i].ends_with('-') => depth -= 1,
+            ',' if depth == 0 =>
+            {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn signature_ids()
+    {
+        let and = Signature::parse("meta::pure::functions::boolean::and(Boolean[1], Boolean[1]):Boolean[1]").unwrap();
+        assert_eq!("meta::pure::functions::boolean::and_Boolean_1__Boolean_1__Boolean_1_", and.id());
+        assert_eq!("meta_pure_functions_boolean_and_Boolean_1__Boolean_1__Boolean_1_", and.rust_name());
+        assert_eq!("{Boolean[1], Boolean[1]->Boolean[1]}", and.function_type());
+
+        let now = Signature::parse("meta::pure::functions::date::now():DateTime[1]").unwrap();
+        assert_eq!("meta::pure::functions::date::now__DateTime_1_", now.id());
+        assert_eq!("{->DateTime[1]}", now.function_type());
+
+        let less_than = Signature::parse("meta::pure::functions::boolean::lessThan(Number[0..1], Number[0..1]):Boolean[1]").unwrap();
+        assert_eq!("meta::pure::functions::boolean::lessThan_Number_$0_1$__Number_$0_1$__Boolean_1_", less_than.id());
+        assert_eq!("meta_pure_functions_boolean_lessThan_Number_0_1__Number_0_1__Boolean_1_", less_than.rust_name());
+
+        let fold = Signature::parse("meta::pure::functions::collection::fold(T[*], Function<{T[1], V[m]->V[m]}>[1], V[m]):V[m]").unwrap();
+        assert_eq!("meta::pure::functions::collection::fold_T_MANY__Function_1__V_m__V_m_", fold.id());
+        assert_eq!("{T[*], Function<{T[1], V[m]->V[m]}>[1], V[m]->V[m]}", fold.function_type());
+        assert_eq!("Function", fold.parameters[1].raw_type());
+        assert_eq!("Any", fold.parameters[2].raw_type());
+
+        let at_least_one = Signature::parse("test::first(String[1..*]):String[1]").unwrap();
+        assert_eq!("test::first_String_$1_MANY$__String_1_", at_least_one.id());
+    }
+
+    #[test]
+    fn signature_errors()
+    {
+        assert_eq!(Err("expected '(' after the function name".to_string()), Signature::parse("test::f"));
+        assert_eq!(Err("expected ':' before the return type".to_string()), Signature::parse("test::f(String[1])"));
+        assert_eq!(Err("expected a package before the function name".to_string()), Signature::parse("f(String[1]):String[1]"));
+        assert_eq!(Err("'String' has no multiplicity".to_string()), Signature::parse("test::f(String):String[1]"));
+        assert_eq!(Err("'1..' is not a multiplicity".to_string()), Signature::parse("test::f(String[1..]):String[1]"));
+        assert_eq!(Err("'<T>' is not a type".to_string()), Signature::parse("test::f(<T>[1]):String[1]"));
+        assert_eq!(Err("'Strng' is neither a built-in type nor a type parameter".to_string()), Signature::parse("test::f(Strng[1]):String[1]"));
+        assert_eq!(Err("'TT' is neither a built-in type nor a type parameter".to_string()), Signature::parse("test::f(String[1]):TT[1]"));
+    }
+}
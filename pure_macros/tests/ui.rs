@@ -0,0 +1,8 @@
+// Copyright 2022 Dave Wathen. All rights reserved.
+
+#[test]
+fn pure_native_errors()
+{
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
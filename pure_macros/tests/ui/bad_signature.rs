@@ -0,0 +1,6 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::string::length(String):Integer[1]")]
+fn length(string: String) -> PureExecutionResult<Collection> { Collection::one(string.len()) }
+
+fn main() {}
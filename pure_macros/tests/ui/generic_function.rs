@@ -0,0 +1,6 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::string::length(String[1]):Integer[1]")]
+fn length<S: AsRef<str>>(string: S) -> PureExecutionResult<Collection> { Collection::one(string.as_ref().len()) }
+
+fn main() {}
@@ -0,0 +1,6 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::string::indexOf(String[1], String[1]):Integer[1]")]
+fn index_of(string: String) -> PureExecutionResult<Collection> { Collection::one(string.len()) }
+
+fn main() {}
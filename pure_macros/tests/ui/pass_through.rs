@@ -0,0 +1,6 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::boolean::not(Boolean[1]):Boolean[1]")]
+fn not(value: impl std::fmt::Display) -> PureExecutionResult<Collection> { Collection::one(value.to_string().is_empty()) }
+
+fn main() {}
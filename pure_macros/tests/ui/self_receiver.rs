@@ -0,0 +1,11 @@
+use pure_macros::pure_native;
+
+struct Strings;
+
+impl Strings
+{
+    #[pure_native("meta::pure::functions::string::length(String[1]):Integer[1]")]
+    fn length(&self) -> PureExecutionResult<Collection> { Collection::one(0) }
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::string::length(String[1]):Integer[1]")]
+fn length(_: String) -> PureExecutionResult<Collection> { Collection::one(0) }
+
+fn main() {}
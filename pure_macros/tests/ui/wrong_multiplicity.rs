@@ -0,0 +1,9 @@
+use pure_macros::pure_native;
+
+#[pure_native("meta::pure::functions::string::joinStrings(String[*]):String[1]")]
+fn join_strings(strings: String) -> PureExecutionResult<Collection> { Collection::one(strings) }
+
+#[pure_native("meta::pure::functions::string::toString(Any[1]):String[1]")]
+fn to_string(value: Option<&Value>) -> PureExecutionResult<Collection> { Collection::one(value.is_some()) }
+
+fn main() {}
@@ -623,7 +623,7 @@ mod tests
     #[test]
     fn can_handle_all_utf_8_encoding_lengths() -> FlatDataResult<()>
     {
-        let reader: Box<&[u8]> = Box::new(&[b'\x24', b'\xC2', b'\xA3', b'\xE2', b'\x82', b'\xAC', b'\xF0', b'\x90', b'\x8D', b'\x88']);
+        let reader: Box<&[u8]> = Box::new(b"\x24\xC2\xA3\xE2\x82\xAC\xF0\x90\x8D\x88");
 
         let mut cursor = CharCursor::open(10, 20, reader)?;
         assert_eq!('$', cursor.current_char().unwrap());
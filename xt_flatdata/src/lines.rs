@@ -299,13 +299,11 @@ mod simple
             let mut lno = Box::new(0);
             let mut lines = SimpleLineReader::new(cursor, eol, &mut lno);
 
-            let mut expected_num = 1;
-            for expected_line in expected.iter()
+            for (expected_num, expected_line) in (1..).zip(expected.iter())
             {
                 let line = lines.read_line()?.unwrap();
                 assert_eq!(*expected_line, line.text);
                 assert_eq!(expected_num, line.line_number);
-                expected_num += 1;
             }
             assert!(lines.read_line()?.is_none());
 
@@ -776,14 +774,12 @@ mod delimited
             let mut lno = Box::new(0);
             let mut lines = DelimitedLineReader::new(cursor, eol, format, &mut lno);
 
-            let mut expected_num = 1;
-            for expected_line in expected.iter()
+            for (expected_num, expected_line) in (1..).zip(expected.iter())
             {
                 let line = lines.read_line()?.unwrap();
                 assert_eq!(*expected_line.text, line.text);
                 assert_eq!(*expected_line.values, line.values);
                 assert_eq!(expected_num, line.line_number);
-                expected_num += 1;
             }
             assert!(lines.read_line()?.is_none());
 